@@ -0,0 +1,74 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Measures the capture-to-read latency of `LatestFrameReader` on the first camera, which should stay below one frame interval.
+// Run with `cargo bench --bench latest_frame_latency --features input-native,output-threaded`.
+
+use nokhwa::{
+    nokhwa_initialize,
+    pixel_format::RgbFormat,
+    utils::{CameraIndex, RequestedFormat, RequestedFormatType},
+    CallbackCamera,
+};
+use std::time::Duration;
+
+const FRAMES: usize = 300;
+
+fn main() {
+    nokhwa_initialize(|_| {});
+    let format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
+    let mut camera = match CallbackCamera::new(CameraIndex::Index(0), format, |_| {}) {
+        Ok(camera) => camera,
+        Err(why) => {
+            eprintln!("No camera to benchmark: {why}");
+            return;
+        }
+    };
+    let mut reader = camera.latest_frame_reader().unwrap();
+    let notify = camera.frame_notify();
+    camera.open_stream().unwrap();
+    let frame_interval = Duration::from_secs(1) / camera.frame_rate().unwrap().max(1);
+
+    let mut latencies = Vec::with_capacity(FRAMES);
+    let mut skipped = 0;
+    while latencies.len() < FRAMES {
+        if !notify.wait(Duration::from_secs(1)) {
+            eprintln!("The camera stopped delivering frames");
+            return;
+        }
+        if let Some(frame) = reader.poll_frame() {
+            if let Some(captured_at) = frame.captured_at() {
+                latencies.push(captured_at.elapsed());
+            }
+            skipped += reader.generation() - frame.generation();
+        }
+    }
+    camera.stop_stream().unwrap();
+
+    latencies.sort_unstable();
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+    println!("frame interval: {frame_interval:?}");
+    println!(
+        "capture-to-read latency over {FRAMES} frames: median {:?}, p99 {:?}, max {:?}",
+        percentile(50),
+        percentile(99),
+        percentile(100)
+    );
+    println!("frames skipped by the reader: {skipped}");
+    if percentile(99) >= frame_interval {
+        println!("p99 latency is above one frame interval");
+    }
+}
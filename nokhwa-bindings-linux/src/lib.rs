@@ -20,17 +20,25 @@ mod internal {
     use nokhwa_core::{
         buffer::Buffer,
         error::NokhwaError,
+        frame_format::BayerPattern,
         traits::CaptureTrait,
         types::{
             ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo,
-            ControlValueDescription, ControlValueSetter, FrameFormat, KnownCameraControl,
-            KnownCameraControlFlag, RequestedFormat, RequestedFormatType, Resolution,
+            ControlChangeMechanism, ControlUnit, ControlValueDescription, ControlValueSetter,
+            ExposureMode, FocusMode, FrameFormat, KnownCameraControl, KnownCameraControlFlag, RequestedFormat, RequestedFormatType, Resolution,
         },
     };
     use std::{
         borrow::Cow,
         collections::HashMap,
         io::{self, ErrorKind},
+        mem::{size_of, MaybeUninit},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{channel, Receiver},
+            Arc,
+        },
+        thread::JoinHandle,
     };
     use v4l::{
         control::{Control, Flags, Type, Value},
@@ -42,10 +50,14 @@ mod internal {
         Device, Format, FourCC,
     };
     use v4l2_sys_mit::{
-        V4L2_CID_BACKLIGHT_COMPENSATION, V4L2_CID_BRIGHTNESS, V4L2_CID_CONTRAST, V4L2_CID_EXPOSURE,
-        V4L2_CID_FOCUS_RELATIVE, V4L2_CID_GAIN, V4L2_CID_GAMMA, V4L2_CID_HUE,
-        V4L2_CID_IRIS_RELATIVE, V4L2_CID_PAN_RELATIVE, V4L2_CID_SATURATION, V4L2_CID_SHARPNESS,
-        V4L2_CID_TILT_RELATIVE, V4L2_CID_WHITE_BALANCE_TEMPERATURE, V4L2_CID_ZOOM_RELATIVE,
+        V4L2_CID_AUTO_WHITE_BALANCE, V4L2_CID_BACKLIGHT_COMPENSATION, V4L2_CID_BLUE_BALANCE,
+        V4L2_CID_BRIGHTNESS, V4L2_CID_CONTRAST, V4L2_CID_EXPOSURE_ABSOLUTE, V4L2_CID_EXPOSURE_AUTO,
+        V4L2_CID_EXPOSURE_AUTO_PRIORITY, V4L2_CID_FOCUS_ABSOLUTE, V4L2_CID_FOCUS_AUTO,
+        V4L2_CID_GAIN, V4L2_CID_GAMMA, V4L2_CID_HUE, V4L2_CID_IRIS_ABSOLUTE, V4L2_CID_PAN_ABSOLUTE,
+        V4L2_CID_PAN_RELATIVE, V4L2_CID_POWER_LINE_FREQUENCY, V4L2_CID_RED_BALANCE,
+        V4L2_CID_SATURATION, V4L2_CID_SHARPNESS, V4L2_CID_TILT_ABSOLUTE, V4L2_CID_TILT_RELATIVE,
+        V4L2_CID_WHITE_BALANCE_TEMPERATURE, V4L2_CID_ZOOM_ABSOLUTE, V4L2_CID_ZOOM_RELATIVE,
+        V4L2_EVENT_CTRL, v4l2_event, v4l2_event_subscription,
     };
 
     /// Attempts to convert a [`KnownCameraControl`] into a V4L2 Control ID.
@@ -59,15 +71,25 @@ mod internal {
             KnownCameraControl::Saturation => V4L2_CID_SATURATION,
             KnownCameraControl::Sharpness => V4L2_CID_SHARPNESS,
             KnownCameraControl::Gamma => V4L2_CID_GAMMA,
+            KnownCameraControl::WhiteBalanceAuto => V4L2_CID_AUTO_WHITE_BALANCE,
             KnownCameraControl::WhiteBalance => V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+            KnownCameraControl::WhiteBalanceRed => V4L2_CID_RED_BALANCE,
+            KnownCameraControl::WhiteBalanceBlue => V4L2_CID_BLUE_BALANCE,
             KnownCameraControl::BacklightComp => V4L2_CID_BACKLIGHT_COMPENSATION,
             KnownCameraControl::Gain => V4L2_CID_GAIN,
-            KnownCameraControl::Pan => V4L2_CID_PAN_RELATIVE,
-            KnownCameraControl::Tilt => V4L2_CID_TILT_RELATIVE,
-            KnownCameraControl::Zoom => V4L2_CID_ZOOM_RELATIVE,
-            KnownCameraControl::Exposure => V4L2_CID_EXPOSURE,
-            KnownCameraControl::Iris => V4L2_CID_IRIS_RELATIVE,
-            KnownCameraControl::Focus => V4L2_CID_FOCUS_RELATIVE,
+            KnownCameraControl::PowerLineFrequency => V4L2_CID_POWER_LINE_FREQUENCY,
+            KnownCameraControl::Pan => V4L2_CID_PAN_ABSOLUTE,
+            KnownCameraControl::Tilt => V4L2_CID_TILT_ABSOLUTE,
+            KnownCameraControl::PanRelative => V4L2_CID_PAN_RELATIVE,
+            KnownCameraControl::TiltRelative => V4L2_CID_TILT_RELATIVE,
+            KnownCameraControl::Zoom => V4L2_CID_ZOOM_ABSOLUTE,
+            KnownCameraControl::ZoomRelative => V4L2_CID_ZOOM_RELATIVE,
+            KnownCameraControl::ExposureMode => V4L2_CID_EXPOSURE_AUTO,
+            KnownCameraControl::Exposure => V4L2_CID_EXPOSURE_ABSOLUTE,
+            KnownCameraControl::ExposurePriority => V4L2_CID_EXPOSURE_AUTO_PRIORITY,
+            KnownCameraControl::Iris => V4L2_CID_IRIS_ABSOLUTE,
+            KnownCameraControl::FocusMode => V4L2_CID_FOCUS_AUTO,
+            KnownCameraControl::Focus => V4L2_CID_FOCUS_ABSOLUTE,
             KnownCameraControl::Other(id) => id as u32,
         }
     }
@@ -83,19 +105,97 @@ mod internal {
             V4L2_CID_SATURATION => KnownCameraControl::Saturation,
             V4L2_CID_SHARPNESS => KnownCameraControl::Sharpness,
             V4L2_CID_GAMMA => KnownCameraControl::Gamma,
+            V4L2_CID_AUTO_WHITE_BALANCE => KnownCameraControl::WhiteBalanceAuto,
             V4L2_CID_WHITE_BALANCE_TEMPERATURE => KnownCameraControl::WhiteBalance,
+            V4L2_CID_RED_BALANCE => KnownCameraControl::WhiteBalanceRed,
+            V4L2_CID_BLUE_BALANCE => KnownCameraControl::WhiteBalanceBlue,
             V4L2_CID_BACKLIGHT_COMPENSATION => KnownCameraControl::BacklightComp,
             V4L2_CID_GAIN => KnownCameraControl::Gain,
-            V4L2_CID_PAN_RELATIVE => KnownCameraControl::Pan,
-            V4L2_CID_TILT_RELATIVE => KnownCameraControl::Tilt,
-            V4L2_CID_ZOOM_RELATIVE => KnownCameraControl::Zoom,
-            V4L2_CID_EXPOSURE => KnownCameraControl::Exposure,
-            V4L2_CID_IRIS_RELATIVE => KnownCameraControl::Iris,
-            V4L2_CID_FOCUS_RELATIVE => KnownCameraControl::Focus,
+            V4L2_CID_POWER_LINE_FREQUENCY => KnownCameraControl::PowerLineFrequency,
+            V4L2_CID_PAN_ABSOLUTE => KnownCameraControl::Pan,
+            V4L2_CID_TILT_ABSOLUTE => KnownCameraControl::Tilt,
+            V4L2_CID_PAN_RELATIVE => KnownCameraControl::PanRelative,
+            V4L2_CID_TILT_RELATIVE => KnownCameraControl::TiltRelative,
+            V4L2_CID_ZOOM_ABSOLUTE => KnownCameraControl::Zoom,
+            V4L2_CID_ZOOM_RELATIVE => KnownCameraControl::ZoomRelative,
+            V4L2_CID_EXPOSURE_AUTO => KnownCameraControl::ExposureMode,
+            V4L2_CID_EXPOSURE_ABSOLUTE => KnownCameraControl::Exposure,
+            V4L2_CID_EXPOSURE_AUTO_PRIORITY => KnownCameraControl::ExposurePriority,
+            V4L2_CID_IRIS_ABSOLUTE => KnownCameraControl::Iris,
+            V4L2_CID_FOCUS_AUTO => KnownCameraControl::FocusMode,
+            V4L2_CID_FOCUS_ABSOLUTE => KnownCameraControl::Focus,
             id => KnownCameraControl::Other(id as u128),
         }
     }
 
+    // `V4L2_CID_EXPOSURE_AUTO` is a menu index (`V4L2_EXPOSURE_AUTO`, `V4L2_EXPOSURE_MANUAL`, `V4L2_EXPOSURE_SHUTTER_PRIORITY`,
+    // `V4L2_EXPOSURE_APERTURE_PRIORITY`), while `ExposureMode` uses the UVC bitmap values.
+    fn v4l2_exposure_mode(index: i64) -> Option<ExposureMode> {
+        match index {
+            0 => Some(ExposureMode::Auto),
+            1 => Some(ExposureMode::Manual),
+            2 => Some(ExposureMode::ShutterPriority),
+            3 => Some(ExposureMode::AperturePriority),
+            _ => None,
+        }
+    }
+
+    fn exposure_mode_to_v4l2(mode: ExposureMode) -> i64 {
+        match mode {
+            ExposureMode::Auto => 0,
+            ExposureMode::Manual => 1,
+            ExposureMode::ShutterPriority => 2,
+            ExposureMode::AperturePriority => 3,
+        }
+    }
+
+    fn v4l2_focus_mode(auto: bool) -> FocusMode {
+        if auto {
+            FocusMode::Auto
+        } else {
+            FocusMode::Manual
+        }
+    }
+
+    // Presents the mode controls with the encodings of `KnownCameraControl::ExposureMode` and `KnownCameraControl::FocusMode`.
+    fn v4l2_mode_description(
+        id: u32,
+        description: ControlValueDescription,
+    ) -> ControlValueDescription {
+        match (id, description) {
+            (
+                V4L2_CID_EXPOSURE_AUTO,
+                ControlValueDescription::Menu {
+                    value,
+                    options,
+                    default,
+                },
+            ) => {
+                let to_core = |index: i64| v4l2_exposure_mode(index).map_or(index, i64::from);
+                ControlValueDescription::Menu {
+                    value: to_core(value),
+                    options: options
+                        .into_iter()
+                        .filter_map(|(index, _)| v4l2_exposure_mode(index))
+                        .map(|mode| (mode.into(), mode.to_string()))
+                        .collect(),
+                    default: to_core(default),
+                }
+            }
+            (V4L2_CID_FOCUS_AUTO, ControlValueDescription::Boolean { value, default }) => {
+                ControlValueDescription::Menu {
+                    value: v4l2_focus_mode(value).into(),
+                    options: [FocusMode::Manual, FocusMode::Auto]
+                        .into_iter()
+                        .map(|mode| (mode.into(), mode.to_string()))
+                        .collect(),
+                    default: v4l2_focus_mode(default).into(),
+                }
+            }
+            (_, description) => description,
+        }
+    }
+
     /// query v4l2 cameras
     #[allow(clippy::unnecessary_wraps)]
     #[allow(clippy::cast_possible_truncation)]
@@ -122,12 +222,39 @@ mod internal {
     /// To see what this does, please see [`CaptureTrait`].
     /// # Quirks
     /// - Calling [`set_resolution()`](CaptureTrait::set_resolution), [`set_frame_rate()`](CaptureTrait::set_frame_rate), or [`set_frame_format()`](CaptureTrait::set_frame_format) each internally calls [`set_camera_format()`](CaptureTrait::set_camera_format).
+    /// - Controls the driver adds on top of the standard ones are listed as [`KnownCameraControl::Other`], holding their V4L2 Control ID. Control classes are not listed.
+    /// - Read only, write only, disabled, inactive, and volatile controls are marked in their [`KnownCameraControlFlag`]s.
+    /// - Control changes are delivered as V4L2 control events through [`subscribe_control_changes()`](CaptureTrait::subscribe_control_changes). Changes you make yourself are not reported.
+    /// - The device is `Send`, so it can be moved to another thread.
     pub struct V4LCaptureDevice<'a> {
         init: bool,
         camera_format: Option<CameraFormat>,
         camera_info: CameraInfo,
         device: Device,
         stream_handle: Option<MmapStream<'a>>,
+        control_events: Option<ControlEventListener>,
+    }
+
+    // `_IOC(dir, 'V', nr, size)`, see `linux/ioctl.h`
+    const fn vidioc(dir: u32, nr: u32, size: usize) -> u32 {
+        (dir << 30) | ((size as u32) << 16) | ((b'V' as u32) << 8) | nr
+    }
+    const VIDIOC_DQEVENT: u32 = vidioc(2, 89, size_of::<v4l2_event>());
+    const VIDIOC_SUBSCRIBE_EVENT: u32 = vidioc(1, 90, size_of::<v4l2_event_subscription>());
+
+    // Waits for `V4L2_EVENT_CTRL` events on the device and forwards the control they are for.
+    struct ControlEventListener {
+        die_bool: Arc<AtomicBool>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl Drop for ControlEventListener {
+        fn drop(&mut self) {
+            self.die_bool.store(true, Ordering::SeqCst);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
     }
 
     impl<'a> V4LCaptureDevice<'a> {
@@ -421,19 +548,36 @@ mod internal {
                     error: why.to_string(),
                 })?
                 .into_iter()
+                // control classes only group the controls that follow them
+                .filter(|desc| !matches!(desc.typ, Type::CtrlClass))
                 .map(|desc| {
                     let id_as_kcc = id_to_known_camera_control(desc.id);
-                    let ctrl_current = self.device.control(desc.id)?.value;
+                    // buttons have no value to read
+                    let ctrl_current = match desc.typ {
+                        Type::Button => Value::None,
+                        _ => self.device.control(desc.id)?.value,
+                    };
 
                     let ctrl_value_desc = match (desc.typ, ctrl_current) {
+                        (Type::Menu | Type::IntegerMenu, Value::Integer(current)) => {
+                            // the driver already skips the indices it does not accept when it fills `items`
+                            ControlValueDescription::Menu {
+                                value: current,
+                                options: desc
+                                    .items
+                                    .iter()
+                                    .flatten()
+                                    .map(|(index, item)| (i64::from(*index), item.to_string()))
+                                    .collect(),
+                                default: desc.default,
+                            }
+                        }
                         (
                             Type::Integer
                             | Type::Integer64
-                            | Type::Menu
                             | Type::U8
                             | Type::U16
-                            | Type::U32
-                            | Type::IntegerMenu,
+                            | Type::U32,
                             Value::Integer(current),
                         ) => ControlValueDescription::IntegerRange {
                             min: desc.minimum as i64,
@@ -453,6 +597,7 @@ mod internal {
                             value: current,
                             default: None,
                         },
+                        (Type::Button, _) => ControlValueDescription::None,
                         _ => {
                             return Err(io::Error::new(
                                 ErrorKind::Unsupported,
@@ -493,18 +638,26 @@ mod internal {
                     .collect::<Option<Vec<KnownCameraControlFlag>>>()
                     .unwrap_or_default();
 
-                    Ok(CameraControl::new(
+                    let mut camera_control = CameraControl::new(
                         id_as_kcc,
                         desc.name,
-                        ctrl_value_desc,
+                        v4l2_mode_description(desc.id, ctrl_value_desc),
                         flags_vec,
                         !desc.flags.intersects(Flags::INACTIVE),
-                    ))
+                    );
+                    // the units V4L2 defines, `V4L2_CID_FOCUS_ABSOLUTE` is up to the driver
+                    camera_control.set_unit(match desc.id {
+                        V4L2_CID_EXPOSURE_ABSOLUTE => ControlUnit::Microseconds(100),
+                        V4L2_CID_WHITE_BALANCE_TEMPERATURE => ControlUnit::Kelvin,
+                        V4L2_CID_PAN_ABSOLUTE | V4L2_CID_TILT_ABSOLUTE => ControlUnit::ArcSeconds,
+                        _ => ControlUnit::Unknown,
+                    });
+                    Ok(camera_control)
                 })
                 .filter(Result::is_ok)
                 .collect::<Result<Vec<CameraControl>, io::Error>>()
                 .map_err(|x| NokhwaError::GetPropertyError {
-                    property: "www".to_string(),
+                    property: "V4L2 Controls".to_string(),
                     error: x.to_string(),
                 })
         }
@@ -514,13 +667,33 @@ mod internal {
             id: KnownCameraControl,
             value: ControlValueSetter,
         ) -> Result<(), NokhwaError> {
-            let conv_value = match value.clone() {
-                ControlValueSetter::None => Value::None,
-                ControlValueSetter::Integer(i) => Value::Integer(i),
-                ControlValueSetter::Boolean(b) => Value::Boolean(b),
-                ControlValueSetter::String(s) => Value::String(s),
-                ControlValueSetter::Bytes(b) => Value::CompoundU8(b),
-                v => {
+            let current = self.camera_control(id)?;
+            if let ControlValueDescription::Menu { .. } = current.description() {
+                if !current.description().verify_setter(&value) {
+                    return Err(NokhwaError::SetPropertyError {
+                        property: id.to_string(),
+                        value: value.to_string(),
+                        error: "Not one of the menu options".to_string(),
+                    });
+                }
+            }
+
+            let conv_value = match (id, value.clone()) {
+                (KnownCameraControl::ExposureMode, ControlValueSetter::EnumValue(mode)) => {
+                    let mode = ExposureMode::try_from(mode)?;
+                    Value::Integer(exposure_mode_to_v4l2(mode))
+                }
+                (KnownCameraControl::FocusMode, ControlValueSetter::EnumValue(mode)) => {
+                    Value::Boolean(FocusMode::try_from(mode)? == FocusMode::Auto)
+                }
+                (_, ControlValueSetter::None) => Value::None,
+                (_, ControlValueSetter::Integer(i) | ControlValueSetter::EnumValue(i)) => {
+                    Value::Integer(i)
+                }
+                (_, ControlValueSetter::Boolean(b)) => Value::Boolean(b),
+                (_, ControlValueSetter::String(s)) => Value::String(s),
+                (_, ControlValueSetter::Bytes(b)) => Value::CompoundU8(b),
+                (_, v) => {
                     return Err(NokhwaError::SetPropertyError {
                         property: id.to_string(),
                         value: v.to_string(),
@@ -551,6 +724,73 @@ mod internal {
             Ok(())
         }
 
+        fn control_change_mechanism(&self) -> ControlChangeMechanism {
+            ControlChangeMechanism::Events
+        }
+
+        fn subscribe_control_changes(
+            &mut self,
+        ) -> Result<Receiver<KnownCameraControl>, NokhwaError> {
+            let handle = self.device.handle();
+            let fd = handle.fd();
+            let to_error = |why: io::Error| NokhwaError::GetPropertyError {
+                property: "V4L2 Control Events".to_string(),
+                error: why.to_string(),
+            };
+
+            for desc in self.device.query_controls().map_err(to_error)? {
+                // SAFETY: `v4l2_event_subscription` is plain data, all zeroes is a valid value.
+                let mut subscription: v4l2_event_subscription = unsafe { std::mem::zeroed() };
+                subscription.type_ = V4L2_EVENT_CTRL;
+                subscription.id = desc.id;
+                if unsafe { libc::ioctl(fd, VIDIOC_SUBSCRIBE_EVENT as _, &mut subscription) } != 0 {
+                    return Err(to_error(io::Error::last_os_error()));
+                }
+            }
+
+            let (sender, receiver) = channel();
+            let die_bool = Arc::new(AtomicBool::new(false));
+            let thread_die_bool = die_bool.clone();
+            let join_handle = std::thread::spawn(move || {
+                // keeps the device open for as long as we listen on it
+                let handle = handle;
+                let mut pollfd = libc::pollfd {
+                    fd: handle.fd(),
+                    events: libc::POLLPRI,
+                    revents: 0,
+                };
+                while !thread_die_bool.load(Ordering::SeqCst) {
+                    let ready = unsafe { libc::poll(&mut pollfd, 1, 100) };
+                    // the device is gone
+                    if pollfd.revents & (libc::POLLERR | libc::POLLHUP) != 0 {
+                        break;
+                    }
+                    if ready <= 0 || pollfd.revents & libc::POLLPRI == 0 {
+                        continue;
+                    }
+                    let mut event = MaybeUninit::<v4l2_event>::zeroed();
+                    if unsafe { libc::ioctl(handle.fd(), VIDIOC_DQEVENT as _, event.as_mut_ptr()) }
+                        != 0
+                    {
+                        continue;
+                    }
+                    let event = unsafe { event.assume_init() };
+                    if event.type_ == V4L2_EVENT_CTRL
+                        && sender.send(id_to_known_camera_control(event.id)).is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+
+            // replacing an old listener stops it, closing its receiver
+            self.control_events = Some(ControlEventListener {
+                die_bool,
+                handle: Some(join_handle),
+            });
+            Ok(receiver)
+        }
+
         fn open_stream(&mut self) -> Result<(), NokhwaError> {
             let stream = match MmapStream::new(&self.device, v4l::buffer::Type::VideoCapture) {
                 Ok(s) => s,
@@ -612,6 +852,18 @@ mod internal {
             "MPG1" => Some(FrameFormat::Mpeg1),
             "MPG2" => Some(FrameFormat::Mpeg2),
             "MPG4" => Some(FrameFormat::Mpeg4),
+            "RGGB" => Some(FrameFormat::Bayer8(BayerPattern::Rggb)),
+            "BA81" => Some(FrameFormat::Bayer8(BayerPattern::Bggr)),
+            "GRBG" => Some(FrameFormat::Bayer8(BayerPattern::Grbg)),
+            "GBRG" => Some(FrameFormat::Bayer8(BayerPattern::Gbrg)),
+            "pRAA" => Some(FrameFormat::Bayer10Packed(BayerPattern::Rggb)),
+            "pBAA" => Some(FrameFormat::Bayer10Packed(BayerPattern::Bggr)),
+            "pgAA" => Some(FrameFormat::Bayer10Packed(BayerPattern::Grbg)),
+            "pGAA" => Some(FrameFormat::Bayer10Packed(BayerPattern::Gbrg)),
+            "pRCC" => Some(FrameFormat::Bayer12Packed(BayerPattern::Rggb)),
+            "pBCC" => Some(FrameFormat::Bayer12Packed(BayerPattern::Bggr)),
+            "pgCC" => Some(FrameFormat::Bayer12Packed(BayerPattern::Grbg)),
+            "pGCC" => Some(FrameFormat::Bayer12Packed(BayerPattern::Gbrg)),
             _ => None,
         }
     }
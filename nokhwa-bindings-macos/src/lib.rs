@@ -146,6 +146,13 @@ mod internal {
             pub redGain: f32,
         }
 
+        #[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+        #[repr(C)]
+        pub struct AVCaptureWhiteBalanceTemperatureAndTintValues {
+            pub temperature: f32,
+            pub tint: f32,
+        }
+
         pub type CVBufferRef = *mut __CVBuffer;
 
         pub type CVImageBufferRef = CVBufferRef;
@@ -198,12 +205,12 @@ mod internal {
     use crate::core_media::{
         dispatch_queue_create, AVCaptureExposureDurationCurrent,
         AVCaptureExposureTargetBiasCurrent, AVCaptureISOCurrent, AVCaptureWhiteBalanceGains,
-        AVMediaTypeAudio, AVMediaTypeClosedCaption, AVMediaTypeDepthData, AVMediaTypeMetadata,
-        AVMediaTypeMetadataObject, AVMediaTypeMuxed, AVMediaTypeSubtitle, AVMediaTypeText,
-        AVMediaTypeTimecode, AVMediaTypeVideo, CGPoint, CMSampleBufferGetImageBuffer,
-        CMVideoFormatDescriptionGetDimensions, CVImageBufferRef, CVPixelBufferGetBaseAddress,
-        CVPixelBufferGetDataSize, CVPixelBufferLockBaseAddress, CVPixelBufferUnlockBaseAddress,
-        NSObject, OSType,
+        AVCaptureWhiteBalanceTemperatureAndTintValues, AVMediaTypeAudio, AVMediaTypeClosedCaption,
+        AVMediaTypeDepthData, AVMediaTypeMetadata, AVMediaTypeMetadataObject, AVMediaTypeMuxed,
+        AVMediaTypeSubtitle, AVMediaTypeText, AVMediaTypeTimecode, AVMediaTypeVideo, CGPoint,
+        CMSampleBufferGetImageBuffer, CMTimeMake, CMVideoFormatDescriptionGetDimensions,
+        CVImageBufferRef, CVPixelBufferGetBaseAddress, CVPixelBufferGetDataSize,
+        CVPixelBufferLockBaseAddress, CVPixelBufferUnlockBaseAddress, NSObject, OSType,
     };
 
     use block::ConcreteBlock;
@@ -225,9 +232,9 @@ mod internal {
     use nokhwa_core::{
         error::NokhwaError,
         types::{
-            ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo,
-            ControlValueDescription, ControlValueSetter, FrameFormat, KnownCameraControl,
-            KnownCameraControlFlag, Resolution,
+            ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlUnit,
+            ControlValueDescription, ControlValueSetter, ExposureMode, FocusMode, FrameFormat,
+            KnownCameraControl, KnownCameraControlFlag, Resolution,
         },
     };
     use objc::runtime::objc_getClass;
@@ -352,6 +359,54 @@ mod internal {
         Ok(out_vec)
     }
 
+    #[allow(clippy::cast_possible_truncation)]
+    fn cmtime_to_micros(time: CMTime) -> i64 {
+        if time.timescale == 0 {
+            return 0;
+        }
+        (i128::from(time.value) * 1_000_000 / i128::from(time.timescale)) as i64
+    }
+
+    fn micros_to_cmtime(micros: i64) -> CMTime {
+        unsafe { CMTimeMake(micros, 1_000_000) }
+    }
+
+    // The focus, exposure and white balance modes all use 1 for the one-shot automatic mode and 2 for the continuous one.
+    fn automatic_mode(continuous_supported: BOOL) -> NSInteger {
+        if continuous_supported == YES {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn settable_control<'a>(
+        controls: &BTreeMap<KnownCameraControl, &'a CameraControl>,
+        id: KnownCameraControl,
+        value: &ControlValueSetter,
+    ) -> Result<&'a CameraControl, NokhwaError> {
+        let error = |error: &str| NokhwaError::SetPropertyError {
+            property: id.to_string(),
+            value: value.to_string(),
+            error: error.to_string(),
+        };
+        let control = controls
+            .get(&id)
+            .copied()
+            .ok_or_else(|| error("Control does not exist"))?;
+
+        if control.flag().contains(&KnownCameraControlFlag::ReadOnly) {
+            return Err(error("Read Only"));
+        }
+        if control.flag().contains(&KnownCameraControlFlag::Disabled) {
+            return Err(error("Disabled"));
+        }
+        if !control.description().verify_setter(value) {
+            return Err(error("Failed to verify value"));
+        }
+        Ok(control)
+    }
+
     fn compare_ns_string(this: *mut Object, other: core_media::NSString) -> bool {
         unsafe {
             let equal: BOOL = msg_send![this, isEqualToString: other];
@@ -931,7 +986,7 @@ mod internal {
             }
         }
 
-        pub fn lock(&self) -> Result<(), NokhwaError> {
+        pub fn lock(&mut self) -> Result<(), NokhwaError> {
             if self.locked {
                 return Ok(());
             }
@@ -958,9 +1013,23 @@ mod internal {
                     error: "Lock Rejected".to_string(),
                 });
             }
+            self.locked = true;
             Ok(())
         }
 
+        // The gains have to be between 1.0 and `maxWhiteBalanceGain`, or AVFoundation throws.
+        fn lock_white_balance_gains(&mut self, gains: AVCaptureWhiteBalanceGains) {
+            let max: f32 = unsafe { msg_send![self.inner, maxWhiteBalanceGain] };
+            let clamped = AVCaptureWhiteBalanceGains {
+                blueGain: gains.blueGain.clamp(1.0, max),
+                greenGain: gains.greenGain.clamp(1.0, max),
+                redGain: gains.redGain.clamp(1.0, max),
+            };
+            let _: () = unsafe {
+                msg_send![self.inner, setWhiteBalanceModeLockedWithDeviceWhiteBalanceGains:clamped completionHandler:Nil]
+            };
+        }
+
         pub fn unlock(&mut self) {
             if self.locked {
                 self.locked = false;
@@ -971,6 +1040,12 @@ mod internal {
         // thank you ffmpeg
         pub fn set_all(&mut self, descriptor: CameraFormat) -> Result<(), NokhwaError> {
             self.lock()?;
+            let result = self.set_all_locked(descriptor);
+            self.unlock();
+            result
+        }
+
+        fn set_all_locked(&mut self, descriptor: CameraFormat) -> Result<(), NokhwaError> {
             let format_list = try_ns_arr_to_vec::<AVCaptureDeviceFormat, NokhwaError>(unsafe {
                 msg_send![self.inner, formats]
             })?;
@@ -1024,17 +1099,19 @@ mod internal {
             let _: () = unsafe {
                 msg_send![self.inner, setValue:min_frame_duration forKey:active_video_max_frame_duration]
             };
-            self.unlock();
             Ok(())
         }
 
         // 0 => Focus POI
-        // 1 => Focus Manual Setting
         // 2 => Exposure POI
         // 3 => Exposure Face Driven
         // 4 => Exposure Target Bias
-        // 5 => Exposure ISO
-        // 6 => Exposure Duration
+        // 5 => Torch Mode
+        // 6 => Distortion Correction
+        // 7 => White Balance Gains (red, green, blue)
+        //
+        // Controls the device does not support are left out. The locked modes are the manual ones, the auto and continuous
+        // modes are automatic (setting to automatic picks continuous where supported).
         pub fn get_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
             let active_format: *mut Object = unsafe { msg_send![self.inner, activeFormat] };
 
@@ -1050,77 +1127,78 @@ mod internal {
                 unsafe { msg_send![self.inner, isFocusModeSupported:NSInteger::from(2)] };
 
             {
-                let mut supported_focus_values = vec![];
+                let mut supported_focus_modes = vec![];
 
                 if focus_locked == YES {
-                    supported_focus_values.push(0)
+                    supported_focus_modes.push(FocusMode::Manual);
                 }
-                if focus_auto == YES {
-                    supported_focus_values.push(1)
+                if focus_auto == YES || focus_continuous == YES {
+                    supported_focus_modes.push(FocusMode::Auto);
                 }
-                if focus_continuous == YES {
-                    supported_focus_values.push(2)
+
+                if !supported_focus_modes.is_empty() {
+                    let focus_mode = if focus_current == 0 {
+                        FocusMode::Manual
+                    } else {
+                        FocusMode::Auto
+                    };
+
+                    controls.push(CameraControl::new(
+                        KnownCameraControl::FocusMode,
+                        "FocusMode".to_string(),
+                        ControlValueDescription::Menu {
+                            value: focus_mode.into(),
+                            options: supported_focus_modes
+                                .into_iter()
+                                .map(|mode| (mode.into(), mode.to_string()))
+                                .collect(),
+                            default: focus_mode.into(),
+                        },
+                        vec![],
+                        true,
+                    ));
                 }
+            }
+
+            let focus_poi_supported: BOOL =
+                unsafe { msg_send![self.inner, isFocusPointOfInterestSupported] };
+
+            if focus_poi_supported == YES {
+                let focus_poi: CGPoint = unsafe { msg_send![self.inner, focusPointOfInterest] };
 
                 controls.push(CameraControl::new(
-                    KnownCameraControl::Focus,
-                    "FocusMode".to_string(),
-                    ControlValueDescription::Enum {
-                        value: focus_current,
-                        possible: supported_focus_values,
-                        default: focus_current,
+                    KnownCameraControl::Other(0),
+                    "FocusPointOfInterest".to_string(),
+                    ControlValueDescription::Point {
+                        value: (focus_poi.x as f64, focus_poi.y as f64),
+                        default: (0.5, 0.5),
                     },
                     vec![],
-                    true,
+                    focus_auto == YES || focus_continuous == YES,
                 ));
             }
 
-            let focus_poi_supported: BOOL =
-                unsafe { msg_send![self.inner, isFocusPointOfInterestSupported] };
-            let focus_poi: CGPoint = unsafe { msg_send![self.inner, focusPointOfInterest] };
-
-            controls.push(CameraControl::new(
-                KnownCameraControl::Other(0),
-                "FocusPointOfInterest".to_string(),
-                ControlValueDescription::Point {
-                    value: (focus_poi.x as f64, focus_poi.y as f64),
-                    default: (0.5, 0.5),
-                },
-                if focus_poi_supported == NO {
-                    vec![
-                        KnownCameraControlFlag::Disabled,
-                        KnownCameraControlFlag::ReadOnly,
-                    ]
-                } else {
-                    vec![]
-                },
-                focus_auto == YES || focus_continuous == YES,
-            ));
-
             let focus_manual: BOOL =
                 unsafe { msg_send![self.inner, isLockingFocusWithCustomLensPositionSupported] };
-            let focus_lenspos: f32 = unsafe { msg_send![self.inner, lensPosition] };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Other(1),
-                "FocusManualLensPosition".to_string(),
-                ControlValueDescription::FloatRange {
-                    min: 0.0,
-                    max: 1.0,
-                    value: focus_lenspos as f64,
-                    step: f64::MIN_POSITIVE,
-                    default: 1.0,
-                },
-                if focus_manual == YES {
-                    vec![]
-                } else {
-                    vec![
-                        KnownCameraControlFlag::Disabled,
-                        KnownCameraControlFlag::ReadOnly,
-                    ]
-                },
-                focus_manual == YES,
-            ));
+            if focus_manual == YES {
+                let focus_lenspos: f32 = unsafe { msg_send![self.inner, lensPosition] };
+
+                // the lens position from 0.0 (closest) to 1.0 (furthest), not a distance
+                controls.push(CameraControl::new(
+                    KnownCameraControl::Focus,
+                    "FocusManualLensPosition".to_string(),
+                    ControlValueDescription::FloatRange {
+                        min: 0.0,
+                        max: 1.0,
+                        value: focus_lenspos as f64,
+                        step: f64::MIN_POSITIVE,
+                        default: 1.0,
+                    },
+                    vec![],
+                    focus_current == 0,
+                ));
+            }
 
             // get exposures
             let exposure_current: NSInteger = unsafe { msg_send![self.inner, exposureMode] };
@@ -1134,82 +1212,81 @@ mod internal {
                 unsafe { msg_send![self.inner, isExposureModeSupported:NSInteger::from(3)] };
 
             {
-                let mut supported_exposure_values = vec![];
+                let mut supported_exposure_modes = vec![];
 
-                if exposure_locked == YES {
-                    supported_exposure_values.push(0);
+                if exposure_locked == YES || exposure_custom == YES {
+                    supported_exposure_modes.push(ExposureMode::Manual);
                 }
-                if exposure_auto == YES {
-                    supported_exposure_values.push(1);
+                if exposure_auto == YES || exposure_continuous == YES {
+                    supported_exposure_modes.push(ExposureMode::Auto);
                 }
-                if exposure_continuous == YES {
-                    supported_exposure_values.push(2);
-                }
-                if exposure_custom == YES {
-                    supported_exposure_values.push(3);
+
+                if !supported_exposure_modes.is_empty() {
+                    // the custom mode is the locked mode with a duration and ISO set by the user
+                    let exposure_mode = if exposure_current == 0 || exposure_current == 3 {
+                        ExposureMode::Manual
+                    } else {
+                        ExposureMode::Auto
+                    };
+
+                    controls.push(CameraControl::new(
+                        KnownCameraControl::ExposureMode,
+                        "ExposureMode".to_string(),
+                        ControlValueDescription::Menu {
+                            value: exposure_mode.into(),
+                            options: supported_exposure_modes
+                                .into_iter()
+                                .map(|mode| (mode.into(), mode.to_string()))
+                                .collect(),
+                            default: exposure_mode.into(),
+                        },
+                        vec![],
+                        true,
+                    ));
                 }
+            }
+
+            let exposure_poi_supported: BOOL =
+                unsafe { msg_send![self.inner, isExposurePointOfInterestSupported] };
+
+            if exposure_poi_supported == YES {
+                let exposure_poi: CGPoint =
+                    unsafe { msg_send![self.inner, exposurePointOfInterest] };
 
                 controls.push(CameraControl::new(
-                    KnownCameraControl::Exposure,
-                    "ExposureMode".to_string(),
-                    ControlValueDescription::Enum {
-                        value: exposure_current,
-                        possible: supported_exposure_values,
-                        default: exposure_current,
+                    KnownCameraControl::Other(2),
+                    "ExposurePointOfInterest".to_string(),
+                    ControlValueDescription::Point {
+                        value: (exposure_poi.x as f64, exposure_poi.y as f64),
+                        default: (0.5, 0.5),
                     },
                     vec![],
-                    true,
+                    exposure_auto == YES || exposure_continuous == YES,
                 ));
             }
 
-            let exposure_poi_supported: BOOL =
-                unsafe { msg_send![self.inner, isExposurePointOfInterestSupported] };
-            let exposure_poi: CGPoint = unsafe { msg_send![self.inner, exposurePointOfInterest] };
-
-            controls.push(CameraControl::new(
-                KnownCameraControl::Other(2),
-                "ExposurePointOfInterest".to_string(),
-                ControlValueDescription::Point {
-                    value: (exposure_poi.x as f64, exposure_poi.y as f64),
-                    default: (0.5, 0.5),
-                },
-                if exposure_poi_supported == NO {
-                    vec![
-                        KnownCameraControlFlag::Disabled,
-                        KnownCameraControlFlag::ReadOnly,
-                    ]
-                } else {
-                    vec![]
-                },
-                focus_auto == YES || focus_continuous == YES,
-            ));
-
             let expposure_face_driven_supported: BOOL =
                 unsafe { msg_send![self.inner, isFaceDrivenAutoExposureEnabled] };
-            let exposure_face_driven: BOOL = unsafe {
-                msg_send![
-                    self.inner,
-                    automaticallyAdjustsFaceDrivenAutoExposureEnabled
-                ]
-            };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Other(3),
-                "ExposureFaceDriven".to_string(),
-                ControlValueDescription::Boolean {
-                    value: exposure_face_driven == YES,
-                    default: false,
-                },
-                if expposure_face_driven_supported == NO {
-                    vec![
-                        KnownCameraControlFlag::Disabled,
-                        KnownCameraControlFlag::ReadOnly,
+            if expposure_face_driven_supported == YES {
+                let exposure_face_driven: BOOL = unsafe {
+                    msg_send![
+                        self.inner,
+                        automaticallyAdjustsFaceDrivenAutoExposureEnabled
                     ]
-                } else {
-                    vec![]
-                },
-                exposure_poi_supported == YES,
-            ));
+                };
+
+                controls.push(CameraControl::new(
+                    KnownCameraControl::Other(3),
+                    "ExposureFaceDriven".to_string(),
+                    ControlValueDescription::Boolean {
+                        value: exposure_face_driven == YES,
+                        default: false,
+                    },
+                    vec![],
+                    exposure_poi_supported == YES,
+                ));
+            }
 
             let exposure_bias: f32 = unsafe { msg_send![self.inner, exposureTargetBias] };
             let exposure_bias_min: f32 = unsafe { msg_send![self.inner, minExposureTargetBias] };
@@ -1229,57 +1306,48 @@ mod internal {
                 true,
             ));
 
-            let exposure_duration: CMTime = unsafe { msg_send![self.inner, exposureDuration] };
-            let exposure_duration_min: CMTime =
-                unsafe { msg_send![active_format, minExposureDuration] };
-            let exposure_duration_max: CMTime =
-                unsafe { msg_send![active_format, maxExposureDuration] };
+            // setting the duration or ISO switches to the custom exposure mode
+            if exposure_custom == YES {
+                let exposure_duration: CMTime = unsafe { msg_send![self.inner, exposureDuration] };
+                let exposure_duration_min: CMTime =
+                    unsafe { msg_send![active_format, minExposureDuration] };
+                let exposure_duration_max: CMTime =
+                    unsafe { msg_send![active_format, maxExposureDuration] };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Gamma,
-                "ExposureDuration".to_string(),
-                ControlValueDescription::IntegerRange {
-                    min: exposure_duration_min.value,
-                    max: exposure_duration_max.value,
-                    value: exposure_duration.value,
-                    step: 1,
-                    default: unsafe { AVCaptureExposureDurationCurrent.value },
-                },
-                if exposure_custom == YES {
-                    vec![
-                        KnownCameraControlFlag::ReadOnly,
-                        KnownCameraControlFlag::Volatile,
-                    ]
-                } else {
-                    vec![KnownCameraControlFlag::Volatile]
-                },
-                exposure_custom == YES,
-            ));
+                let mut exposure_duration_control = CameraControl::new(
+                    KnownCameraControl::Exposure,
+                    "ExposureDuration".to_string(),
+                    ControlValueDescription::IntegerRange {
+                        min: cmtime_to_micros(exposure_duration_min),
+                        max: cmtime_to_micros(exposure_duration_max),
+                        value: cmtime_to_micros(exposure_duration),
+                        step: 1,
+                        default: cmtime_to_micros(exposure_duration),
+                    },
+                    vec![KnownCameraControlFlag::Volatile],
+                    exposure_current == 3,
+                );
+                exposure_duration_control.set_unit(ControlUnit::Microseconds(1));
+                controls.push(exposure_duration_control);
 
-            let exposure_iso: f32 = unsafe { msg_send![self.inner, ISO] };
-            let exposure_iso_min: f32 = unsafe { msg_send![active_format, minISO] };
-            let exposure_iso_max: f32 = unsafe { msg_send![active_format, maxISO] };
+                let exposure_iso: f32 = unsafe { msg_send![self.inner, ISO] };
+                let exposure_iso_min: f32 = unsafe { msg_send![active_format, minISO] };
+                let exposure_iso_max: f32 = unsafe { msg_send![active_format, maxISO] };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Brightness,
-                "ExposureISO".to_string(),
-                ControlValueDescription::FloatRange {
-                    min: exposure_iso_min as f64,
-                    max: exposure_iso_max as f64,
-                    value: exposure_iso as f64,
-                    step: f32::MIN_POSITIVE as f64,
-                    default: unsafe { AVCaptureISOCurrent } as f64,
-                },
-                if exposure_custom == YES {
-                    vec![
-                        KnownCameraControlFlag::ReadOnly,
-                        KnownCameraControlFlag::Volatile,
-                    ]
-                } else {
-                    vec![KnownCameraControlFlag::Volatile]
-                },
-                exposure_custom == YES,
-            ));
+                controls.push(CameraControl::new(
+                    KnownCameraControl::Gain,
+                    "ExposureISO".to_string(),
+                    ControlValueDescription::FloatRange {
+                        min: exposure_iso_min as f64,
+                        max: exposure_iso_max as f64,
+                        value: exposure_iso as f64,
+                        step: f32::MIN_POSITIVE as f64,
+                        default: exposure_iso as f64,
+                    },
+                    vec![KnownCameraControlFlag::Volatile],
+                    exposure_current == 3,
+                ));
+            }
 
             let lens_aperture: f32 = unsafe { msg_send![self.inner, lensAperture] };
 
@@ -1306,37 +1374,30 @@ mod internal {
                 unsafe { msg_send![self.inner, isWhiteBalanceModeSupported:NSInteger::from(2)] };
 
             {
-                let mut possible = vec![];
+                let white_balance_automatic =
+                    white_balance_auto == YES || white_balance_continuous == YES;
 
-                if white_balance_manual == YES {
-                    possible.push(0);
-                }
-                if white_balance_auto == YES {
-                    possible.push(1);
-                }
-                if white_balance_continuous == YES {
-                    possible.push(2);
-                }
+                if white_balance_manual == YES || white_balance_automatic {
+                    // with only one of them supported, there is nothing to switch
+                    let flags = if white_balance_manual == YES && white_balance_automatic {
+                        vec![]
+                    } else {
+                        vec![KnownCameraControlFlag::ReadOnly]
+                    };
 
-                controls.push(CameraControl::new(
-                    KnownCameraControl::WhiteBalance,
-                    "WhiteBalanceMode".to_string(),
-                    ControlValueDescription::Enum {
-                        value: white_balance_current as i64,
-                        possible,
-                        default: 0,
-                    },
-                    vec![],
-                    true,
-                ));
+                    controls.push(CameraControl::new(
+                        KnownCameraControl::WhiteBalanceAuto,
+                        "WhiteBalanceAuto".to_string(),
+                        ControlValueDescription::Boolean {
+                            value: white_balance_current != 0,
+                            default: white_balance_automatic,
+                        },
+                        flags,
+                        true,
+                    ));
+                }
             }
 
-            let white_balance_gains: AVCaptureWhiteBalanceGains =
-                unsafe { msg_send![self.inner, deviceWhiteBalanceGains] };
-            let white_balance_default: AVCaptureWhiteBalanceGains =
-                unsafe { msg_send![self.inner, grayWorldDeviceWhiteBalanceGains] };
-            let white_balancne_max: AVCaptureWhiteBalanceGains =
-                unsafe { msg_send![self.inner, maxWhiteBalanceGain] };
             let white_balance_gain_supported: BOOL = unsafe {
                 msg_send![
                     self.inner,
@@ -1344,48 +1405,102 @@ mod internal {
                 ]
             };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Gain,
-                "WhiteBalanceGain".to_string(),
-                ControlValueDescription::RGB {
-                    value: (
-                        white_balance_gains.redGain as f64,
-                        white_balance_gains.greenGain as f64,
-                        white_balance_gains.blueGain as f64,
-                    ),
-                    max: (
-                        white_balancne_max.redGain as f64,
-                        white_balancne_max.greenGain as f64,
-                        white_balancne_max.blueGain as f64,
-                    ),
-                    default: (
-                        white_balance_default.redGain as f64,
-                        white_balance_default.greenGain as f64,
-                        white_balance_default.blueGain as f64,
-                    ),
-                },
-                if white_balance_gain_supported == YES {
-                    vec![
-                        KnownCameraControlFlag::Disabled,
-                        KnownCameraControlFlag::ReadOnly,
+            if white_balance_gain_supported == YES {
+                let white_balance_gains: AVCaptureWhiteBalanceGains =
+                    unsafe { msg_send![self.inner, deviceWhiteBalanceGains] };
+                let white_balance_default: AVCaptureWhiteBalanceGains =
+                    unsafe { msg_send![self.inner, grayWorldDeviceWhiteBalanceGains] };
+                let white_balancne_max: f32 = unsafe { msg_send![self.inner, maxWhiteBalanceGain] };
+
+                controls.push(CameraControl::new(
+                    KnownCameraControl::Other(7),
+                    "WhiteBalanceGain".to_string(),
+                    ControlValueDescription::RGB {
+                        value: (
+                            white_balance_gains.redGain as f64,
+                            white_balance_gains.greenGain as f64,
+                            white_balance_gains.blueGain as f64,
+                        ),
+                        max: (
+                            white_balancne_max as f64,
+                            white_balancne_max as f64,
+                            white_balancne_max as f64,
+                        ),
+                        default: (
+                            white_balance_default.redGain as f64,
+                            white_balance_default.greenGain as f64,
+                            white_balance_default.blueGain as f64,
+                        ),
+                    },
+                    vec![],
+                    white_balance_current == 0,
+                ));
+
+                let temperature_and_tint: AVCaptureWhiteBalanceTemperatureAndTintValues = unsafe {
+                    msg_send![
+                        self.inner,
+                        temperatureAndTintValuesForDeviceWhiteBalanceGains: white_balance_gains
                     ]
-                } else {
-                    vec![]
-                },
-                white_balance_gain_supported == YES,
-            ));
+                };
+                let temperature_and_tint_default: AVCaptureWhiteBalanceTemperatureAndTintValues = unsafe {
+                    msg_send![
+                        self.inner,
+                        temperatureAndTintValuesForDeviceWhiteBalanceGains: white_balance_default
+                    ]
+                };
+
+                // The device has no temperature range, only the gains have one. The extremes are all of the blue gain (the
+                // warmest light it can correct) and all of the red gain (the coldest).
+                let temperature_at = |red: f32, blue: f32| {
+                    let gains = AVCaptureWhiteBalanceGains {
+                        blueGain: blue,
+                        greenGain: 1.0,
+                        redGain: red,
+                    };
+                    let values: AVCaptureWhiteBalanceTemperatureAndTintValues = unsafe {
+                        msg_send![
+                            self.inner,
+                            temperatureAndTintValuesForDeviceWhiteBalanceGains: gains
+                        ]
+                    };
+                    values.temperature.round() as i64
+                };
+                let warmest = temperature_at(1.0, white_balancne_max);
+                let coldest = temperature_at(white_balancne_max, 1.0);
+                let (temperature_min, temperature_max) =
+                    (warmest.min(coldest), warmest.max(coldest));
+
+                let mut temperature_control = CameraControl::new(
+                    KnownCameraControl::WhiteBalance,
+                    "WhiteBalanceTemperature".to_string(),
+                    ControlValueDescription::IntegerRange {
+                        min: temperature_min,
+                        max: temperature_max,
+                        value: (temperature_and_tint.temperature.round() as i64)
+                            .clamp(temperature_min, temperature_max),
+                        step: 1,
+                        default: (temperature_and_tint_default.temperature.round() as i64)
+                            .clamp(temperature_min, temperature_max),
+                    },
+                    vec![],
+                    white_balance_current == 0,
+                );
+                temperature_control.set_unit(ControlUnit::Kelvin);
+                controls.push(temperature_control);
+            }
 
             // get flash
             let has_torch: BOOL = unsafe { msg_send![self.inner, isTorchAvailable] };
-            let torch_active: BOOL = unsafe { msg_send![self.inner, isTorchActive] };
-            let torch_off: BOOL =
-                unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(0)] };
-            let torch_on: BOOL =
-                unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(1)] };
-            let torch_auto: BOOL =
-                unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(2)] };
 
-            {
+            if has_torch == YES {
+                let torch_mode: NSInteger = unsafe { msg_send![self.inner, torchMode] };
+                let torch_off: BOOL =
+                    unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(0)] };
+                let torch_on: BOOL =
+                    unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(1)] };
+                let torch_auto: BOOL =
+                    unsafe { msg_send![self.inner, isTorchModeSupported:NSInteger::from(2)] };
+
                 let mut possible = vec![];
 
                 if torch_off == YES {
@@ -1402,27 +1517,23 @@ mod internal {
                     KnownCameraControl::Other(5),
                     "TorchMode".to_string(),
                     ControlValueDescription::Enum {
-                        value: (torch_active == YES) as i64,
+                        value: torch_mode as i64,
                         possible,
                         default: 0,
                     },
-                    if has_torch == YES {
-                        vec![
-                            KnownCameraControlFlag::Disabled,
-                            KnownCameraControlFlag::ReadOnly,
-                        ]
-                    } else {
-                        vec![]
-                    },
-                    has_torch == YES,
+                    vec![],
+                    true,
                 ));
             }
 
             // get low light boost
             let has_llb: BOOL = unsafe { msg_send![self.inner, isLowLightBoostSupported] };
-            let llb_enabled: BOOL = unsafe { msg_send![self.inner, isLowLightBoostEnabled] };
 
-            {
+            if has_llb == YES {
+                let llb_enabled: BOOL = unsafe {
+                    msg_send![self.inner, automaticallyEnablesLowLightBoostWhenAvailable]
+                };
+
                 controls.push(CameraControl::new(
                     KnownCameraControl::BacklightComp,
                     "LowLightCompensation".to_string(),
@@ -1430,15 +1541,8 @@ mod internal {
                         value: llb_enabled == YES,
                         default: false,
                     },
-                    if has_llb == NO {
-                        vec![
-                            KnownCameraControlFlag::Disabled,
-                            KnownCameraControlFlag::ReadOnly,
-                        ]
-                    } else {
-                        vec![]
-                    },
-                    has_llb == YES,
+                    vec![],
+                    true,
                 ));
             }
 
@@ -1464,34 +1568,45 @@ mod internal {
             // zoom distortion correction
             let distortion_correction_supported: BOOL =
                 unsafe { msg_send![self.inner, isGeometricDistortionCorrectionSupported] };
-            let distortion_correction_current_value: BOOL =
-                unsafe { msg_send![self.inner, isGeometricDistortionCorrectionEnabled] };
 
-            controls.push(CameraControl::new(
-                KnownCameraControl::Other(6),
-                "DistortionCorrection".to_string(),
-                ControlValueDescription::Boolean {
-                    value: distortion_correction_current_value == YES,
-                    default: false,
-                },
-                if distortion_correction_supported == YES {
-                    vec![
-                        KnownCameraControlFlag::ReadOnly,
-                        KnownCameraControlFlag::Disabled,
-                    ]
-                } else {
-                    vec![]
-                },
-                distortion_correction_supported == YES,
-            ));
+            if distortion_correction_supported == YES {
+                let distortion_correction_current_value: BOOL =
+                    unsafe { msg_send![self.inner, isGeometricDistortionCorrectionEnabled] };
+
+                controls.push(CameraControl::new(
+                    KnownCameraControl::Other(6),
+                    "DistortionCorrection".to_string(),
+                    ControlValueDescription::Boolean {
+                        value: distortion_correction_current_value == YES,
+                        default: false,
+                    },
+                    vec![],
+                    true,
+                ));
+            }
 
             Ok(controls)
         }
 
+        /// Sets a control, locking the device for configuration around it.
+        /// See [`get_controls()`](AVCaptureDevice::get_controls) for which controls exist and how their values are converted.
+        /// # Errors
+        /// If the device cannot be locked, the control does not exist, or the value is rejected, this will error.
         pub fn set_control(
             &mut self,
             id: KnownCameraControl,
             value: ControlValueSetter,
+        ) -> Result<(), NokhwaError> {
+            self.lock()?;
+            let result = self.set_control_locked(id, value);
+            self.unlock();
+            result
+        }
+
+        fn set_control_locked(
+            &mut self,
+            id: KnownCameraControl,
+            value: ControlValueSetter,
         ) -> Result<(), NokhwaError> {
             let rc = self.get_controls()?;
             let controls = rc
@@ -1500,31 +1615,8 @@ mod internal {
                 .collect::<BTreeMap<_, _>>();
 
             match id {
-                KnownCameraControl::Brightness => {
-                    let isoctrl = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                        property: id.to_string(),
-                        value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if isoctrl.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error:
-                                "Exposure is in improper state to set ISO (Please set to `custom`!)"
-                                    .to_string(),
-                        });
-                    }
-
-                    if isoctrl.flag().contains(&KnownCameraControlFlag::Disabled) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
-
+                KnownCameraControl::Gain => {
+                    settable_control(&controls, id, &value)?;
                     let current_duration = unsafe { AVCaptureExposureDurationCurrent };
                     let new_iso = *value.as_float().ok_or(NokhwaError::SetPropertyError {
                         property: id.to_string(),
@@ -1532,71 +1624,22 @@ mod internal {
                         error: "Expected float".to_string(),
                     })? as f32;
 
-                    if !isoctrl.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
-                    }
-
                     let _: () = unsafe {
                         msg_send![self.inner, setExposureModeCustomWithDuration:current_duration ISO:new_iso completionHandler:Nil]
                     };
 
                     Ok(())
                 }
-                KnownCameraControl::Gamma => {
-                    let duration_ctrl = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                        property: id.to_string(),
-                        value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if duration_ctrl
-                        .flag()
-                        .contains(&KnownCameraControlFlag::ReadOnly)
-                    {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Exposure is in improper state to set Duration (Please set to `custom`!)"
-                                .to_string(),
-                        });
-                    }
-
-                    if duration_ctrl
-                        .flag()
-                        .contains(&KnownCameraControlFlag::Disabled)
-                    {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
-                    let current_duration: CMTime =
-                        unsafe { msg_send![self.inner, exposureDuration] };
-
-                    let current_iso = unsafe { AVCaptureISOCurrent };
-                    let new_duration = CMTime {
-                        value: *value.as_integer().ok_or(NokhwaError::SetPropertyError {
+                KnownCameraControl::Exposure => {
+                    settable_control(&controls, id, &value)?;
+                    let current_iso = unsafe { AVCaptureISOCurrent };
+                    let new_duration = micros_to_cmtime(*value.as_integer().ok_or(
+                        NokhwaError::SetPropertyError {
                             property: id.to_string(),
                             value: value.to_string(),
                             error: "Expected i64".to_string(),
-                        })?,
-                        timescale: current_duration.timescale,
-                        flags: current_duration.flags,
-                        epoch: current_duration.epoch,
-                    };
-
-                    if !duration_ctrl.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
-                    }
+                        },
+                    )?);
 
                     let _: () = unsafe {
                         msg_send![self.inner, setExposureModeCustomWithDuration:new_duration ISO:current_iso completionHandler:Nil]
@@ -1604,125 +1647,113 @@ mod internal {
 
                     Ok(())
                 }
-                KnownCameraControl::WhiteBalance => {
-                    let wb_enum_value = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                        property: id.to_string(),
-                        value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if wb_enum_value
-                        .flag()
-                        .contains(&KnownCameraControlFlag::ReadOnly)
-                    {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Read Only".to_string(),
-                        });
-                    }
-
-                    if wb_enum_value
-                        .flag()
-                        .contains(&KnownCameraControlFlag::Disabled)
-                    {
-                        return Err(NokhwaError::SetPropertyError {
+                KnownCameraControl::ExposureMode => {
+                    settable_control(&controls, id, &value)?;
+                    let mode = value
+                        .as_enum()
+                        .and_then(|mode| ExposureMode::try_from(*mode).ok())
+                        .ok_or(NokhwaError::SetPropertyError {
                             property: id.to_string(),
                             value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
-                    let setter =
-                        NSInteger::from(*value.as_enum().ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected Enum".to_string(),
-                        })? as i32);
+                            error: "Expected ExposureMode".to_string(),
+                        })?;
 
-                    if !wb_enum_value.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
+                    match mode {
+                        ExposureMode::Auto => {
+                            let continuous: BOOL = unsafe {
+                                msg_send![self.inner, isExposureModeSupported:NSInteger::from(2)]
+                            };
+                            let setter = automatic_mode(continuous);
+                            let _: () = unsafe { msg_send![self.inner, setExposureMode: setter] };
+                        }
+                        _ => {
+                            let custom: BOOL = unsafe {
+                                msg_send![self.inner, isExposureModeSupported:NSInteger::from(3)]
+                            };
+                            if custom == YES {
+                                // keeps the current duration and ISO, so they can be set one at a time
+                                let (current_duration, current_iso) = unsafe {
+                                    (AVCaptureExposureDurationCurrent, AVCaptureISOCurrent)
+                                };
+                                let _: () = unsafe {
+                                    msg_send![self.inner, setExposureModeCustomWithDuration:current_duration ISO:current_iso completionHandler:Nil]
+                                };
+                            } else {
+                                let _: () = unsafe {
+                                    msg_send![self.inner, setExposureMode:NSInteger::from(0)]
+                                };
+                            }
+                        }
                     }
 
-                    let _: () = unsafe { msg_send![self.inner, whiteBalanceMode: setter] };
-
                     Ok(())
                 }
-                KnownCameraControl::BacklightComp => {
-                    let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
+                KnownCameraControl::WhiteBalanceAuto => {
+                    settable_control(&controls, id, &value)?;
+                    let setter = if *value.as_boolean().ok_or(NokhwaError::SetPropertyError {
                         property: id.to_string(),
                         value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Read Only".to_string(),
-                        });
-                    }
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::Disabled) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
+                        error: "Expected Boolean".to_string(),
+                    })? {
+                        let continuous: BOOL = unsafe {
+                            msg_send![self.inner, isWhiteBalanceModeSupported:NSInteger::from(2)]
+                        };
+                        automatic_mode(continuous)
+                    } else {
+                        NSInteger::from(0)
+                    };
 
-                    let setter =
-                        NSInteger::from(*value.as_enum().ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected Enum".to_string(),
-                        })? as i32);
+                    let _: () = unsafe { msg_send![self.inner, setWhiteBalanceMode: setter] };
 
-                    if !ctrlvalue.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
-                    }
+                    Ok(())
+                }
+                KnownCameraControl::WhiteBalance => {
+                    settable_control(&controls, id, &value)?;
+                    let temperature = *value.as_integer().ok_or(NokhwaError::SetPropertyError {
+                        property: id.to_string(),
+                        value: value.to_string(),
+                        error: "Expected i64".to_string(),
+                    })? as f32;
 
-                    let _: () = unsafe { msg_send![self.inner, whiteBalanceMode: setter] };
+                    // keep the current tint, only the temperature changes
+                    let current_gains: AVCaptureWhiteBalanceGains =
+                        unsafe { msg_send![self.inner, deviceWhiteBalanceGains] };
+                    let current: AVCaptureWhiteBalanceTemperatureAndTintValues = unsafe {
+                        msg_send![
+                            self.inner,
+                            temperatureAndTintValuesForDeviceWhiteBalanceGains: current_gains
+                        ]
+                    };
+                    let new_values = AVCaptureWhiteBalanceTemperatureAndTintValues {
+                        temperature,
+                        tint: current.tint,
+                    };
+                    let gains: AVCaptureWhiteBalanceGains = unsafe {
+                        msg_send![
+                            self.inner,
+                            deviceWhiteBalanceGainsForTemperatureAndTintValues: new_values
+                        ]
+                    };
+                    self.lock_white_balance_gains(gains);
 
                     Ok(())
                 }
-                KnownCameraControl::Gain => {
+                KnownCameraControl::BacklightComp => {
                     let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
                         property: id.to_string(),
                         value: value.to_string(),
                         error: "Control does not exist".to_string(),
                     })?;
 
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Read Only".to_string(),
-                        });
-                    }
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::Disabled) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
-
-                    let setter = NSInteger::from(*value.as_boolean().ok_or(
-                        NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected Boolean".to_string(),
-                        },
-                    )? as i32);
+                    let setter = if *value.as_boolean().ok_or(NokhwaError::SetPropertyError {
+                        property: id.to_string(),
+                        value: value.to_string(),
+                        error: "Expected Boolean".to_string(),
+                    })? {
+                        YES
+                    } else {
+                        NO
+                    };
 
                     if !ctrlvalue.description().verify_setter(&value) {
                         return Err(NokhwaError::SetPropertyError {
@@ -1732,7 +1763,12 @@ mod internal {
                         });
                     }
 
-                    let _: () = unsafe { msg_send![self.inner, whiteBalanceMode: setter] };
+                    let _: () = unsafe {
+                        msg_send![
+                            self.inner,
+                            setAutomaticallyEnablesLowLightBoostWhenAvailable: setter
+                        ]
+                    };
 
                     Ok(())
                 }
@@ -1779,92 +1815,46 @@ mod internal {
 
                     Ok(())
                 }
-                KnownCameraControl::Exposure => {
-                    let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                        property: id.to_string(),
-                        value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Read Only".to_string(),
-                        });
-                    }
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::Disabled) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
-
-                    let setter =
-                        NSInteger::from(*value.as_enum().ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected Enum".to_string(),
-                        })? as i32);
-
-                    if !ctrlvalue.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
-                    }
-
-                    let _: () = unsafe { msg_send![self.inner, exposureMode: setter] };
-
-                    Ok(())
-                }
                 KnownCameraControl::Iris => Err(NokhwaError::SetPropertyError {
                     property: id.to_string(),
                     value: value.to_string(),
                     error: "Read Only".to_string(),
                 }),
-                KnownCameraControl::Focus => {
-                    let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                        property: id.to_string(),
-                        value: value.to_string(),
-                        error: "Control does not exist".to_string(),
-                    })?;
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                        return Err(NokhwaError::SetPropertyError {
+                KnownCameraControl::FocusMode => {
+                    settable_control(&controls, id, &value)?;
+                    let mode = value
+                        .as_enum()
+                        .and_then(|mode| FocusMode::try_from(*mode).ok())
+                        .ok_or(NokhwaError::SetPropertyError {
                             property: id.to_string(),
                             value: value.to_string(),
-                            error: "Read Only".to_string(),
-                        });
-                    }
-
-                    if ctrlvalue.flag().contains(&KnownCameraControlFlag::Disabled) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Disabled".to_string(),
-                        });
-                    }
+                            error: "Expected FocusMode".to_string(),
+                        })?;
+                    let setter = match mode {
+                        FocusMode::Manual => NSInteger::from(0),
+                        FocusMode::Auto => {
+                            let continuous: BOOL = unsafe {
+                                msg_send![self.inner, isFocusModeSupported:NSInteger::from(2)]
+                            };
+                            automatic_mode(continuous)
+                        }
+                    };
 
-                    let setter =
-                        NSInteger::from(*value.as_enum().ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected Enum".to_string(),
-                        })? as i32);
+                    let _: () = unsafe { msg_send![self.inner, setFocusMode: setter] };
 
-                    if !ctrlvalue.description().verify_setter(&value) {
-                        return Err(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Failed to verify value".to_string(),
-                        });
-                    }
+                    Ok(())
+                }
+                KnownCameraControl::Focus => {
+                    settable_control(&controls, id, &value)?;
+                    let setter = *value.as_float().ok_or(NokhwaError::SetPropertyError {
+                        property: id.to_string(),
+                        value: value.to_string(),
+                        error: "Expected float".to_string(),
+                    })? as c_float;
 
-                    let _: () = unsafe { msg_send![self.inner, focusMode: setter] };
+                    let _: () = unsafe {
+                        msg_send![self.inner, setFocusModeLockedWithLensPosition: setter completionHandler: Nil]
+                    };
 
                     Ok(())
                 }
@@ -1912,50 +1902,8 @@ mod internal {
                             });
                         }
 
-                        let _: () = unsafe { msg_send![self.inner, focusPointOfInterest: setter] };
-
-                        Ok(())
-                    }
-                    1 => {
-                        let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Control does not exist".to_string(),
-                        })?;
-
-                        if ctrlvalue.flag().contains(&KnownCameraControlFlag::ReadOnly) {
-                            return Err(NokhwaError::SetPropertyError {
-                                property: id.to_string(),
-                                value: value.to_string(),
-                                error: "Read Only".to_string(),
-                            });
-                        }
-
-                        if ctrlvalue.flag().contains(&KnownCameraControlFlag::Disabled) {
-                            return Err(NokhwaError::SetPropertyError {
-                                property: id.to_string(),
-                                value: value.to_string(),
-                                error: "Disabled".to_string(),
-                            });
-                        }
-
-                        let setter = *value.as_float().ok_or(NokhwaError::SetPropertyError {
-                            property: id.to_string(),
-                            value: value.to_string(),
-                            error: "Expected float".to_string(),
-                        })? as c_float;
-
-                        if !ctrlvalue.description().verify_setter(&value) {
-                            return Err(NokhwaError::SetPropertyError {
-                                property: id.to_string(),
-                                value: value.to_string(),
-                                error: "Failed to verify value".to_string(),
-                            });
-                        }
-
-                        let _: () = unsafe {
-                            msg_send![self.inner, setFocusModeLockedWithLensPosition: setter handler: Nil]
-                        };
+                        let _: () =
+                            unsafe { msg_send![self.inner, setFocusPointOfInterest: setter] };
 
                         Ok(())
                     }
@@ -2003,7 +1951,7 @@ mod internal {
                         }
 
                         let _: () =
-                            unsafe { msg_send![self.inner, exposurePointOfInterest: setter] };
+                            unsafe { msg_send![self.inner, setExposurePointOfInterest: setter] };
 
                         Ok(())
                     }
@@ -2052,7 +2000,7 @@ mod internal {
                         let _: () = unsafe {
                             msg_send![
                                 self.inner,
-                                automaticallyAdjustsFaceDrivenAutoExposureEnabled: setter
+                                setAutomaticallyAdjustsFaceDrivenAutoExposureEnabled: setter
                             ]
                         };
 
@@ -2096,7 +2044,7 @@ mod internal {
                         }
 
                         let _: () = unsafe {
-                            msg_send![self.inner, setExposureTargetBias: setter handler: Nil]
+                            msg_send![self.inner, setExposureTargetBias: setter completionHandler: Nil]
                         };
 
                         Ok(())
@@ -2140,7 +2088,7 @@ mod internal {
                             });
                         }
 
-                        let _: () = unsafe { msg_send![self.inner, torchMode: setter] };
+                        let _: () = unsafe { msg_send![self.inner, setTorchMode: setter] };
 
                         Ok(())
                     }
@@ -2187,11 +2135,41 @@ mod internal {
                         }
 
                         let _: () = unsafe {
-                            msg_send![self.inner, geometricDistortionCorrectionEnabled: setter]
+                            msg_send![self.inner, setGeometricDistortionCorrectionEnabled: setter]
                         };
 
                         Ok(())
                     }
+                    7 => {
+                        let ctrlvalue = controls.get(&id).ok_or(NokhwaError::SetPropertyError {
+                            property: id.to_string(),
+                            value: value.to_string(),
+                            error: "Control does not exist".to_string(),
+                        })?;
+
+                        let (red, green, blue) =
+                            value.as_rgb().ok_or(NokhwaError::SetPropertyError {
+                                property: id.to_string(),
+                                value: value.to_string(),
+                                error: "Expected RGB".to_string(),
+                            })?;
+
+                        if !ctrlvalue.description().verify_setter(&value) {
+                            return Err(NokhwaError::SetPropertyError {
+                                property: id.to_string(),
+                                value: value.to_string(),
+                                error: "Failed to verify value".to_string(),
+                            });
+                        }
+
+                        self.lock_white_balance_gains(AVCaptureWhiteBalanceGains {
+                            blueGain: *blue as f32,
+                            greenGain: *green as f32,
+                            redGain: *red as f32,
+                        });
+
+                        Ok(())
+                    }
                     _ => Err(NokhwaError::SetPropertyError {
                         property: id.to_string(),
                         value: value.to_string(),
@@ -32,7 +32,8 @@ pub mod wmf {
     use nokhwa_core::error::NokhwaError;
     use nokhwa_core::types::{
         ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueDescription,
-        ControlValueSetter, FrameFormat, KnownCameraControl, KnownCameraControlFlag, Resolution,
+        ControlValueSetter, ExposureMode, FocusMode, FrameFormat, KnownCameraControl,
+        KnownCameraControlFlag, Resolution,
     };
     use once_cell::sync::Lazy;
     use std::ffi::c_void;
@@ -377,6 +378,9 @@ pub mod wmf {
         ProcAmpRange(i32),
         CCValue(i32),
         CCRange(i32),
+        // the auto/manual flag of a property, rather than its value
+        ProcAmpAuto(i32),
+        CCAuto(i32),
     }
 
     #[allow(clippy::cast_sign_loss)]
@@ -391,15 +395,30 @@ pub mod wmf {
             KnownCameraControl::WhiteBalance => {
                 MFControlId::ProcAmpRange(VideoProcAmp_WhiteBalance.0)
             }
+            KnownCameraControl::WhiteBalanceAuto => {
+                MFControlId::ProcAmpAuto(VideoProcAmp_WhiteBalance.0)
+            }
+            KnownCameraControl::WhiteBalanceRed | KnownCameraControl::WhiteBalanceBlue => {
+                return None
+            }
             KnownCameraControl::BacklightComp => {
                 MFControlId::ProcAmpBoolean(VideoProcAmp_BacklightCompensation.0)
             }
             KnownCameraControl::Gain => MFControlId::ProcAmpRange(VideoProcAmp_Gain.0),
+            KnownCameraControl::PowerLineFrequency => return None,
             KnownCameraControl::Pan => MFControlId::CCRange(CameraControl_Pan.0),
             KnownCameraControl::Tilt => MFControlId::CCRange(CameraControl_Tilt.0),
+            KnownCameraControl::PanRelative | KnownCameraControl::TiltRelative => return None,
             KnownCameraControl::Zoom => MFControlId::CCRange(CameraControl_Zoom.0),
+            KnownCameraControl::ZoomRelative => return None,
             KnownCameraControl::Exposure => MFControlId::CCValue(CameraControl_Exposure.0),
+            // Media Foundation carries the exposure mode as the auto/manual flag of the exposure property.
+            KnownCameraControl::ExposureMode => MFControlId::CCAuto(CameraControl_Exposure.0),
+            // Not exposed by `IAMCameraControl`.
+            KnownCameraControl::ExposurePriority => return None,
             KnownCameraControl::Iris => MFControlId::CCValue(CameraControl_Iris.0),
+            // Same as the exposure mode, this is the auto/manual flag of the focus property.
+            KnownCameraControl::FocusMode => MFControlId::CCAuto(CameraControl_Focus.0),
             KnownCameraControl::Focus => MFControlId::CCValue(CameraControl_Focus.0),
             KnownCameraControl::Other(o) => {
                 if o == VideoProcAmp_ColorEnable.0 as u128 {
@@ -672,9 +691,8 @@ pub mod wmf {
                         .cast::<IAMCameraControl>()
                         .cast::<*mut c_void>(),
                 ) {
-                    return Err(NokhwaError::SetPropertyError {
-                        property: "MF_SOURCE_READER_MEDIASOURCE".to_string(),
-                        value: "IAMCameraControl".to_string(),
+                    return Err(NokhwaError::GetPropertyError {
+                        property: format!("{control} (IAMCameraControl)"),
                         error: why.to_string(),
                     });
                 }
@@ -689,9 +707,8 @@ pub mod wmf {
                     &IAMVideoProcAmp::IID,
                     ptr_receiver.cast::<IAMVideoProcAmp>().cast::<*mut c_void>(),
                 ) {
-                    return Err(NokhwaError::SetPropertyError {
-                        property: "MF_SOURCE_READER_MEDIASOURCE".to_string(),
-                        value: "IAMVideoProcAmp".to_string(),
+                    return Err(NokhwaError::GetPropertyError {
+                        property: format!("{control} (IAMVideoProcAmp)"),
                         error: why.to_string(),
                     });
                 }
@@ -823,6 +840,92 @@ pub mod wmf {
                         default: i64::from(default),
                     }
                 },
+                MFControlId::ProcAmpAuto(id) | MFControlId::CCAuto(id) => unsafe {
+                    // for `GetRange`, the flags are the capabilities, i.e. which of auto and manual the property supports
+                    let mut capabilities = 0;
+                    let range = match control_id {
+                        MFControlId::ProcAmpAuto(_) => video_proc_amp.GetRange(
+                            id,
+                            &mut min,
+                            &mut max,
+                            &mut step,
+                            &mut default,
+                            &mut capabilities,
+                        ),
+                        _ => camera_control.GetRange(
+                            id,
+                            &mut min,
+                            &mut max,
+                            &mut step,
+                            &mut default,
+                            &mut capabilities,
+                        ),
+                    };
+                    if let Err(why) = range {
+                        return Err(NokhwaError::GetPropertyError {
+                            property: format!("{:?}: {} - Range", control_id, control),
+                            error: why.to_string(),
+                        });
+                    }
+                    let current = match control_id {
+                        MFControlId::ProcAmpAuto(_) => {
+                            video_proc_amp.Get(id, &mut value, &mut flag)
+                        }
+                        _ => camera_control.Get(id, &mut value, &mut flag),
+                    };
+                    if let Err(why) = current {
+                        return Err(NokhwaError::GetPropertyError {
+                            property: format!("{:?}: {} - Value", control_id, control),
+                            error: why.to_string(),
+                        });
+                    }
+
+                    let is_auto = flag & CameraControl_Flags_Auto.0 != 0;
+                    let supports_auto = capabilities & CameraControl_Flags_Auto.0 != 0;
+                    let supports_manual = capabilities & CameraControl_Flags_Manual.0 != 0;
+                    match control {
+                        KnownCameraControl::ExposureMode => {
+                            let mode = |auto| {
+                                if auto {
+                                    ExposureMode::Auto
+                                } else {
+                                    ExposureMode::Manual
+                                }
+                            };
+                            ControlValueDescription::Menu {
+                                value: mode(is_auto).into(),
+                                options: [(supports_manual, false), (supports_auto, true)]
+                                    .into_iter()
+                                    .filter(|(supported, _)| *supported)
+                                    .map(|(_, auto)| (mode(auto).into(), mode(auto).to_string()))
+                                    .collect(),
+                                default: mode(supports_auto).into(),
+                            }
+                        }
+                        KnownCameraControl::FocusMode => {
+                            let mode = |auto| {
+                                if auto {
+                                    FocusMode::Auto
+                                } else {
+                                    FocusMode::Manual
+                                }
+                            };
+                            ControlValueDescription::Menu {
+                                value: mode(is_auto).into(),
+                                options: [(supports_manual, false), (supports_auto, true)]
+                                    .into_iter()
+                                    .filter(|(supported, _)| *supported)
+                                    .map(|(_, auto)| (mode(auto).into(), mode(auto).to_string()))
+                                    .collect(),
+                                default: mode(supports_auto).into(),
+                            }
+                        }
+                        _ => ControlValueDescription::Boolean {
+                            value: is_auto,
+                            default: supports_auto,
+                        },
+                    }
+                },
             };
 
             let is_manual = if flag == CameraControl_Flags_Manual.0 {
@@ -846,6 +949,13 @@ pub mod wmf {
             value: ControlValueSetter,
         ) -> Result<(), NokhwaError> {
             let current_value = self.control(control)?;
+            if !current_value.description().verify_setter(&value) {
+                return Err(NokhwaError::SetPropertyError {
+                    property: control.to_string(),
+                    value: value.to_string(),
+                    error: "Value out of range or not supported".to_string(),
+                });
+            }
 
             let camera_control = unsafe {
                 let mut receiver: MaybeUninit<IAMCameraControl> = MaybeUninit::uninit();
@@ -859,8 +969,8 @@ pub mod wmf {
                         .cast::<*mut c_void>(),
                 ) {
                     return Err(NokhwaError::SetPropertyError {
-                        property: "MF_SOURCE_READER_MEDIASOURCE".to_string(),
-                        value: "IAMCameraControl".to_string(),
+                        property: format!("{control} (IAMCameraControl)"),
+                        value: value.to_string(),
                         error: why.to_string(),
                     });
                 }
@@ -876,8 +986,8 @@ pub mod wmf {
                     ptr_receiver.cast::<IAMVideoProcAmp>().cast::<*mut c_void>(),
                 ) {
                     return Err(NokhwaError::SetPropertyError {
-                        property: "MF_SOURCE_READER_MEDIASOURCE".to_string(),
-                        value: "IAMVideoProcAmp".to_string(),
+                        property: format!("{control} (IAMVideoProcAmp)"),
+                        value: value.to_string(),
                         error: why.to_string(),
                     });
                 }
@@ -890,6 +1000,33 @@ pub mod wmf {
                 error: "Does not exist".to_string(),
             })?;
 
+            // The auto/manual flag is set together with the value of its property, so keep the value as it is.
+            if let MFControlId::ProcAmpAuto(id) | MFControlId::CCAuto(id) = control_id {
+                let flag = if !control.is_manual_mode(&value) {
+                    CameraControl_Flags_Auto
+                } else {
+                    CameraControl_Flags_Manual
+                };
+                let mut current = 0;
+                let mut current_flag = 0;
+                let result = unsafe {
+                    if let MFControlId::ProcAmpAuto(_) = control_id {
+                        video_proc_amp
+                            .Get(id, &mut current, &mut current_flag)
+                            .and_then(|_| video_proc_amp.Set(id, current, flag.0))
+                    } else {
+                        camera_control
+                            .Get(id, &mut current, &mut current_flag)
+                            .and_then(|_| camera_control.Set(id, current, flag.0))
+                    }
+                };
+                return result.map_err(|why| NokhwaError::SetPropertyError {
+                    property: control.to_string(),
+                    value: value.to_string(),
+                    error: why.to_string(),
+                });
+            }
+
             let ctrl_value = match value {
                 ControlValueSetter::Integer(i) => i as i32,
                 ControlValueSetter::Boolean(b) => i32::from(b),
@@ -901,22 +1038,28 @@ pub mod wmf {
                 }
             };
 
-            let flag = current_value
-                .flag()
-                .get(0)
-                .map(|x| {
-                    if *x == KnownCameraControlFlag::Automatic {
-                        CameraControl_Flags_Auto
-                    } else {
-                        CameraControl_Flags_Manual
-                    }
-                })
-                .ok_or(NokhwaError::StructureError {
-                    structure: "KnownCameraControlFlag".to_string(),
-                    error: "could not cast to i32".to_string(),
-                })?;
+            // Setting a value with the auto flag is ignored, so properties with a mode are switched to manual.
+            let flag = if control.mode_control().is_some() {
+                CameraControl_Flags_Manual
+            } else {
+                current_value
+                    .flag()
+                    .get(0)
+                    .map(|x| {
+                        if *x == KnownCameraControlFlag::Automatic {
+                            CameraControl_Flags_Auto
+                        } else {
+                            CameraControl_Flags_Manual
+                        }
+                    })
+                    .ok_or(NokhwaError::StructureError {
+                        structure: "KnownCameraControlFlag".to_string(),
+                        error: "could not cast to i32".to_string(),
+                    })?
+            };
 
             match control_id {
+                MFControlId::ProcAmpAuto(_) | MFControlId::CCAuto(_) => unreachable!(),
                 MFControlId::ProcAmpBoolean(id) | MFControlId::ProcAmpRange(id) => unsafe {
                     if let Err(why) = video_proc_amp.Set(id, ctrl_value, flag.0) {
                         return Err(NokhwaError::SetPropertyError {
@@ -15,6 +15,7 @@
  */
 
 use crate::{frame_format::FrameFormat, types::ApiBackend};
+use std::time::Duration;
 use thiserror::Error;
 
 /// All errors in `nokhwa`.
@@ -51,6 +52,18 @@ pub enum NokhwaError {
         destination: String,
         error: String,
     },
+    #[error(
+        "Stream was restarted after {errors} consecutive frame errors, last error: {last_error}"
+    )]
+    RecoveredAfterErrors { errors: usize, last_error: String },
+    #[error("Timed out: {0}")]
+    TimeoutError(String),
+    #[error("No frame arrived for {0:?}, the stream has stalled")]
+    StreamStalled(Duration),
+    #[error("Only stale frames: the latest one is {age:?} old, more than {max_age:?}")]
+    OnlyStaleFrames { age: Duration, max_age: Duration },
+    #[error("Buffer too small: the frame needs {required} bytes, but the buffer has {provided}")]
+    BufferTooSmall { required: usize, provided: usize },
     #[error("Could not stop stream: {0}")]
     StreamShutdownError(String),
     #[error("This operation is not supported by backend {0}.")]
@@ -57,6 +57,13 @@ pub enum FrameFormat {
     Rgb8,
     RgbA8,
 
+    // Raw Bayer Formats, these are not decoded to RGB. See `BayerPattern`.
+    Bayer8(BayerPattern),
+    /// 10 bits per sample, 4 samples packed in 5 bytes (MIPI RAW10).
+    Bayer10Packed(BayerPattern),
+    /// 12 bits per sample, 2 samples packed in 3 bytes (MIPI RAW12).
+    Bayer12Packed(BayerPattern),
+
     // Custom
     Custom(u128),
     PlatformSpecificCustomFormat(PlatformFrameFormat),
@@ -83,6 +90,18 @@ impl FrameFormat {
         FrameFormat::Luma8,
         FrameFormat::Rgb8,
         FrameFormat::RgbA8,
+        FrameFormat::Bayer8(BayerPattern::Rggb),
+        FrameFormat::Bayer8(BayerPattern::Bggr),
+        FrameFormat::Bayer8(BayerPattern::Grbg),
+        FrameFormat::Bayer8(BayerPattern::Gbrg),
+        FrameFormat::Bayer10Packed(BayerPattern::Rggb),
+        FrameFormat::Bayer10Packed(BayerPattern::Bggr),
+        FrameFormat::Bayer10Packed(BayerPattern::Grbg),
+        FrameFormat::Bayer10Packed(BayerPattern::Gbrg),
+        FrameFormat::Bayer12Packed(BayerPattern::Rggb),
+        FrameFormat::Bayer12Packed(BayerPattern::Bggr),
+        FrameFormat::Bayer12Packed(BayerPattern::Grbg),
+        FrameFormat::Bayer12Packed(BayerPattern::Gbrg),
     ];
 
     pub const COMPRESSED: &'static [FrameFormat] = &[
@@ -110,6 +129,64 @@ impl FrameFormat {
     pub const LUMA: &'static [FrameFormat] = &[FrameFormat::Luma8];
 
     pub const RGB: &'static [FrameFormat] = &[FrameFormat::Rgb8, FrameFormat::RgbA8];
+
+    pub const BAYER: &'static [FrameFormat] = &[
+        FrameFormat::Bayer8(BayerPattern::Rggb),
+        FrameFormat::Bayer8(BayerPattern::Bggr),
+        FrameFormat::Bayer8(BayerPattern::Grbg),
+        FrameFormat::Bayer8(BayerPattern::Gbrg),
+        FrameFormat::Bayer10Packed(BayerPattern::Rggb),
+        FrameFormat::Bayer10Packed(BayerPattern::Bggr),
+        FrameFormat::Bayer10Packed(BayerPattern::Grbg),
+        FrameFormat::Bayer10Packed(BayerPattern::Gbrg),
+        FrameFormat::Bayer12Packed(BayerPattern::Rggb),
+        FrameFormat::Bayer12Packed(BayerPattern::Bggr),
+        FrameFormat::Bayer12Packed(BayerPattern::Grbg),
+        FrameFormat::Bayer12Packed(BayerPattern::Gbrg),
+    ];
+
+    /// Creates a [`FrameFormat::Custom`] from a format GUID, e.g. the `guidFormat` of a UVC format descriptor, in the
+    /// byte order of the descriptor.
+    #[must_use]
+    pub const fn from_guid(guid: [u8; 16]) -> Self {
+        FrameFormat::Custom(u128::from_le_bytes(guid))
+    }
+
+    /// Gets the format GUID if this is a [`FrameFormat::Custom`], see [`from_guid()`](FrameFormat::from_guid).
+    #[must_use]
+    pub const fn guid(&self) -> Option<[u8; 16]> {
+        match self {
+            FrameFormat::Custom(guid) => Some(guid.to_le_bytes()),
+            _ => None,
+        }
+    }
+
+    /// Gets the colour filter array pattern if this is a raw Bayer format.
+    #[must_use]
+    pub fn bayer_pattern(&self) -> Option<BayerPattern> {
+        match self {
+            FrameFormat::Bayer8(pattern)
+            | FrameFormat::Bayer10Packed(pattern)
+            | FrameFormat::Bayer12Packed(pattern) => Some(*pattern),
+            _ => None,
+        }
+    }
+}
+
+/// The colour filter array (CFA) layout of a raw Bayer frame, named after its top-left 2x2 block read left to right, top to bottom.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum BayerPattern {
+    Rggb,
+    Bggr,
+    Grbg,
+    Gbrg,
+}
+
+impl Display for BayerPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
 }
 
 impl Display for FrameFormat {
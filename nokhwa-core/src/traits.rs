@@ -18,13 +18,14 @@ use crate::{
     buffer::Buffer,
     error::NokhwaError,
     format_request::FormatFilter,
-    frame_format::SourceFrameFormat,
+    frame_format::{FrameFormat, SourceFrameFormat},
     types::{
-        ApiBackend, CameraControl, CameraFormat, CameraInfo, ControlValueSetter,
-        KnownCameraControl, Resolution,
+        buf_mjpeg_to_rgb, buf_yuyv422_to_rgb, ApiBackend, CameraControl, CameraControlProfile,
+        CameraFormat, CameraInfo, ControlChangeMechanism, ControlUnit, ControlValueDescription,
+        ControlValueSetter, KnownCameraControl, KnownCameraControlFlag, Rect, Resolution,
     },
 };
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, sync::mpsc::Receiver};
 
 pub trait Backend {
     const BACKEND: ApiBackend;
@@ -152,6 +153,176 @@ pub trait CaptureTrait {
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError>;
 
+    /// Sets several controls at once, returning the result of each in the same order as `controls`.
+    ///
+    /// Mode controls (e.g. [`KnownCameraControl::ExposureMode`]) are set before the controls they govern
+    /// (e.g. [`KnownCameraControl::Exposure`]), otherwise the camera may ignore the value. Other than that, the controls are set
+    /// in the given order, back to back, so they take effect on as few frames as the backend allows.
+    /// A failing control does not stop the rest from being set.
+    fn set_camera_controls(
+        &mut self,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Vec<Result<(), NokhwaError>> {
+        let mut order = (0..controls.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|idx| controls[*idx].0.manual_mode_value().is_none());
+
+        let mut results = Vec::with_capacity(controls.len());
+        results.resize_with(controls.len(), || Ok(()));
+        for idx in order {
+            let (id, value) = &controls[idx];
+            results[idx] = self.set_camera_control(*id, value.clone());
+        }
+        results
+    }
+
+    /// Sets the region auto exposure meters on, e.g. a face instead of the bright window behind it.
+    /// `None` goes back to metering on the full frame.
+    ///
+    /// The [`Rect`] is in pixels of the current [`Resolution`].
+    /// # Errors
+    /// If the camera has no region of interest support (e.g. UVC cameras before UVC 1.5), this will error with
+    /// [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError). If the [`Rect`] does not fit in the frame, or
+    /// it fails to be set, this will error.
+    fn set_ae_roi(&mut self, roi: Option<Rect>) -> Result<(), NokhwaError> {
+        let _ = roi;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Sets the region auto focus focuses on. `None` goes back to focusing on the full frame.
+    ///
+    /// The [`Rect`] is in pixels of the current [`Resolution`].
+    /// # Errors
+    /// See [`set_ae_roi()`](CaptureTrait::set_ae_roi).
+    fn set_af_roi(&mut self, roi: Option<Rect>) -> Result<(), NokhwaError> {
+        let _ = roi;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Writes the device reported default back to every control in [`camera_controls()`](CaptureTrait::camera_controls).
+    ///
+    /// Read-only controls and controls without a default are skipped. Mode controls (e.g. [`KnownCameraControl::ExposureMode`])
+    /// are written last, as setting the controls they govern may switch them to manual.
+    /// # Errors
+    /// If the controls cannot be listed or a default fails to be set, this will error. Controls before the failing one stay reset.
+    fn reset_to_default(&mut self) -> Result<(), NokhwaError> {
+        let mut controls = self.camera_controls()?;
+        controls.sort_by_key(|control| control.control().manual_mode_value().is_some());
+        for control in controls {
+            if control.flag().contains(&KnownCameraControlFlag::ReadOnly) {
+                continue;
+            }
+            if let Some(default) = control.description().default_value() {
+                self.set_camera_control(control.control(), default)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes a snapshot of the current value of every control in [`camera_controls()`](CaptureTrait::camera_controls), to be written back
+    /// later with [`apply_control_profile()`](CaptureTrait::apply_control_profile).
+    ///
+    /// Read-only and write-only controls are left out, as they cannot be restored.
+    /// # Errors
+    /// If the controls cannot be listed, this will error.
+    fn control_profile(&self) -> Result<CameraControlProfile, NokhwaError> {
+        Ok(CameraControlProfile::new(
+            self.camera_controls()?
+                .into_iter()
+                .filter(|control| {
+                    !control.flag().contains(&KnownCameraControlFlag::ReadOnly)
+                        && !control.flag().contains(&KnownCameraControlFlag::WriteOnly)
+                })
+                .map(|control| (control.control(), control.value()))
+                .collect(),
+        ))
+    }
+
+    /// Writes a [`CameraControlProfile`] back to the camera, returning the controls that were skipped.
+    ///
+    /// The controls are set with [`set_camera_controls()`](CaptureTrait::set_camera_controls), so mode controls go first.
+    /// Controls that this camera does not have, or that are read-only on it, are skipped.
+    ///
+    /// A governed control (e.g. [`KnownCameraControl::Exposure`]) whose mode control is automatic in the profile is not
+    /// written, as writing it would switch the mode to manual. The camera picks that value again once the mode is restored.
+    /// # Errors
+    /// If the controls cannot be listed, or a control fails to be set, this will error. All other controls are still set,
+    /// and the error is the first one that happened.
+    fn apply_control_profile(
+        &mut self,
+        profile: &CameraControlProfile,
+    ) -> Result<Vec<KnownCameraControl>, NokhwaError> {
+        let writable = self
+            .camera_controls()?
+            .into_iter()
+            .filter(|control| !control.flag().contains(&KnownCameraControlFlag::ReadOnly))
+            .map(|control| control.control())
+            .collect::<Vec<KnownCameraControl>>();
+        let (to_apply, skipped): (Vec<_>, Vec<_>) = profile
+            .controls()
+            .iter()
+            .cloned()
+            .partition(|(id, _)| writable.contains(id));
+        let to_apply = to_apply
+            .into_iter()
+            .filter(|(id, _)| {
+                id.mode_control()
+                    .and_then(|mode| profile.value(mode).map(|value| mode.is_manual_mode(value)))
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<_>>();
+
+        self.set_camera_controls(&to_apply)
+            .into_iter()
+            .find(Result::is_err)
+            .unwrap_or(Ok(()))?;
+        Ok(skipped.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// Tells you how this backend reports control values that change on their own (e.g. through auto exposure).
+    ///
+    /// With [`ControlChangeMechanism::Events`], use [`subscribe_control_changes()`](CaptureTrait::subscribe_control_changes).
+    /// With [`ControlChangeMechanism::Polling`], use a [`ControlWatcher`](crate::types::ControlWatcher).
+    fn control_change_mechanism(&self) -> ControlChangeMechanism {
+        ControlChangeMechanism::Polling
+    }
+
+    /// Subscribes to control change events. The receiver gets a [`KnownCameraControl`] every time the camera reports that its value changed.
+    ///
+    /// Only backends with [`ControlChangeMechanism::Events`] support this.
+    /// # Errors
+    /// If the backend does not deliver control change events, this will error with [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError).
+    fn subscribe_control_changes(&mut self) -> Result<Receiver<KnownCameraControl>, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Sets [`KnownCameraControl::Exposure`] to the closest valid value to `micros` microseconds.
+    ///
+    /// The value is clamped to the control's range, and rounded to its step. Returns the exposure time that was actually set, in microseconds.
+    /// # Errors
+    /// If the exposure's [`ControlUnit`] is not [`ControlUnit::Microseconds`], or setting it fails, this will error.
+    fn set_exposure_micros(&mut self, micros: u64) -> Result<u64, NokhwaError> {
+        let exposure = self.camera_control(KnownCameraControl::Exposure)?;
+        match exposure.unit() {
+            ControlUnit::Microseconds(per_step) => {
+                set_control_in_unit(self, &exposure, micros, u64::from(per_step))
+            }
+            unit => Err(control_unit_error(&exposure, micros, unit)),
+        }
+    }
+
+    /// Sets [`KnownCameraControl::Focus`] to the closest valid value to `millimeters`.
+    ///
+    /// The value is clamped to the control's range, and rounded to its step. Returns the focus distance that was actually set, in millimeters.
+    /// # Errors
+    /// If the focus' [`ControlUnit`] is not [`ControlUnit::Millimeters`], or setting it fails, this will error.
+    fn set_focus_millimeters(&mut self, millimeters: u64) -> Result<u64, NokhwaError> {
+        let focus = self.camera_control(KnownCameraControl::Focus)?;
+        match focus.unit() {
+            ControlUnit::Millimeters => set_control_in_unit(self, &focus, millimeters, 1),
+            unit => Err(control_unit_error(&focus, millimeters, unit)),
+        }
+    }
+
     /// Will open the camera stream with set parameters. This will be called internally if you try and call [`frame()`](CaptureTrait::frame()) before you call [`open_stream()`](CaptureTrait::open_stream()).
     /// # Errors
     /// If the specific backend fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error.
@@ -172,6 +343,60 @@ pub trait CaptureTrait {
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureTrait::open_stream()) has not been called yet, this will error.
     fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError>;
 
+    /// Will write a frame from the camera **without** any processing applied into `buffer`, like [`frame_raw()`](CaptureTrait::frame_raw()),
+    /// returning the number of bytes written. Reusing `buffer` across calls avoids allocating for every frame.
+    /// # Errors
+    /// If `buffer` is too small for the frame, this will error with [`NokhwaError::BufferTooSmall`]. Otherwise, like [`frame_raw()`](CaptureTrait::frame_raw()).
+    fn write_frame_to(&mut self, buffer: &mut [u8]) -> Result<usize, NokhwaError> {
+        let frame = self.frame_raw()?;
+        let provided = buffer.len();
+        let dest = buffer
+            .get_mut(..frame.len())
+            .ok_or(NokhwaError::BufferTooSmall {
+                required: frame.len(),
+                provided,
+            })?;
+        dest.copy_from_slice(&frame);
+        Ok(frame.len())
+    }
+
+    /// Will write a frame from the camera decoded to RGB888 into `buffer`, returning the number of bytes written
+    /// (`width * height * 3`). Reusing `buffer` across calls avoids allocating for every frame.
+    ///
+    /// The default implementation decodes MJPEG and YUYV frames, and copies RGB888 ones.
+    /// # Errors
+    /// If `buffer` is too small for the frame, this will error with [`NokhwaError::BufferTooSmall`]. If the frame could not be read
+    /// or decoded, this will error.
+    fn write_frame_rgb_to(&mut self, buffer: &mut [u8]) -> Result<usize, NokhwaError> {
+        let format = self
+            .camera_format()
+            .ok_or_else(|| NokhwaError::GetPropertyError {
+                property: "CameraFormat".to_string(),
+                error: "No format set".to_string(),
+            })?;
+        let resolution = format.resolution();
+        let required = resolution.width() as usize * resolution.height() as usize * 3;
+        let provided = buffer.len();
+        let dest = buffer
+            .get_mut(..required)
+            .ok_or(NokhwaError::BufferTooSmall { required, provided })?;
+
+        let frame = self.frame_raw()?;
+        match format.format() {
+            FrameFormat::MJpeg => buf_mjpeg_to_rgb(&frame, dest, false)?,
+            FrameFormat::Yuv422 => buf_yuyv422_to_rgb(&frame, dest, false)?,
+            FrameFormat::Rgb8 if frame.len() == required => dest.copy_from_slice(&frame),
+            src => {
+                return Err(NokhwaError::ProcessFrameError {
+                    src,
+                    destination: "RGB888".to_string(),
+                    error: format!("Cannot decode a {}-byte frame into a buffer", frame.len()),
+                })
+            }
+        }
+        Ok(required)
+    }
+
     // #[cfg(feature = "wgpu-types")]
     // #[cfg_attr(feature = "docs-features", doc(cfg(feature = "wgpu-types")))]
     // /// Directly copies a frame to a Wgpu texture. This will automatically convert the frame into a RGBA frame.
@@ -236,6 +461,22 @@ pub trait CaptureTrait {
     /// # Errors
     /// Please check the `Quirks` section of each backend.
     fn stop_stream(&mut self) -> Result<(), NokhwaError>;
+
+    /// Stops the stream and opens it again with the current [`CameraFormat`], e.g. to recover from a glitch without
+    /// creating the camera again.
+    ///
+    /// If the stream fails to open again, it is left closed as after [`stop_stream()`](CaptureTrait::stop_stream()), never half open.
+    /// # Errors
+    /// If the stream could not be stopped or opened again, this will error.
+    fn restart_stream(&mut self) -> Result<(), NokhwaError> {
+        self.stop_stream()?;
+        if let Err(why) = self.open_stream() {
+            // the backend may have set up part of the stream before failing
+            let _ = self.stop_stream();
+            return Err(why);
+        }
+        Ok(())
+    }
 }
 
 impl<T> From<T> for Box<dyn CaptureTrait>
@@ -360,6 +601,49 @@ where
     }
 }
 
+// Converts `amount` to the closest raw value of `control`, sets it, and returns the amount that was set.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+fn set_control_in_unit<C: CaptureTrait + ?Sized>(
+    camera: &mut C,
+    control: &CameraControl,
+    amount: u64,
+    per_step: u64,
+) -> Result<u64, NokhwaError> {
+    let per_step = per_step.max(1);
+    let raw = (amount.saturating_add(per_step / 2) / per_step).min(i64::MAX as u64) as i64;
+    let raw = match control.description() {
+        ControlValueDescription::IntegerRange { min, max, step, .. } => {
+            let step = (*step).max(1);
+            let clamped = raw.clamp(*min, *max);
+            let rounded = min + (clamped - min + step / 2) / step * step;
+            // `max` itself may be off the step grid
+            if rounded > *max {
+                rounded - step
+            } else {
+                rounded
+            }
+        }
+        ControlValueDescription::Integer { .. } => raw,
+        _ => {
+            return Err(NokhwaError::SetPropertyError {
+                property: control.control().to_string(),
+                value: amount.to_string(),
+                error: "Not an integer control".to_string(),
+            })
+        }
+    };
+    camera.set_camera_control(control.control(), ControlValueSetter::Integer(raw))?;
+    Ok(raw.max(0) as u64 * per_step)
+}
+
+fn control_unit_error(control: &CameraControl, amount: u64, unit: ControlUnit) -> NokhwaError {
+    NokhwaError::SetPropertyError {
+        property: control.control().to_string(),
+        value: amount.to_string(),
+        error: format!("The control's unit is {unit}, not the requested one"),
+    }
+}
+
 pub trait OneShot: CaptureTrait {
     fn one_shot(&mut self) -> Result<Buffer, NokhwaError> {
         if self.is_stream_open() {
@@ -389,3 +673,287 @@ pub trait AsyncOneShot: AsyncCaptureTrait {
 }
 
 pub trait VirtualBackendTrait {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CameraIndex;
+
+    // A camera with white balance controls and nothing else, recording every control write in order.
+    struct MockCamera {
+        info: CameraInfo,
+        controls: Vec<CameraControl>,
+        writes: Vec<(KnownCameraControl, ControlValueSetter)>,
+    }
+
+    impl MockCamera {
+        fn new(auto: bool, kelvin: i64) -> Self {
+            MockCamera {
+                info: CameraInfo::new("Mock", "Mock Camera", "", &CameraIndex::Index(0)),
+                controls: vec![
+                    CameraControl::new(
+                        KnownCameraControl::WhiteBalanceAuto,
+                        "White Balance, Auto".to_string(),
+                        ControlValueDescription::Boolean {
+                            value: auto,
+                            default: true,
+                        },
+                        vec![],
+                        true,
+                    ),
+                    CameraControl::new(
+                        KnownCameraControl::WhiteBalance,
+                        "White Balance Temperature".to_string(),
+                        ControlValueDescription::IntegerRange {
+                            min: 2800,
+                            max: 6500,
+                            value: kelvin,
+                            step: 10,
+                            default: 4600,
+                        },
+                        vec![],
+                        !auto,
+                    ),
+                ],
+                writes: vec![],
+            }
+        }
+    }
+
+    impl CaptureTrait for MockCamera {
+        fn init(&mut self) -> Result<(), NokhwaError> {
+            Ok(())
+        }
+
+        fn init_with_format(&mut self, _: FormatFilter) -> Result<CameraFormat, NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn backend(&self) -> ApiBackend {
+            ApiBackend::Custom("Mock")
+        }
+
+        fn camera_info(&self) -> &CameraInfo {
+            &self.info
+        }
+
+        fn refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
+            Ok(())
+        }
+
+        fn camera_format(&self) -> Option<CameraFormat> {
+            None
+        }
+
+        fn set_camera_format(&mut self, _: CameraFormat) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn compatible_list_by_resolution(
+            &mut self,
+            _: SourceFrameFormat,
+        ) -> Result<HashMap<Resolution, Vec<u32>>, NokhwaError> {
+            Ok(HashMap::new())
+        }
+
+        fn compatible_fourcc(&mut self) -> Result<Vec<SourceFrameFormat>, NokhwaError> {
+            Ok(vec![])
+        }
+
+        fn resolution(&self) -> Option<Resolution> {
+            None
+        }
+
+        fn set_resolution(&mut self, _: Resolution) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn frame_rate(&self) -> Option<u32> {
+            None
+        }
+
+        fn set_frame_rate(&mut self, _: u32) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn frame_format(&self) -> SourceFrameFormat {
+            FrameFormat::Yuv422.into()
+        }
+
+        fn set_frame_format(&mut self, _: SourceFrameFormat) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn camera_control(
+            &self,
+            control: KnownCameraControl,
+        ) -> Result<CameraControl, NokhwaError> {
+            self.controls
+                .iter()
+                .find(|c| c.control() == control)
+                .cloned()
+                .ok_or_else(|| NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+            Ok(self.controls.clone())
+        }
+
+        fn set_camera_control(
+            &mut self,
+            id: KnownCameraControl,
+            value: ControlValueSetter,
+        ) -> Result<(), NokhwaError> {
+            self.camera_control(id)?;
+            self.writes.push((id, value));
+            Ok(())
+        }
+
+        fn open_stream(&mut self) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn is_stream_open(&self) -> bool {
+            false
+        }
+
+        fn frame(&mut self) -> Result<Buffer, NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+            Ok(())
+        }
+    }
+
+    fn exposure_control() -> CameraControl {
+        let mut exposure = CameraControl::new(
+            KnownCameraControl::Exposure,
+            "Exposure Time, Absolute".to_string(),
+            ControlValueDescription::IntegerRange {
+                min: 1,
+                max: 100,
+                value: 1,
+                step: 4,
+                default: 1,
+            },
+            vec![],
+            true,
+        );
+        exposure.set_unit(ControlUnit::Microseconds(100));
+        exposure
+    }
+
+    fn white_balance_profile(auto: bool, kelvin: i64) -> CameraControlProfile {
+        CameraControlProfile::new(vec![
+            (
+                KnownCameraControl::WhiteBalance,
+                ControlValueSetter::Integer(kelvin),
+            ),
+            (
+                KnownCameraControl::WhiteBalanceAuto,
+                ControlValueSetter::Boolean(auto),
+            ),
+        ])
+    }
+
+    #[test]
+    fn auto_white_balance_profile_skips_the_temperature() {
+        let mut camera = MockCamera::new(false, 3200);
+        let skipped = camera
+            .apply_control_profile(&white_balance_profile(true, 5600))
+            .unwrap();
+        assert!(skipped.is_empty());
+        assert_eq!(
+            camera.writes,
+            vec![(
+                KnownCameraControl::WhiteBalanceAuto,
+                ControlValueSetter::Boolean(true)
+            )]
+        );
+    }
+
+    #[test]
+    fn auto_then_manual_white_balance_profile() {
+        let mut camera = MockCamera::new(true, 4600);
+        camera
+            .apply_control_profile(&white_balance_profile(true, 4600))
+            .unwrap();
+        camera
+            .apply_control_profile(&white_balance_profile(false, 3200))
+            .unwrap();
+        assert_eq!(
+            camera.writes,
+            vec![
+                (
+                    KnownCameraControl::WhiteBalanceAuto,
+                    ControlValueSetter::Boolean(true)
+                ),
+                (
+                    KnownCameraControl::WhiteBalanceAuto,
+                    ControlValueSetter::Boolean(false)
+                ),
+                (
+                    KnownCameraControl::WhiteBalance,
+                    ControlValueSetter::Integer(3200)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn profile_round_trips_the_manual_temperature() {
+        let mut camera = MockCamera::new(false, 5100);
+        let profile = camera.control_profile().unwrap();
+        assert_eq!(
+            profile.value(KnownCameraControl::WhiteBalance),
+            Some(&ControlValueSetter::Integer(5100))
+        );
+        camera.apply_control_profile(&profile).unwrap();
+        assert_eq!(
+            camera.writes,
+            vec![
+                (
+                    KnownCameraControl::WhiteBalanceAuto,
+                    ControlValueSetter::Boolean(false)
+                ),
+                (
+                    KnownCameraControl::WhiteBalance,
+                    ControlValueSetter::Integer(5100)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn exposure_micros_rounds_to_the_nearest_step() {
+        let mut camera = MockCamera::new(false, 4600);
+        camera.controls.push(exposure_control());
+        // raw values 1, 5, 9, 13, ... are valid
+        assert_eq!(camera.set_exposure_micros(1000).unwrap(), 900);
+        assert_eq!(camera.set_exposure_micros(800).unwrap(), 900);
+        assert_eq!(camera.set_exposure_micros(1200).unwrap(), 1300);
+        assert_eq!(camera.set_exposure_micros(1149).unwrap(), 1300);
+        assert_eq!(
+            camera.writes.last(),
+            Some(&(
+                KnownCameraControl::Exposure,
+                ControlValueSetter::Integer(13)
+            ))
+        );
+    }
+
+    #[test]
+    fn exposure_micros_stays_in_range() {
+        let mut camera = MockCamera::new(false, 4600);
+        camera.controls.push(exposure_control());
+        assert_eq!(camera.set_exposure_micros(0).unwrap(), 100);
+        // 100 is off the step grid, 97 is the highest valid value
+        assert_eq!(camera.set_exposure_micros(10_000).unwrap(), 9700);
+        assert_eq!(camera.set_exposure_micros(u64::MAX).unwrap(), 9700);
+    }
+}
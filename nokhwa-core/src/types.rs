@@ -1,7 +1,9 @@
 use crate::{
     error::NokhwaError,
-    frame_format::{FrameFormat},
+    frame_format::{BayerPattern, FrameFormat},
+    traits::CaptureTrait,
 };
+use image::{ImageBuffer, Rgb};
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
@@ -9,6 +11,7 @@ use std::{
     borrow::Borrow,
     cmp::Ordering,
     fmt::{Display, Formatter},
+    time::{Duration, Instant},
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
@@ -304,6 +307,75 @@ impl Ord for Resolution {
     }
 }
 
+/// A rectangle in the frame, in pixels from the top left corner of the current [`Resolution`].
+/// This is used to select a region of interest, e.g. with [`set_ae_roi()`](crate::traits::CaptureTrait::set_ae_roi).
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Rect {
+    /// Create a new [`Rect`] from its top left corner (`x`, `y`) and size.
+    #[must_use]
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Create a [`Rect`] covering the whole frame.
+    #[must_use]
+    pub fn full_frame(resolution: Resolution) -> Self {
+        Rect::new(0, 0, resolution.width(), resolution.height())
+    }
+
+    /// Get the x coordinate of the left edge.
+    #[must_use]
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Get the y coordinate of the top edge.
+    #[must_use]
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Get the width.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Checks if this [`Rect`] is not empty and lies inside a frame of `resolution`.
+    #[must_use]
+    pub fn fits_in(&self, resolution: Resolution) -> bool {
+        self.width != 0
+            && self.height != 0
+            && u64::from(self.x) + u64::from(self.width) <= u64::from(resolution.width())
+            && u64::from(self.y) + u64::from(self.height) <= u64::from(resolution.height())
+    }
+}
+
+impl Display for Rect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 /// The frame rate of a camera.
@@ -316,7 +388,9 @@ pub enum FrameRate {
     Fraction {
         numerator: u16,
         denominator: u16,
-    }
+    },
+    /// The driver reports the frame interval in 100ns units, like UVC does (e.g. 333667 for 29.97 FPS)
+    Interval(u32),
 }
 
 impl FrameRate {
@@ -335,11 +409,16 @@ impl FrameRate {
         }
     }
 
+    pub fn new_interval(interval: u32) -> Self {
+        FrameRate::Interval(interval)
+    }
+
     pub fn as_float(&self) -> f32 {
         match self {
             FrameRate::Integer(fps) => fps as f32,
             FrameRate::Float(fps) => fps,
-            FrameRate::Fraction { numerator, denominator } => (numerator as f32) / (denominator as f32)
+            FrameRate::Fraction { numerator, denominator } => (numerator as f32) / (denominator as f32),
+            FrameRate::Interval(interval) => FRAME_INTERVAL_UNITS_PER_SECOND as f32 / *interval as f32,
         }
     }
 
@@ -348,10 +427,30 @@ impl FrameRate {
             FrameRate::Integer(fps) => *fps,
             FrameRate::Float(fps) => fps as u32,
             FrameRate::Fraction { numerator, denominator } => numerator / denominator,
+            FrameRate::Interval(_) => self.as_float().round() as u32,
+        }
+    }
+
+    /// Gets the frame interval in 100ns units.
+    ///
+    /// Use this instead of [`as_u32()`](FrameRate::as_u32) when matching against device descriptors, as rounding to whole
+    /// FPS makes e.g. 29.97 FPS (`333667`) and 30 FPS (`333333`) indistinguishable.
+    pub fn as_interval(&self) -> u32 {
+        match self {
+            FrameRate::Integer(fps) => FRAME_INTERVAL_UNITS_PER_SECOND / (*fps).max(1),
+            FrameRate::Float(fps) => (FRAME_INTERVAL_UNITS_PER_SECOND as f32 / fps).round() as u32,
+            FrameRate::Fraction { numerator, denominator } => {
+                (u64::from(FRAME_INTERVAL_UNITS_PER_SECOND) * u64::from(*denominator)
+                    / u64::from((*numerator).max(1))) as u32
+            }
+            FrameRate::Interval(interval) => *interval,
         }
     }
 }
 
+/// The number of 100ns frame interval units in one second.
+pub const FRAME_INTERVAL_UNITS_PER_SECOND: u32 = 10_000_000;
+
 impl Default for FrameRate {
     fn default() -> Self {
         FrameRate::Integer(30)
@@ -379,7 +478,7 @@ impl Display for FrameRate {
         match self {
             FrameRate::Integer(fps) => write!(f, "Framerate: {fps} FPS"),
             FrameRate::Float(fps) => write!(f, "Framerate: {fps} FPS"),
-            FrameRate::Fraction { .. } => {
+            FrameRate::Fraction { .. } | FrameRate::Interval(_) => {
                 let as_float = self.as_float();
                 write!(f, "Framerate: {as_float} FPS")
             }
@@ -471,6 +570,18 @@ impl CameraFormat {
         self.frame_rate
     }
 
+    /// Get the frame rate of the current [`CameraFormat`] as a [`f32`], without rounding it to a whole FPS.
+    #[must_use]
+    pub fn frame_rate_f32(&self) -> f32 {
+        self.frame_rate.as_float()
+    }
+
+    /// Get the frame interval of the current [`CameraFormat`] in 100ns units. See [`FrameRate::as_interval()`].
+    #[must_use]
+    pub fn frame_interval(&self) -> u32 {
+        self.frame_rate.as_interval()
+    }
+
     /// Set the [`CameraFormat`]'s frame rate.
     pub fn set_frame_rate(&mut self, frame_rate: FrameRate) {
         self.frame_rate = frame_rate;
@@ -646,26 +757,143 @@ impl Display for CameraInfo {
     }
 }
 
+/// Coarse flags of what a camera supports, for filtering a device list before opening anything.
+///
+/// These are read from the device's descriptors, so a `true` only means the camera claims to support it. Backends that cannot
+/// tell leave a flag `false`.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CameraCapabilities {
+    /// The camera has image controls (e.g. brightness, exposure) you can set.
+    pub controls: bool,
+    /// The camera can take still images separately from its video stream.
+    pub still_capture: bool,
+    /// The camera can pan and tilt.
+    pub pan_tilt: bool,
+    /// The camera can zoom.
+    pub zoom: bool,
+    /// The camera can focus (automatically or manually).
+    pub focus: bool,
+    /// The camera offers uncompressed formats (e.g. YUYV, Bayer), not only compressed ones like MJPEG.
+    pub raw_formats: bool,
+}
+
+/// What a backend does with a new frame when its frame queue is full, i.e. when the consumer is slower than the camera.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum BackpressurePolicy {
+    /// The oldest queued frame is dropped to make room. This is the default, and what a live preview wants.
+    #[default]
+    DropOldest,
+    /// The new frame is dropped.
+    DropNewest,
+    /// The producer waits until there is room, so no queued frame is lost. A live camera will drop frames on its side instead.
+    Block,
+    /// The new frame is dropped, and the next read errors.
+    Error,
+}
+
+/// How often a [`BackpressurePolicy`] had to act.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct BackpressureStats {
+    /// The number of frames dropped because the queue was full.
+    pub dropped_frames: u64,
+    /// The total time the producer waited for room, with [`BackpressurePolicy::Block`].
+    pub blocked_for: Duration,
+}
+
+/// A snapshot of the frames a camera wrapper received. The counters are read one after another, so a snapshot taken while
+/// frames arrive may be off by a frame between them, which is fine for display.
+///
+/// There is no measured frame rate. Divide [`frames_received`](FrameStats::frames_received) by the time between two resets
+/// of the stats instead.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct FrameStats {
+    /// The number of frames received from the camera.
+    pub frames_received: u64,
+    /// The number of received frames that were replaced by a newer one before they were read.
+    pub frames_dropped: u64,
+    /// The number of frames that were discarded instead of read, because they were older than the maximum frame age.
+    pub frames_too_old: u64,
+    /// The total size of the received frames, in bytes.
+    pub bytes_received: u64,
+    /// When the latest frame was received, or `None` if none was yet.
+    pub last_frame_at: Option<Instant>,
+    /// Whether the stream watchdog considers the stream stalled right now, i.e. it fired and no frame arrived since.
+    pub stalled: bool,
+    /// How many times the stream watchdog fired.
+    pub stalls: u64,
+}
+
 /// The list of known camera controls to the library. <br>
 /// These can control the picture brightness, etc. <br>
 /// Note that not all backends/devices support all these. Run [`supported_camera_controls()`](crate::traits::CaptureTrait::camera_controls) to see which ones can be set.
+///
+/// With the `serialize` feature, this serializes as its variant name (e.g. `"Brightness"`, or `{"Other": 42}`).
+/// These names are kept stable, see [`CameraControlProfile`].
 #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum KnownCameraControl {
     Brightness,
     Contrast,
+    /// The hue rotation. For UVC this is in hundredths of a degree, usually `-18000..=18000`.
     Hue,
     Saturation,
     Sharpness,
+    /// The gamma correction. For UVC this is gamma times 100, usually `100..=500`.
     Gamma,
+    /// Whether the white balance is managed by the driver. This is set through [`ControlValueSetter::Boolean`].
+    WhiteBalanceAuto,
+    /// The white balance temperature in Kelvin. This only takes effect if [`KnownCameraControl::WhiteBalanceAuto`] is off.
     WhiteBalance,
+    /// The red component gain of the white balance, for devices that do not expose a temperature.
+    /// This only takes effect if [`KnownCameraControl::WhiteBalanceAuto`] is off.
+    WhiteBalanceRed,
+    /// The blue component gain of the white balance, for devices that do not expose a temperature.
+    /// This only takes effect if [`KnownCameraControl::WhiteBalanceAuto`] is off.
+    WhiteBalanceBlue,
+    /// The backlight compensation, which brightens subjects in front of a bright background (e.g. a window).
+    /// Cameras that only support on/off report this as [`ControlValueDescription::Boolean`], others as an
+    /// [`ControlValueDescription::IntegerRange`] of levels.
     BacklightComp,
+    /// The sensor (analog/digital) gain. Raising this brightens the image without lengthening the exposure, at the cost of noise.
+    /// Many cameras manage gain together with auto-exposure and ignore a manually set value unless
+    /// [`KnownCameraControl::ExposureMode`] allows manual exposure. Backends report this with [`KnownCameraControlFlag::Automatic`].
     Gain,
+    /// The anti-flicker setting matching the mains frequency of the lighting, see [`PowerLineFrequency`].
+    /// This is set through [`ControlValueSetter::EnumValue`], the values the device accepts are listed in [`ControlValueDescription::Menu`].
+    PowerLineFrequency,
+    /// The absolute pan (horizontal rotation). For UVC this is in arc-seconds (1/3600 of a degree), positive is clockwise when viewed from above.
     Pan,
+    /// The absolute tilt (vertical rotation). For UVC this is in arc-seconds (1/3600 of a degree), positive points the camera up.
     Tilt,
+    /// The pan direction and speed, for devices that can only pan relatively.
+    /// Negative values pan counter-clockwise, positive values pan clockwise, `0` stops. The magnitude is the speed.
+    PanRelative,
+    /// The tilt direction and speed, for devices that can only tilt relatively.
+    /// Negative values tilt down, positive values tilt up, `0` stops. The magnitude is the speed.
+    TiltRelative,
+    /// The absolute zoom (focal length). This is done by the camera or its driver before the frame reaches you, so it stacks
+    /// with any cropping done afterwards (e.g. the browser's `crop-and-scale` resize mode). Use one or the other to avoid zooming twice.
     Zoom,
+    /// The zoom direction and speed, for devices that can only zoom relatively.
+    /// Negative values zoom out (wide), positive values zoom in (telephoto), `0` stops. The magnitude is the speed.
+    ZoomRelative,
+    /// The auto-exposure mode, see [`ExposureMode`]. This is set through [`ControlValueSetter::EnumValue`].
+    ExposureMode,
+    /// The exposure value. This only takes effect if [`KnownCameraControl::ExposureMode`] allows manual exposure.
     Exposure,
+    /// Whether auto exposure may lower the frame rate to expose for longer than one frame interval (`true`), or has to keep
+    /// the configured frame rate (`false`). This is a [`ControlValueDescription::Boolean`].
+    ///
+    /// With this on, cameras in dim light drop from e.g. 30 FPS to 7 FPS, regardless of the [`CameraFormat`] you asked for.
+    /// Turn it off to keep the frame rate, at the cost of a darker or noisier picture. This only matters while
+    /// [`KnownCameraControl::ExposureMode`] is automatic, with manual exposure the [`KnownCameraControl::Exposure`] value decides.
+    ExposurePriority,
     Iris,
+    /// The auto-focus mode, see [`FocusMode`]. This is set through [`ControlValueSetter::EnumValue`].
+    FocusMode,
+    /// The absolute focus (lens) position. This only takes effect if [`KnownCameraControl::FocusMode`] is [`FocusMode::Manual`].
     Focus,
     /// Other camera control. Listed is the ID.
     /// Wasteful, however is needed for a unified API across Windows, Linux, and MacOSX due to Microsoft's usage of GUIDs.
@@ -676,7 +904,7 @@ pub enum KnownCameraControl {
 
 /// All camera controls in an array.
 #[must_use]
-pub const fn all_known_camera_controls() -> [KnownCameraControl; 15] {
+pub const fn all_known_camera_controls() -> [KnownCameraControl; 25] {
     [
         KnownCameraControl::Brightness,
         KnownCameraControl::Contrast,
@@ -684,24 +912,254 @@ pub const fn all_known_camera_controls() -> [KnownCameraControl; 15] {
         KnownCameraControl::Saturation,
         KnownCameraControl::Sharpness,
         KnownCameraControl::Gamma,
+        KnownCameraControl::WhiteBalanceAuto,
         KnownCameraControl::WhiteBalance,
+        KnownCameraControl::WhiteBalanceRed,
+        KnownCameraControl::WhiteBalanceBlue,
         KnownCameraControl::BacklightComp,
         KnownCameraControl::Gain,
+        KnownCameraControl::PowerLineFrequency,
         KnownCameraControl::Pan,
         KnownCameraControl::Tilt,
+        KnownCameraControl::PanRelative,
+        KnownCameraControl::TiltRelative,
         KnownCameraControl::Zoom,
+        KnownCameraControl::ZoomRelative,
+        KnownCameraControl::ExposureMode,
         KnownCameraControl::Exposure,
+        KnownCameraControl::ExposurePriority,
         KnownCameraControl::Iris,
+        KnownCameraControl::FocusMode,
         KnownCameraControl::Focus,
     ]
 }
 
+impl KnownCameraControl {
+    /// Gets the mode control that governs this control, if there is one.
+    ///
+    /// Some controls are silently ignored by the driver unless their mode control is set to manual first
+    /// (e.g. [`Exposure`](KnownCameraControl::Exposure) while [`ExposureMode`](KnownCameraControl::ExposureMode) is [`ExposureMode::Auto`]).
+    /// Backends use this to switch the mode before writing the value.
+    #[must_use]
+    pub fn mode_control(&self) -> Option<KnownCameraControl> {
+        match self {
+            KnownCameraControl::Exposure => Some(KnownCameraControl::ExposureMode),
+            KnownCameraControl::Focus => Some(KnownCameraControl::FocusMode),
+            KnownCameraControl::WhiteBalance
+            | KnownCameraControl::WhiteBalanceRed
+            | KnownCameraControl::WhiteBalanceBlue => Some(KnownCameraControl::WhiteBalanceAuto),
+            _ => None,
+        }
+    }
+
+    /// Gets the value that puts this mode control into manual mode, if this is a mode control.
+    #[must_use]
+    pub fn manual_mode_value(&self) -> Option<ControlValueSetter> {
+        match self {
+            KnownCameraControl::ExposureMode => {
+                Some(ControlValueSetter::EnumValue(ExposureMode::Manual.into()))
+            }
+            KnownCameraControl::FocusMode => {
+                Some(ControlValueSetter::EnumValue(FocusMode::Manual.into()))
+            }
+            KnownCameraControl::WhiteBalanceAuto => Some(ControlValueSetter::Boolean(false)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `value`, the current value of this mode control, lets the controls it governs be set manually.
+    #[must_use]
+    pub fn is_manual_mode(&self, value: &ControlValueSetter) -> bool {
+        match (self, value) {
+            (KnownCameraControl::ExposureMode, ControlValueSetter::EnumValue(mode)) => {
+                ExposureMode::try_from(*mode).map_or(false, ExposureMode::allows_manual_exposure)
+            }
+            (KnownCameraControl::FocusMode, ControlValueSetter::EnumValue(mode)) => {
+                matches!(FocusMode::try_from(*mode), Ok(FocusMode::Manual))
+            }
+            (KnownCameraControl::WhiteBalanceAuto, ControlValueSetter::Boolean(auto)) => !auto,
+            _ => true,
+        }
+    }
+}
+
 impl Display for KnownCameraControl {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", &self)
     }
 }
 
+/// The auto-exposure mode of a camera, used with [`KnownCameraControl::ExposureMode`].
+///
+/// The exposure value ([`KnownCameraControl::Exposure`]) only sticks when the mode is [`ExposureMode::Manual`]
+/// or [`ExposureMode::ShutterPriority`]. Setting the mode back to [`ExposureMode::Auto`] hands exposure back to the driver.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ExposureMode {
+    /// Exposure time and iris are both managed by the driver.
+    Auto,
+    /// Exposure time and iris are both managed by you.
+    Manual,
+    /// Exposure time is managed by you, iris is managed by the driver.
+    ShutterPriority,
+    /// Iris is managed by you, exposure time is managed by the driver. Not all devices support this.
+    AperturePriority,
+}
+
+impl ExposureMode {
+    /// Returns `true` if a manually set exposure value is honoured in this mode.
+    #[must_use]
+    pub fn allows_manual_exposure(self) -> bool {
+        matches!(self, ExposureMode::Manual | ExposureMode::ShutterPriority)
+    }
+
+    /// Converts this mode into the UVC `CT_AE_MODE_CONTROL` bitmap value.
+    #[must_use]
+    pub fn as_uvc_ae_mode(self) -> u8 {
+        match self {
+            ExposureMode::Manual => 1,
+            ExposureMode::Auto => 2,
+            ExposureMode::ShutterPriority => 4,
+            ExposureMode::AperturePriority => 8,
+        }
+    }
+
+    /// Converts a UVC `CT_AE_MODE_CONTROL` bitmap value into an [`ExposureMode`].
+    #[must_use]
+    pub fn from_uvc_ae_mode(mode: u8) -> Option<Self> {
+        match mode {
+            1 => Some(ExposureMode::Manual),
+            2 => Some(ExposureMode::Auto),
+            4 => Some(ExposureMode::ShutterPriority),
+            8 => Some(ExposureMode::AperturePriority),
+            _ => None,
+        }
+    }
+}
+
+impl From<ExposureMode> for i64 {
+    fn from(mode: ExposureMode) -> Self {
+        i64::from(mode.as_uvc_ae_mode())
+    }
+}
+
+impl TryFrom<i64> for ExposureMode {
+    type Error = NokhwaError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        u8::try_from(value)
+            .ok()
+            .and_then(ExposureMode::from_uvc_ae_mode)
+            .ok_or(NokhwaError::StructureError {
+                structure: "ExposureMode".to_string(),
+                error: format!("Invalid exposure mode {value}"),
+            })
+    }
+}
+
+impl Display for ExposureMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The anti-flicker setting of a camera, used with [`KnownCameraControl::PowerLineFrequency`].
+///
+/// The values match the UVC `PU_POWER_LINE_FREQUENCY_CONTROL` and V4L2 `V4L2_CID_POWER_LINE_FREQUENCY` encoding.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum PowerLineFrequency {
+    /// No anti-flicker.
+    Disabled,
+    /// For 50 Hz mains (e.g. most of Europe, Asia, Africa and Oceania).
+    Hz50,
+    /// For 60 Hz mains (e.g. most of the Americas).
+    Hz60,
+    /// The driver detects the frequency. Only UVC 1.5 devices support this.
+    Auto,
+}
+
+impl From<PowerLineFrequency> for i64 {
+    fn from(frequency: PowerLineFrequency) -> Self {
+        match frequency {
+            PowerLineFrequency::Disabled => 0,
+            PowerLineFrequency::Hz50 => 1,
+            PowerLineFrequency::Hz60 => 2,
+            PowerLineFrequency::Auto => 3,
+        }
+    }
+}
+
+impl TryFrom<i64> for PowerLineFrequency {
+    type Error = NokhwaError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PowerLineFrequency::Disabled),
+            1 => Ok(PowerLineFrequency::Hz50),
+            2 => Ok(PowerLineFrequency::Hz60),
+            3 => Ok(PowerLineFrequency::Auto),
+            _ => Err(NokhwaError::StructureError {
+                structure: "PowerLineFrequency".to_string(),
+                error: format!("Invalid power line frequency {value}"),
+            }),
+        }
+    }
+}
+
+impl Display for PowerLineFrequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerLineFrequency::Disabled => write!(f, "Disabled"),
+            PowerLineFrequency::Hz50 => write!(f, "50 Hz"),
+            PowerLineFrequency::Hz60 => write!(f, "60 Hz"),
+            PowerLineFrequency::Auto => write!(f, "Auto"),
+        }
+    }
+}
+
+/// The auto-focus mode of a camera, used with [`KnownCameraControl::FocusMode`].
+///
+/// The values match the UVC `CT_FOCUS_AUTO_CONTROL` and V4L2 `V4L2_CID_FOCUS_AUTO` encoding, where `0` is manual and `1` is auto.
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum FocusMode {
+    /// The focus is continuously managed by the driver.
+    Auto,
+    /// The focus is held at the position set through [`KnownCameraControl::Focus`].
+    Manual,
+}
+
+impl From<FocusMode> for i64 {
+    fn from(mode: FocusMode) -> Self {
+        match mode {
+            FocusMode::Manual => 0,
+            FocusMode::Auto => 1,
+        }
+    }
+}
+
+impl TryFrom<i64> for FocusMode {
+    type Error = NokhwaError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FocusMode::Manual),
+            1 => Ok(FocusMode::Auto),
+            _ => Err(NokhwaError::StructureError {
+                structure: "FocusMode".to_string(),
+                error: format!("Invalid focus mode {value}"),
+            }),
+        }
+    }
+}
+
+impl Display for FocusMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
 /// This tells you weather a [`KnownCameraControl`] is automatically managed by the OS/Driver
 /// or manually managed by you, the programmer.
 #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
@@ -725,6 +1183,9 @@ impl Display for KnownCameraControlFlag {
 /// The values for a [`CameraControl`].
 ///
 /// This provides a wide range of values that can be used to control a camera.
+///
+/// With the `serialize` feature, this serializes as its variant name holding its fields, e.g.
+/// `{"Boolean": {"value": true, "default": false}}`. These names are kept stable, see [`CameraControlProfile`].
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum ControlValueDescription {
@@ -779,6 +1240,14 @@ pub enum ControlValueDescription {
         possible: Vec<i64>,
         default: i64,
     },
+    /// Like [`Enum`](ControlValueDescription::Enum), but each accepted value carries a human-readable label.
+    ///
+    /// This is set through [`ControlValueSetter::EnumValue`].
+    Menu {
+        value: i64,
+        options: Vec<(i64, String)>,
+        default: i64,
+    },
     RGB {
         value: (f64, f64, f64),
         max: (f64, f64, f64),
@@ -817,7 +1286,8 @@ impl ControlValueDescription {
             ControlValueDescription::Point { value, .. } => {
                 ControlValueSetter::Point(value.0, value.1)
             }
-            ControlValueDescription::Enum { value, .. } => ControlValueSetter::EnumValue(*value),
+            ControlValueDescription::Enum { value, .. }
+            | ControlValueDescription::Menu { value, .. } => ControlValueSetter::EnumValue(*value),
             ControlValueDescription::RGB { value, .. } => {
                 ControlValueSetter::RGB(value.0, value.1, value.2)
             }
@@ -827,6 +1297,46 @@ impl ControlValueDescription {
         }
     }
 
+    /// Get the default value of this [`ControlValueDescription`], as reported by the device.
+    ///
+    /// Returns `None` if the description does not carry a default.
+    #[must_use]
+    pub fn default_value(&self) -> Option<ControlValueSetter> {
+        match self {
+            ControlValueDescription::None | ControlValueDescription::StringList { .. } => None,
+            ControlValueDescription::Integer { default, .. }
+            | ControlValueDescription::IntegerRange { default, .. } => {
+                Some(ControlValueSetter::Integer(*default))
+            }
+            ControlValueDescription::Float { default, .. }
+            | ControlValueDescription::FloatRange { default, .. } => {
+                Some(ControlValueSetter::Float(*default))
+            }
+            ControlValueDescription::Boolean { default, .. } => {
+                Some(ControlValueSetter::Boolean(*default))
+            }
+            ControlValueDescription::String { default, .. } => {
+                default.clone().map(ControlValueSetter::String)
+            }
+            ControlValueDescription::Bytes { default, .. } => {
+                Some(ControlValueSetter::Bytes(default.clone()))
+            }
+            ControlValueDescription::KeyValuePair { default, .. } => {
+                Some(ControlValueSetter::KeyValue(default.0, default.1))
+            }
+            ControlValueDescription::Point { default, .. } => {
+                Some(ControlValueSetter::Point(default.0, default.1))
+            }
+            ControlValueDescription::Enum { default, .. }
+            | ControlValueDescription::Menu { default, .. } => {
+                Some(ControlValueSetter::EnumValue(*default))
+            }
+            ControlValueDescription::RGB { default, .. } => {
+                Some(ControlValueSetter::RGB(default.0, default.1, default.2))
+            }
+        }
+    }
+
     /// Verifies if the [setter](ControlValueSetter) is valid for the provided [`ControlValueDescription`].
     /// - `true` => Is valid.
     /// - `false` => Is not valid.
@@ -915,6 +1425,10 @@ impl ControlValueDescription {
                 Some(e) => possible.contains(e),
                 None => false,
             },
+            ControlValueDescription::Menu { options, .. } => match setter.as_enum() {
+                Some(e) => options.iter().any(|(option, _)| option == e),
+                None => false,
+            },
             ControlValueDescription::RGB { max, .. } => match setter.as_rgb() {
                 Some(v) => *v.0 >= max.0 && *v.1 >= max.1 && *v.2 >= max.2,
                 None => false,
@@ -1076,6 +1590,13 @@ impl Display for ControlValueDescription {
                     "Current: {value}, Possible Values: {possible:?}, Default: {default}",
                 )
             }
+            ControlValueDescription::Menu {
+                value,
+                options,
+                default,
+            } => {
+                write!(f, "Current: {value}, Options: {options:?}, Default: {default}",)
+            }
             ControlValueDescription::RGB {
                 value,
                 max,
@@ -1118,6 +1639,8 @@ pub struct CameraControl {
     description: ControlValueDescription,
     flag: Vec<KnownCameraControlFlag>,
     active: bool,
+    #[cfg_attr(feature = "serialize", serde(default))]
+    unit: ControlUnit,
 }
 
 impl CameraControl {
@@ -1136,6 +1659,7 @@ impl CameraControl {
             description,
             flag,
             active,
+            unit: ControlUnit::Unknown,
         }
     }
 
@@ -1182,6 +1706,49 @@ impl CameraControl {
     pub fn set_active(&mut self, active: bool) {
         self.active = active;
     }
+
+    /// Gets the [`ControlUnit`] of this [`CameraControl`]'s value.
+    #[must_use]
+    pub fn unit(&self) -> ControlUnit {
+        self.unit
+    }
+
+    /// Sets the [`ControlUnit`] of this [`CameraControl`]'s value.
+    pub fn set_unit(&mut self, unit: ControlUnit) {
+        self.unit = unit;
+    }
+}
+
+/// The physical unit of a [`CameraControl`]'s value, so the same value means the same thing on every camera.
+///
+/// Backends only set this where the camera API defines the unit. Everything else is [`ControlUnit::Unknown`], as the scale is up to
+/// the camera (e.g. exposure "156" may be anything).
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ControlUnit {
+    /// The unit is not known.
+    #[default]
+    Unknown,
+    /// Each step of the value is this many microseconds, e.g. `100` for UVC and V4L2 exposure times.
+    Microseconds(u32),
+    /// Millimeters, e.g. the focus distance of UVC cameras.
+    Millimeters,
+    /// Kelvin, e.g. a white balance temperature.
+    Kelvin,
+    /// Arc-seconds (1/3600 of a degree), e.g. the pan and tilt angle of UVC cameras.
+    ArcSeconds,
+}
+
+impl Display for ControlUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ControlUnit::Unknown => write!(f, "Unknown"),
+            ControlUnit::Microseconds(per_step) => write!(f, "{per_step} µs"),
+            ControlUnit::Millimeters => write!(f, "mm"),
+            ControlUnit::Kelvin => write!(f, "K"),
+            ControlUnit::ArcSeconds => write!(f, "arc-seconds"),
+        }
+    }
 }
 
 impl Display for CameraControl {
@@ -1195,6 +1762,9 @@ impl Display for CameraControl {
 }
 
 /// The setter for a control value
+///
+/// With the `serialize` feature, this serializes as its variant name holding the value, e.g. `{"Integer": 128}` or `{"EnumValue": 1}`.
+/// These names are kept stable, see [`CameraControlProfile`].
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum ControlValueSetter {
@@ -1343,6 +1913,142 @@ impl Display for ControlValueSetter {
     }
 }
 
+/// A snapshot of the values of a camera's controls, made by [`control_profile()`](crate::traits::CaptureTrait::control_profile)
+/// and written back by [`apply_control_profile()`](crate::traits::CaptureTrait::apply_control_profile).
+///
+/// This is plain data, so it can be stored however you like and applied to the same camera later (e.g. after it was replugged).
+///
+/// # Serialization
+/// With the `serialize` feature, a profile serializes as a `controls` list of `[control, value]` pairs, where the control is a
+/// [`KnownCameraControl`] and the value a [`ControlValueSetter`], each written as its variant name. In JSON:
+/// ```json
+/// { "controls": [["Brightness", { "Integer": 128 }], ["ExposureMode", { "EnumValue": 1 }]] }
+/// ```
+/// The field and variant names are part of the public API. They are not renamed outside of a breaking release, and new
+/// controls are only ever added, so a stored profile keeps loading after an upgrade.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CameraControlProfile {
+    controls: Vec<(KnownCameraControl, ControlValueSetter)>,
+}
+
+impl CameraControlProfile {
+    /// Creates a new [`CameraControlProfile`] from a list of controls and their values.
+    #[must_use]
+    pub fn new(controls: Vec<(KnownCameraControl, ControlValueSetter)>) -> Self {
+        CameraControlProfile { controls }
+    }
+
+    /// Gets the controls and their values in this profile.
+    #[must_use]
+    pub fn controls(&self) -> &[(KnownCameraControl, ControlValueSetter)] {
+        &self.controls
+    }
+
+    /// Gets the value of `control` in this profile, if it is in it.
+    #[must_use]
+    pub fn value(&self, control: KnownCameraControl) -> Option<&ControlValueSetter> {
+        self.controls
+            .iter()
+            .find(|(id, _)| *id == control)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of `control` in this profile, replacing the previous value if there was one.
+    pub fn set_value(&mut self, control: KnownCameraControl, value: ControlValueSetter) {
+        match self.controls.iter_mut().find(|(id, _)| *id == control) {
+            Some((_, old)) => *old = value,
+            None => self.controls.push((control, value)),
+        }
+    }
+
+    /// Removes `control` from this profile, returning its value if it was in it.
+    pub fn remove(&mut self, control: KnownCameraControl) -> Option<ControlValueSetter> {
+        let idx = self.controls.iter().position(|(id, _)| *id == control)?;
+        Some(self.controls.remove(idx).1)
+    }
+}
+
+/// How a backend tells you that control values changed on their own, e.g. because auto exposure or auto white balance
+/// rewrote them. See [`control_change_mechanism()`](crate::traits::CaptureTrait::control_change_mechanism).
+#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ControlChangeMechanism {
+    /// The backend cannot notify you. Poll with a [`ControlWatcher`] instead.
+    Polling,
+    /// The backend sends the changed controls through [`subscribe_control_changes()`](crate::traits::CaptureTrait::subscribe_control_changes).
+    Events,
+}
+
+impl Display for ControlChangeMechanism {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Finds the controls whose values changed between calls to [`refresh_controls()`](ControlWatcher::refresh_controls),
+/// for backends that use [`ControlChangeMechanism::Polling`].
+///
+/// This works with every backend, but only sees what the camera reports at the moment you call it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ControlWatcher {
+    last: Option<CameraControlProfile>,
+}
+
+impl ControlWatcher {
+    /// Creates a new [`ControlWatcher`] that has not seen any values yet.
+    #[must_use]
+    pub fn new() -> Self {
+        ControlWatcher::default()
+    }
+
+    /// Reads every control of `camera` and returns the ones whose value changed since the last call.
+    ///
+    /// The first call has nothing to compare against, so it returns every control. Controls that appeared or disappeared
+    /// since the last call count as changed.
+    /// # Errors
+    /// If the controls cannot be listed, this will error. The values from the last successful call are kept.
+    pub fn refresh_controls<C: CaptureTrait + ?Sized>(
+        &mut self,
+        camera: &C,
+    ) -> Result<Vec<KnownCameraControl>, NokhwaError> {
+        let current = CameraControlProfile::new(
+            camera
+                .camera_controls()?
+                .into_iter()
+                .map(|control| (control.control(), control.value()))
+                .collect(),
+        );
+
+        let changed = match &self.last {
+            Some(last) => {
+                let mut changed = current
+                    .controls()
+                    .iter()
+                    .filter(|(id, value)| last.value(*id) != Some(value))
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<KnownCameraControl>>();
+                changed.extend(
+                    last.controls()
+                        .iter()
+                        .filter(|(id, _)| current.value(*id).is_none())
+                        .map(|(id, _)| *id),
+                );
+                changed
+            }
+            None => current.controls().iter().map(|(id, _)| *id).collect(),
+        };
+        self.last = Some(current);
+        Ok(changed)
+    }
+
+    /// Gets the values seen by the last call to [`refresh_controls()`](ControlWatcher::refresh_controls), if there was one.
+    #[must_use]
+    pub fn last_values(&self) -> Option<&CameraControlProfile> {
+        self.last.as_ref()
+    }
+}
+
 /// The list of known capture backends to the library. <br>
 /// - `AUTO` is special - it tells the Camera struct to automatically choose a backend most suited for the current platform.
 /// - `AVFoundation` - Uses `AVFoundation` on `MacOSX`
@@ -1568,19 +2274,15 @@ pub fn yuyv422_to_rgb(data: &[u8], rgba: bool) -> Result<Vec<u8>, NokhwaError> {
 
 /// Same as [`yuyv422_to_rgb`] but with a destination buffer instead of a return `Vec<u8>`
 /// # Errors
-/// If the stream is invalid Yuv422, or the destination buffer is not large enough, this will error.
+/// If the stream is invalid Yuv422, or the destination buffer is not exactly large enough, this will error.
 #[inline]
 pub fn buf_yuyv422_to_rgb(data: &[u8], dest: &mut [u8], rgba: bool) -> Result<(), NokhwaError> {
-    let mut buf: Vec<u8> = Vec::new();
-    if data.len() % 4 != 0 {
-        return Err(NokhwaError::ProcessFrameError {
-            src: FrameFormat::Yuv422.into(),
-            destination: "RGB888".to_string(),
-            error: "Assertion failure, the YUV stream isn't 4:2:2! (wrong number of bytes)"
-                .to_string(),
-        });
-    }
-    for chunk in data.chunks_exact(4) {
+    check_yuyv422_buffers(data, dest, rgba)?;
+    let pixel_size = if rgba { 4 } else { 3 };
+    for (chunk, pixels) in data
+        .chunks_exact(4)
+        .zip(dest.chunks_exact_mut(2 * pixel_size))
+    {
         let y0 = chunk[0] as f32;
         let u = chunk[1] as f32;
         let y1 = chunk[2] as f32;
@@ -1595,14 +2297,361 @@ pub fn buf_yuyv422_to_rgb(data: &[u8], dest: &mut [u8], rgba: bool) -> Result<()
         let b1 = y1 + 1.732446 * (u - 128.);
 
         if rgba {
-            buf.extend_from_slice(&[
+            pixels.copy_from_slice(&[
                 r0 as u8, g0 as u8, b0 as u8, 255, r1 as u8, g1 as u8, b1 as u8, 255,
             ]);
         } else {
-            buf.extend_from_slice(&[r0 as u8, g0 as u8, b0 as u8, r1 as u8, g1 as u8, b1 as u8]);
+            pixels.copy_from_slice(&[r0 as u8, g0 as u8, b0 as u8, r1 as u8, g1 as u8, b1 as u8]);
+        }
+    }
+    Ok(())
+}
+
+fn check_yuyv422_buffers(data: &[u8], dest: &[u8], rgba: bool) -> Result<(), NokhwaError> {
+    if data.len() % 4 != 0 {
+        return Err(NokhwaError::ProcessFrameError {
+            src: FrameFormat::Yuv422.into(),
+            destination: "RGB888".to_string(),
+            error: "Assertion failure, the YUV stream isn't 4:2:2! (wrong number of bytes)"
+                .to_string(),
+        });
+    }
+    let required = yuyv422_predicted_size(data.len(), rgba);
+    if dest.len() != required {
+        return Err(NokhwaError::ProcessFrameError {
+            src: FrameFormat::Yuv422.into(),
+            destination: "RGB888".to_string(),
+            error: format!(
+                "Destination buffer is {} bytes, expected {required}",
+                dest.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// The standard whose coefficients a camera's YUV data was encoded with.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum YuvColorSpace {
+    /// ITU-R BT.601, used by most SD cameras.
+    #[default]
+    Bt601,
+    /// ITU-R BT.709, used by most HD cameras.
+    Bt709,
+}
+
+/// The range of YUV values a camera uses for black to white.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum YuvRange {
+    /// Y is in `16..=235` and U, V in `16..=240`, also called TV or studio range.
+    #[default]
+    Limited,
+    /// All of `0..=255` is used, also called PC or JPEG range.
+    Full,
+}
+
+/// How [`yuyv422_to_rgb_with()`] converts YUV to RGB.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct YuvConversion {
+    pub color_space: YuvColorSpace,
+    pub range: YuvRange,
+}
+
+impl YuvConversion {
+    #[must_use]
+    pub fn new(color_space: YuvColorSpace, range: YuvRange) -> Self {
+        YuvConversion { color_space, range }
+    }
+
+    /// Converts one YUV pixel to RGB.
+    #[allow(clippy::many_single_char_names)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[must_use]
+    pub fn to_rgb(&self, y: u8, u: u8, v: u8) -> [u8; 3] {
+        let (kr, kb) = match self.color_space {
+            YuvColorSpace::Bt601 => (0.299, 0.114),
+            YuvColorSpace::Bt709 => (0.2126, 0.0722),
+        };
+        let kg = 1.0 - kr - kb;
+        let (y, cb, cr) = match self.range {
+            YuvRange::Limited => (
+                (f32::from(y) - 16.0) * 255.0 / 219.0,
+                (f32::from(u) - 128.0) * 255.0 / 224.0,
+                (f32::from(v) - 128.0) * 255.0 / 224.0,
+            ),
+            YuvRange::Full => (
+                f32::from(y),
+                f32::from(u) - 128.0,
+                f32::from(v) - 128.0,
+            ),
+        };
+
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let g = y - 2.0 * kb * (1.0 - kb) / kg * cb - 2.0 * kr * (1.0 - kr) / kg * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        [
+            r.round().clamp(0.0, 255.0) as u8,
+            g.round().clamp(0.0, 255.0) as u8,
+            b.round().clamp(0.0, 255.0) as u8,
+        ]
+    }
+
+    /// Converts one RGB pixel to YUV, the inverse of [`to_rgb()`](YuvConversion::to_rgb).
+    #[allow(clippy::many_single_char_names)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[must_use]
+    pub fn from_rgb(&self, r: u8, g: u8, b: u8) -> [u8; 3] {
+        let (kr, kb) = match self.color_space {
+            YuvColorSpace::Bt601 => (0.299, 0.114),
+            YuvColorSpace::Bt709 => (0.2126, 0.0722),
+        };
+        let kg = 1.0 - kr - kb;
+        let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+
+        let y = kr * r + kg * g + kb * b;
+        let cb = (b - y) / (2.0 * (1.0 - kb));
+        let cr = (r - y) / (2.0 * (1.0 - kr));
+        let (y, u, v) = match self.range {
+            YuvRange::Limited => (
+                y * 219.0 / 255.0 + 16.0,
+                cb * 224.0 / 255.0 + 128.0,
+                cr * 224.0 / 255.0 + 128.0,
+            ),
+            YuvRange::Full => (y, cb + 128.0, cr + 128.0),
+        };
+        [
+            y.round().clamp(0.0, 255.0) as u8,
+            u.round().clamp(0.0, 255.0) as u8,
+            v.round().clamp(0.0, 255.0) as u8,
+        ]
+    }
+}
+
+/// A frame in planar I420 (YUV 4:2:0) layout, as video encoders (e.g. x264, libvpx) take it.
+///
+/// The Y plane has one byte per pixel. The U and V planes have one byte per 2x2 block of pixels, rounded up for odd sizes, so
+/// each is `width * height / 4` bytes for even sizes. The planes are stored back to back without row padding, so each
+/// stride is the width of its plane.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct YuvPlanarFrame {
+    resolution: Resolution,
+    data: Vec<u8>,
+}
+
+impl YuvPlanarFrame {
+    /// Creates a [`YuvPlanarFrame`] from the Y, U and V planes stored back to back, converted from `src`.
+    /// # Errors
+    /// If `data` is not the size of the three planes at `resolution`, this will error with `src` as the source format.
+    pub fn new(
+        src: FrameFormat,
+        resolution: Resolution,
+        data: Vec<u8>,
+    ) -> Result<Self, NokhwaError> {
+        let frame = YuvPlanarFrame { resolution, data };
+        let expected = frame.y_len() + 2 * frame.uv_len();
+        if frame.data.len() != expected {
+            return Err(NokhwaError::ProcessFrameError {
+                src,
+                destination: "I420".to_string(),
+                error: format!("Expected {expected} bytes, got {}", frame.data.len()),
+            });
+        }
+        Ok(frame)
+    }
+
+    #[must_use]
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// The number of bytes between the starts of two rows of the Y plane.
+    #[must_use]
+    pub fn y_stride(&self) -> usize {
+        self.resolution.width() as usize
+    }
+
+    /// The number of bytes between the starts of two rows of the U and V planes.
+    #[must_use]
+    pub fn uv_stride(&self) -> usize {
+        (self.resolution.width() as usize + 1) / 2
+    }
+
+    #[must_use]
+    pub fn y_plane(&self) -> &[u8] {
+        &self.data[..self.y_len()]
+    }
+
+    #[must_use]
+    pub fn u_plane(&self) -> &[u8] {
+        &self.data[self.y_len()..self.y_len() + self.uv_len()]
+    }
+
+    #[must_use]
+    pub fn v_plane(&self) -> &[u8] {
+        &self.data[self.y_len() + self.uv_len()..]
+    }
+
+    /// Gets all three planes, in Y, U, V order, which is also how they are laid out in [`data()`](YuvPlanarFrame::data).
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[must_use]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    fn y_len(&self) -> usize {
+        self.y_stride() * self.resolution.height() as usize
+    }
+
+    fn uv_len(&self) -> usize {
+        self.uv_stride() * ((self.resolution.height() as usize + 1) / 2)
+    }
+}
+
+/// Converts YUYV 4:2:2 to planar I420. The luma is copied as is, and the chroma of each two rows is averaged.
+/// # Errors
+/// If `data` is not the size of a YUYV frame at `resolution` (which has to be an even width), this will error.
+#[allow(clippy::cast_possible_truncation)]
+pub fn yuyv422_to_i420(data: &[u8], resolution: Resolution) -> Result<YuvPlanarFrame, NokhwaError> {
+    let width = resolution.width() as usize;
+    let height = resolution.height() as usize;
+    if width % 2 != 0 || data.len() != width * height * 2 {
+        return Err(NokhwaError::ProcessFrameError {
+            src: FrameFormat::Yuv422.into(),
+            destination: "I420".to_string(),
+            error: format!(
+                "Expected {} bytes of YUYV at an even width, got {} at {resolution}",
+                width * height * 2,
+                data.len()
+            ),
+        });
+    }
+
+    let uv_width = width / 2;
+    let uv_height = (height + 1) / 2;
+    let mut y_plane = Vec::with_capacity(width * height);
+    let mut u_plane = Vec::with_capacity(uv_width * uv_height);
+    let mut v_plane = Vec::with_capacity(uv_width * uv_height);
+    let row_len = width * 2;
+    for (row_idx, row) in data.chunks_exact(row_len).enumerate() {
+        for chunk in row.chunks_exact(4) {
+            y_plane.extend_from_slice(&[chunk[0], chunk[2]]);
+        }
+        if row_idx % 2 != 0 {
+            continue;
+        }
+        // averaged with the next row, or used alone if this is the odd last one
+        let next_row = data
+            .get((row_idx + 1) * row_len..(row_idx + 2) * row_len)
+            .unwrap_or(row);
+        for (chunk, next_chunk) in row.chunks_exact(4).zip(next_row.chunks_exact(4)) {
+            u_plane.push(((u16::from(chunk[1]) + u16::from(next_chunk[1]) + 1) / 2) as u8);
+            v_plane.push(((u16::from(chunk[3]) + u16::from(next_chunk[3]) + 1) / 2) as u8);
+        }
+    }
+
+    y_plane.append(&mut u_plane);
+    y_plane.append(&mut v_plane);
+    YuvPlanarFrame::new(FrameFormat::Yuv422, resolution, y_plane)
+}
+
+/// Converts RGB888 to planar I420 with the coefficients and range of `conversion`. The chroma of each 2x2 block of pixels
+/// is averaged.
+/// # Errors
+/// If `data` is not the size of an RGB888 frame at `resolution`, this will error.
+#[allow(clippy::cast_possible_truncation)]
+pub fn rgb_to_i420(
+    data: &[u8],
+    resolution: Resolution,
+    conversion: YuvConversion,
+) -> Result<YuvPlanarFrame, NokhwaError> {
+    let width = resolution.width() as usize;
+    let height = resolution.height() as usize;
+    if data.len() != width * height * 3 {
+        return Err(NokhwaError::ProcessFrameError {
+            src: FrameFormat::Rgb8.into(),
+            destination: "I420".to_string(),
+            error: format!(
+                "Expected {} bytes of RGB888, got {} at {resolution}",
+                width * height * 3,
+                data.len()
+            ),
+        });
+    }
+
+    let uv_width = (width + 1) / 2;
+    let uv_height = (height + 1) / 2;
+    let mut y_plane = Vec::with_capacity(width * height);
+    // sums and pixel counts of each 2x2 block
+    let mut uv_sums = vec![(0_u32, 0_u32, 0_u32); uv_width * uv_height];
+    for (idx, pixel) in data.chunks_exact(3).enumerate() {
+        let [y, u, v] = conversion.from_rgb(pixel[0], pixel[1], pixel[2]);
+        y_plane.push(y);
+        let block = &mut uv_sums[(idx / width / 2) * uv_width + (idx % width) / 2];
+        block.0 += u32::from(u);
+        block.1 += u32::from(v);
+        block.2 += 1;
+    }
+
+    let u_plane = uv_sums
+        .iter()
+        .map(|(u, _, count)| ((u + count / 2) / count) as u8);
+    let v_plane = uv_sums
+        .iter()
+        .map(|(_, v, count)| ((v + count / 2) / count) as u8);
+    let mut planes = y_plane;
+    planes.extend(u_plane);
+    planes.extend(v_plane);
+    YuvPlanarFrame::new(FrameFormat::Rgb8, resolution, planes)
+}
+
+/// Converts YUYV 4:2:2 to RGB888 (or RGBA8888 if `rgba`) like [`yuyv422_to_rgb`], with the coefficients and range of
+/// `conversion` instead of the fixed ones.
+/// # Errors
+/// If the stream is invalid Yuv422, this will error.
+pub fn yuyv422_to_rgb_with(
+    data: &[u8],
+    rgba: bool,
+    conversion: YuvConversion,
+) -> Result<Vec<u8>, NokhwaError> {
+    let mut rgb = vec![0; yuyv422_predicted_size(data.len(), rgba)];
+    buf_yuyv422_to_rgb_with(data, &mut rgb, rgba, conversion)?;
+    Ok(rgb)
+}
+
+/// Same as [`yuyv422_to_rgb_with`] but with a destination buffer instead of a return `Vec<u8>`
+/// # Errors
+/// If the stream is invalid Yuv422, or the destination buffer is not exactly large enough, this will error.
+pub fn buf_yuyv422_to_rgb_with(
+    data: &[u8],
+    dest: &mut [u8],
+    rgba: bool,
+    conversion: YuvConversion,
+) -> Result<(), NokhwaError> {
+    check_yuyv422_buffers(data, dest, rgba)?;
+    let pixel_size = if rgba { 4 } else { 3 };
+    for (chunk, pixels) in data
+        .chunks_exact(4)
+        .zip(dest.chunks_exact_mut(2 * pixel_size))
+    {
+        for (y, pixel) in [chunk[0], chunk[2]]
+            .into_iter()
+            .zip(pixels.chunks_exact_mut(pixel_size))
+        {
+            pixel[..3].copy_from_slice(&conversion.to_rgb(y, chunk[1], chunk[3]));
+            if rgba {
+                pixel[3] = 255;
+            }
         }
     }
-    dest.copy_from_slice(&buf);
     Ok(())
 }
 
@@ -1734,3 +2783,414 @@ pub fn buf_nv12_to_rgb(
 
     Ok(())
 }
+
+/// Demosaics a raw 8 bit Bayer frame (e.g. from [`FrameFormat::Bayer8`]) to RGB888 with bilinear interpolation. [For further reading](https://en.wikipedia.org/wiki/Demosaicing)
+///
+/// Every missing colour of a pixel is the average of its neighbours of that colour. At the frame edges, the samples are mirrored.
+/// # Errors
+/// This may error when the data stream size is not `width * height`, or the frame is smaller than one 2x2 Bayer block.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_wrap)]
+#[allow(clippy::cast_sign_loss)]
+pub fn debayer(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    pattern: BayerPattern,
+) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+    let error = |error: &str| NokhwaError::ProcessFrameError {
+        src: FrameFormat::Bayer8(pattern),
+        destination: "RGB".to_string(),
+        error: error.to_string(),
+    };
+
+    if width < 2 || height < 2 {
+        return Err(error("bad resolution"));
+    }
+
+    let (width_usize, height_usize) = (width as usize, height as usize);
+    if data.len() != width_usize * height_usize {
+        return Err(error("bad input buffer size"));
+    }
+
+    // the 2x2 block, indexed [row][column], 0 => red, 1 => green, 2 => blue
+    let block = match pattern {
+        BayerPattern::Rggb => [[0, 1], [1, 2]],
+        BayerPattern::Bggr => [[2, 1], [1, 0]],
+        BayerPattern::Grbg => [[1, 0], [2, 1]],
+        BayerPattern::Gbrg => [[1, 2], [0, 1]],
+    };
+    // mirroring keeps the Bayer parity, so -1 becomes 1 and `len` becomes `len - 2`
+    let mirror = |idx: isize, len: usize| -> usize {
+        if idx < 0 {
+            idx.unsigned_abs()
+        } else if idx as usize >= len {
+            2 * (len - 1) - idx as usize
+        } else {
+            idx as usize
+        }
+    };
+
+    let mut out = vec![0_u8; width_usize * height_usize * 3];
+    for y in 0..height_usize {
+        for x in 0..width_usize {
+            let mut sums = [0_u32; 3];
+            let mut counts = [0_u32; 3];
+            for dy in -1..=1_isize {
+                for dx in -1..=1_isize {
+                    let sy = mirror(y as isize + dy, height_usize);
+                    let sx = mirror(x as isize + dx, width_usize);
+                    let colour = block[sy % 2][sx % 2];
+                    sums[colour] += u32::from(data[sy * width_usize + sx]);
+                    counts[colour] += 1;
+                }
+            }
+
+            let own = block[y % 2][x % 2];
+            let base_index = (y * width_usize + x) * 3;
+            for colour in 0..3 {
+                out[base_index + colour] = if colour == own {
+                    data[y * width_usize + x]
+                } else {
+                    ((sums[colour] + counts[colour] / 2) / counts[colour].max(1)) as u8
+                };
+            }
+        }
+    }
+
+    ImageBuffer::from_vec(width, height, out).ok_or_else(|| error("bad output buffer size"))
+}
+
+#[cfg(test)]
+#[allow(clippy::cast_possible_truncation)]
+mod tests {
+    use super::*;
+
+    const PATTERNS: [BayerPattern; 4] = [
+        BayerPattern::Rggb,
+        BayerPattern::Bggr,
+        BayerPattern::Grbg,
+        BayerPattern::Gbrg,
+    ];
+
+    // 0 => red, 1 => green, 2 => blue, spelled out per pattern rather than shared with `debayer`
+    fn bayer_colour(pattern: BayerPattern, x: usize, y: usize) -> usize {
+        let site = (y % 2, x % 2);
+        match (pattern, site) {
+            (BayerPattern::Rggb, (0, 0))
+            | (BayerPattern::Bggr, (1, 1))
+            | (BayerPattern::Grbg, (0, 1))
+            | (BayerPattern::Gbrg, (1, 0)) => 0,
+            (BayerPattern::Rggb, (1, 1))
+            | (BayerPattern::Bggr, (0, 0))
+            | (BayerPattern::Grbg, (1, 0))
+            | (BayerPattern::Gbrg, (0, 1)) => 2,
+            _ => 1,
+        }
+    }
+
+    fn bayer_frame(pattern: BayerPattern, width: usize, height: usize, rgb: [u8; 3]) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| rgb[bayer_colour(pattern, x, y)]))
+            .collect()
+    }
+
+    #[test]
+    fn debayer_flat_colour_every_pattern() {
+        for pattern in PATTERNS {
+            for (width, height) in [(2, 2), (4, 4), (5, 3), (3, 5), (7, 2)] {
+                let data = bayer_frame(pattern, width, height, [200, 100, 50]);
+                let image = debayer(&data, width as u32, height as u32, pattern).unwrap();
+
+                assert_eq!(image.dimensions(), (width as u32, height as u32));
+                for (x, y, pixel) in image.enumerate_pixels() {
+                    assert_eq!(
+                        pixel.0,
+                        [200, 100, 50],
+                        "{pattern} {width}x{height} at ({x}, {y})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn debayer_keeps_own_sample() {
+        for pattern in PATTERNS {
+            let (width, height) = (5, 5);
+            let data = (0..width * height)
+                .map(|i| (i * 9) as u8)
+                .collect::<Vec<_>>();
+            let image = debayer(&data, width as u32, height as u32, pattern).unwrap();
+
+            for (x, y, pixel) in image.enumerate_pixels() {
+                let (x, y) = (x as usize, y as usize);
+                let own = bayer_colour(pattern, x, y);
+                assert_eq!(pixel.0[own], data[y * width + x], "{pattern} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn debayer_mirrors_edges() {
+        // R G
+        // G B
+        let data = [10, 20, 30, 40];
+        let image = debayer(&data, 2, 2, BayerPattern::Rggb).unwrap();
+
+        assert_eq!(image.get_pixel(0, 0).0, [10, 25, 40]);
+        assert_eq!(image.get_pixel(1, 0).0, [10, 20, 40]);
+        assert_eq!(image.get_pixel(0, 1).0, [10, 30, 40]);
+        assert_eq!(image.get_pixel(1, 1).0, [10, 25, 40]);
+    }
+
+    #[test]
+    fn debayer_interpolates_interior() {
+        let data = [
+            0, 10, 0, //
+            20, 99, 30, //
+            0, 40, 0,
+        ];
+        let image = debayer(&data, 3, 3, BayerPattern::Bggr).unwrap();
+        // BGGR puts red at (1, 1), green at the edges' centres and blue in the corners
+        assert_eq!(image.get_pixel(1, 1).0, [99, 25, 0]);
+    }
+
+    #[test]
+    fn debayer_rejects_bad_input() {
+        assert!(debayer(&[0; 4], 1, 4, BayerPattern::Rggb).is_err());
+        assert!(debayer(&[0; 4], 4, 1, BayerPattern::Rggb).is_err());
+        assert!(debayer(&[0; 5], 2, 2, BayerPattern::Rggb).is_err());
+        assert!(debayer(&[0; 15], 5, 3, BayerPattern::Gbrg).is_ok());
+    }
+
+    const CONVERSIONS: [YuvConversion; 4] = [
+        YuvConversion {
+            color_space: YuvColorSpace::Bt601,
+            range: YuvRange::Limited,
+        },
+        YuvConversion {
+            color_space: YuvColorSpace::Bt601,
+            range: YuvRange::Full,
+        },
+        YuvConversion {
+            color_space: YuvColorSpace::Bt709,
+            range: YuvRange::Limited,
+        },
+        YuvConversion {
+            color_space: YuvColorSpace::Bt709,
+            range: YuvRange::Full,
+        },
+    ];
+
+    fn assert_close(actual: [u8; 3], expected: [u8; 3], tolerance: u8, context: &str) {
+        for (a, e) in actual.into_iter().zip(expected) {
+            assert!(
+                a.abs_diff(e) <= tolerance,
+                "{context}: got {actual:?}, expected {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn yuv_conversion_black_and_white() {
+        for conversion in CONVERSIONS {
+            let (black, white) = match conversion.range {
+                YuvRange::Limited => (16, 235),
+                YuvRange::Full => (0, 255),
+            };
+            assert_eq!(
+                conversion.to_rgb(black, 128, 128),
+                [0, 0, 0],
+                "{conversion:?}"
+            );
+            assert_eq!(
+                conversion.to_rgb(white, 128, 128),
+                [255, 255, 255],
+                "{conversion:?}"
+            );
+            assert_eq!(
+                conversion.from_rgb(0, 0, 0),
+                [black, 128, 128],
+                "{conversion:?}"
+            );
+            assert_eq!(
+                conversion.from_rgb(255, 255, 255),
+                [white, 128, 128],
+                "{conversion:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn yuv_conversion_known_triples() {
+        // (conversion, RGB, YUV) for pure red, green and blue, from the BT.601 and BT.709 matrices
+        let triples = [
+            (CONVERSIONS[0], [255, 0, 0], [81, 90, 240]),
+            (CONVERSIONS[0], [0, 255, 0], [145, 54, 34]),
+            (CONVERSIONS[0], [0, 0, 255], [41, 240, 110]),
+            (CONVERSIONS[1], [255, 0, 0], [76, 85, 255]),
+            (CONVERSIONS[1], [0, 255, 0], [150, 44, 21]),
+            (CONVERSIONS[1], [0, 0, 255], [29, 255, 107]),
+            (CONVERSIONS[2], [255, 0, 0], [63, 102, 240]),
+            (CONVERSIONS[2], [0, 255, 0], [173, 42, 26]),
+            (CONVERSIONS[2], [0, 0, 255], [32, 240, 118]),
+            (CONVERSIONS[3], [255, 0, 0], [54, 99, 255]),
+            (CONVERSIONS[3], [0, 255, 0], [182, 30, 12]),
+            (CONVERSIONS[3], [0, 0, 255], [18, 255, 116]),
+        ];
+
+        for (conversion, [r, g, b], [y, u, v]) in triples {
+            let context = format!("{conversion:?} {:?}", [r, g, b]);
+            assert_close(conversion.from_rgb(r, g, b), [y, u, v], 1, &context);
+            // full range clamps the chroma of the primaries at 255, so allow for that on the way back
+            assert_close(conversion.to_rgb(y, u, v), [r, g, b], 3, &context);
+        }
+    }
+
+    #[test]
+    fn yuv_conversion_round_trip() {
+        for conversion in CONVERSIONS {
+            for rgb in [
+                [128, 128, 128],
+                [200, 120, 40],
+                [30, 160, 220],
+                [90, 60, 120],
+            ] {
+                let [y, u, v] = conversion.from_rgb(rgb[0], rgb[1], rgb[2]);
+                let context = format!("{conversion:?} {rgb:?}");
+                assert_close(conversion.to_rgb(y, u, v), rgb, 2, &context);
+            }
+        }
+    }
+
+    #[test]
+    fn yuyv422_to_rgb_with_shares_chroma() {
+        // two pixels sharing U and V, black then white in limited range
+        let data = [16, 128, 235, 128];
+        let limited = YuvConversion::new(YuvColorSpace::Bt709, YuvRange::Limited);
+
+        let rgb = yuyv422_to_rgb_with(&data, false, limited).unwrap();
+        assert_eq!(rgb, [0, 0, 0, 255, 255, 255]);
+
+        let mut with_alpha = [0; 8];
+        buf_yuyv422_to_rgb_with(&data, &mut with_alpha, true, limited).unwrap();
+        assert_eq!(with_alpha, [0, 0, 0, 255, 255, 255, 255, 255]);
+
+        // the same bytes in full range are a dark grey and a near white
+        let full = YuvConversion::new(YuvColorSpace::Bt709, YuvRange::Full);
+        let rgb = yuyv422_to_rgb_with(&data, false, full).unwrap();
+        assert_eq!(rgb, [16, 16, 16, 235, 235, 235]);
+    }
+
+    #[test]
+    fn yuyv422_to_rgb_with_checks_buffers() {
+        let conversion = YuvConversion::default();
+        assert!(yuyv422_to_rgb_with(&[16, 128, 16], false, conversion).is_err());
+        assert!(
+            buf_yuyv422_to_rgb_with(&[16, 128, 16, 128], &mut [0; 5], false, conversion).is_err()
+        );
+        assert!(
+            buf_yuyv422_to_rgb_with(&[16, 128, 16, 128], &mut [0; 6], true, conversion).is_err()
+        );
+    }
+
+    #[test]
+    fn yuyv422_to_i420_planes() {
+        // 4x2, the chroma of the two rows is averaged (rounding up)
+        let data = [
+            10, 100, 11, 200, 12, 50, 13, 60, //
+            20, 101, 21, 203, 22, 51, 23, 63,
+        ];
+        let frame = yuyv422_to_i420(&data, Resolution::new(4, 2)).unwrap();
+
+        assert_eq!((frame.y_stride(), frame.uv_stride()), (4, 2));
+        assert_eq!(frame.y_plane(), [10, 11, 12, 13, 20, 21, 22, 23]);
+        assert_eq!(frame.u_plane(), [101, 51]);
+        assert_eq!(frame.v_plane(), [202, 62]);
+        assert_eq!(frame.data().len(), 8 + 2 + 2);
+    }
+
+    #[test]
+    fn yuyv422_to_i420_odd_height() {
+        // the last row has no row below it, so its chroma is used as is
+        let data = [
+            1, 10, 2, 20, //
+            3, 12, 4, 22, //
+            5, 90, 6, 80,
+        ];
+        let frame = yuyv422_to_i420(&data, Resolution::new(2, 3)).unwrap();
+
+        assert_eq!(frame.y_plane(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(frame.u_plane(), [11, 90]);
+        assert_eq!(frame.v_plane(), [21, 80]);
+    }
+
+    #[test]
+    fn yuyv422_to_i420_rejects_bad_input() {
+        // YUYV has no odd widths, every chunk is two pixels
+        assert!(matches!(
+            yuyv422_to_i420(&[0; 12], Resolution::new(3, 2)),
+            Err(NokhwaError::ProcessFrameError {
+                src: FrameFormat::Yuv422,
+                ..
+            })
+        ));
+        assert!(yuyv422_to_i420(&[0; 14], Resolution::new(4, 2)).is_err());
+    }
+
+    #[test]
+    fn rgb_to_i420_odd_size() {
+        // 3x3 has 2x2 chroma blocks, the right and bottom ones only partly covered
+        let conversion = YuvConversion::new(YuvColorSpace::Bt601, YuvRange::Full);
+        let data = [128; 27];
+        let frame = rgb_to_i420(&data, Resolution::new(3, 3), conversion).unwrap();
+
+        assert_eq!((frame.y_stride(), frame.uv_stride()), (3, 2));
+        assert_eq!(frame.y_plane(), [128; 9]);
+        assert_eq!(frame.u_plane(), [128; 4]);
+        assert_eq!(frame.v_plane(), [128; 4]);
+        assert_eq!(frame.data().len(), 9 + 4 + 4);
+    }
+
+    #[test]
+    fn rgb_to_i420_averages_blocks() {
+        // 3x1: a red and a blue pixel share a block, the green one is alone in the next
+        let conversion = YuvConversion::new(YuvColorSpace::Bt601, YuvRange::Full);
+        let data = [255, 0, 0, 0, 0, 255, 0, 255, 0];
+        let frame = rgb_to_i420(&data, Resolution::new(3, 1), conversion).unwrap();
+
+        let red = conversion.from_rgb(255, 0, 0);
+        let blue = conversion.from_rgb(0, 0, 255);
+        let green = conversion.from_rgb(0, 255, 0);
+        let average = |a: u8, b: u8| ((u16::from(a) + u16::from(b) + 1) / 2) as u8;
+        assert_eq!(frame.y_plane(), [red[0], blue[0], green[0]]);
+        assert_eq!(frame.u_plane(), [average(red[1], blue[1]), green[1]]);
+        assert_eq!(frame.v_plane(), [average(red[2], blue[2]), green[2]]);
+    }
+
+    #[test]
+    fn rgb_to_i420_rejects_bad_input() {
+        assert!(matches!(
+            rgb_to_i420(&[0; 8], Resolution::new(3, 1), YuvConversion::default()),
+            Err(NokhwaError::ProcessFrameError {
+                src: FrameFormat::Rgb8,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn yuv_planar_frame_checks_size() {
+        let resolution = Resolution::new(5, 3);
+        // 15 bytes of Y, and 3x2 of each U and V
+        assert!(YuvPlanarFrame::new(FrameFormat::Nv12, resolution, vec![0; 27]).is_ok());
+        assert!(matches!(
+            YuvPlanarFrame::new(FrameFormat::Nv12, resolution, vec![0; 26]),
+            Err(NokhwaError::ProcessFrameError {
+                src: FrameFormat::Nv12,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "serialize")]
+
+use nokhwa_core::types::{CameraControlProfile, ControlValueSetter, KnownCameraControl};
+
+// The stored form of a profile is public API, so this fixture must keep loading. Do not edit it to make a change pass.
+const FIXTURE: &str = include_str!("fixtures/control_profile.json");
+
+fn fixture_profile() -> CameraControlProfile {
+    CameraControlProfile::new(vec![
+        (
+            KnownCameraControl::Brightness,
+            ControlValueSetter::Integer(128),
+        ),
+        (
+            KnownCameraControl::ExposureMode,
+            ControlValueSetter::EnumValue(1),
+        ),
+        (
+            KnownCameraControl::WhiteBalanceAuto,
+            ControlValueSetter::Boolean(false),
+        ),
+        (KnownCameraControl::Zoom, ControlValueSetter::Float(1.5)),
+        (
+            KnownCameraControl::Gain,
+            ControlValueSetter::RGB(1.0, 1.25, 2.0),
+        ),
+        (KnownCameraControl::Focus, ControlValueSetter::None),
+        (
+            KnownCameraControl::Other(0),
+            ControlValueSetter::Point(0.5, 0.25),
+        ),
+        (
+            KnownCameraControl::Other(1),
+            ControlValueSetter::String("daylight".to_string()),
+        ),
+        (
+            KnownCameraControl::Other(2),
+            ControlValueSetter::Bytes(vec![1, 2, 255]),
+        ),
+        (
+            KnownCameraControl::Other(3),
+            ControlValueSetter::KeyValue(-1, 2),
+        ),
+        (
+            KnownCameraControl::Other(4),
+            ControlValueSetter::StringList("auto".to_string()),
+        ),
+    ])
+}
+
+#[test]
+fn control_profile_loads_fixture() {
+    let profile: CameraControlProfile = serde_json::from_str(FIXTURE).unwrap();
+    assert_eq!(profile, fixture_profile());
+}
+
+#[test]
+fn control_profile_serializes_as_fixture() {
+    let serialized = serde_json::to_value(fixture_profile()).unwrap();
+    let fixture: serde_json::Value = serde_json::from_str(FIXTURE).unwrap();
+    assert_eq!(serialized, fixture);
+}
+
+#[test]
+fn control_profile_round_trip() {
+    let profile = fixture_profile();
+    let json = serde_json::to_string(&profile).unwrap();
+    let loaded: CameraControlProfile = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, profile);
+}
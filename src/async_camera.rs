@@ -0,0 +1,593 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{thread_utils::join_with_timeout, Camera, ThreadOptions};
+use flume::{r#async::RecvStream, Receiver, SendTimeoutError, Sender, TrySendError};
+use futures_core::Stream;
+#[cfg(feature = "output-tokio")]
+use image::ImageBuffer;
+#[cfg(feature = "output-tokio")]
+use nokhwa_core::pixel_format::FormatDecoder;
+use nokhwa_core::{
+    buffer::Buffer,
+    error::NokhwaError,
+    traits::CaptureTrait,
+    types::{
+        CameraFormat, CameraIndex, CameraInfo, ControlValueSetter, KnownCameraControl,
+        RequestedFormat,
+    },
+};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+type ReplySender<T> = Sender<Result<T, NokhwaError>>;
+
+enum AsyncCameraRequest {
+    OpenStream(ReplySender<()>),
+    Frame(ReplySender<Buffer>),
+    StopStream(ReplySender<()>),
+    CameraRequest(RequestedFormat, ReplySender<CameraFormat>),
+    CameraControl(KnownCameraControl, ControlValueSetter, ReplySender<()>),
+    Shutdown,
+}
+
+/// A camera for async code. It runs a [`Camera`] on a dedicated blocking thread, so waiting for a frame
+/// never blocks the async runtime (e.g. a `tokio` worker).
+///
+/// Every method sends a request to the thread and awaits the reply over a channel. The futures are cancel-safe:
+/// if one is dropped, the request still finishes on the thread and its result is discarded, so the camera is never left
+/// half-way through an operation. A cancelled [`frame()`](AsyncCamera::frame) keeps its frame for the next call instead,
+/// so it can be raced against e.g. a shutdown signal without losing frames.
+///
+/// Dropping the `AsyncCamera` stops the stream and waits for the thread to finish. With the `output-tokio` feature,
+/// [`shutdown()`](AsyncCamera::shutdown) does the same without blocking the runtime.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub struct AsyncCamera {
+    current_camera: CameraInfo,
+    requests: Sender<AsyncCameraRequest>,
+    // the reply of a frame request whose future was dropped before it arrived
+    pending_frame: Mutex<Option<Receiver<Result<Buffer, NokhwaError>>>>,
+    thread_priority_warning: Option<NokhwaError>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl AsyncCamera {
+    /// Create a new `AsyncCamera` from a [`CameraIndex`] and [`format`]
+    ///
+    /// # Errors
+    /// This will error if you either have a bad platform configuration (e.g. `input-v4l` but not on linux) or the backend cannot create the camera (e.g. permission denied).
+    pub fn new(index: CameraIndex, format: RequestedFormat) -> Result<Self, NokhwaError> {
+        Ok(Self::with_custom(Camera::new(index, format)?))
+    }
+
+    /// Create a new `AsyncCamera` like [`new()`](AsyncCamera::new), but the [`Camera`] is created on the camera thread
+    /// instead of the calling one, so opening a slow device does not block the async runtime. The camera never leaves that thread.
+    ///
+    /// # Errors
+    /// This will error if you either have a bad platform configuration (e.g. `input-v4l` but not on linux) or the backend cannot create the camera (e.g. permission denied).
+    pub async fn new_async(
+        index: CameraIndex,
+        format: RequestedFormat,
+    ) -> Result<Self, NokhwaError> {
+        let (ready, ready_receiver) = flume::bounded(1);
+        let (requests, handle, thread_priority_warning) = Self::spawn(
+            move || Camera::new(index, format),
+            ready,
+            &ThreadOptions::default(),
+        )?;
+        let current_camera = ready_receiver
+            .recv_async()
+            .await
+            .map_err(|why| NokhwaError::GeneralError(format!("Camera thread stopped: {why}")))?;
+
+        match current_camera {
+            Ok(current_camera) => Ok(AsyncCamera {
+                current_camera,
+                requests,
+                pending_frame: Mutex::new(None),
+                thread_priority_warning,
+                handle: Some(handle),
+            }),
+            // the thread has already returned
+            Err(why) => {
+                let _ = handle.join();
+                Err(why)
+            }
+        }
+    }
+
+    /// Allows creation of an `AsyncCamera` from an existing [`Camera`], e.g. one with a custom backend.
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Panics
+    /// If the camera thread could not be spawned, like [`std::thread::spawn()`].
+    #[must_use]
+    pub fn with_custom(camera: Camera) -> Self {
+        match Self::with_thread_options(camera, ThreadOptions::default()) {
+            Ok(camera) => camera,
+            Err(why) => panic!("{why}"),
+        }
+    }
+
+    /// Allows creation of an `AsyncCamera` from an existing [`Camera`] like [`with_custom()`](AsyncCamera::with_custom),
+    /// with the name and priority of the camera thread set by `options`.
+    ///
+    /// If the priority could not be set, the camera still starts, see [`thread_priority_warning()`](AsyncCamera::thread_priority_warning).
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the camera thread could not be spawned, this will error.
+    pub fn with_thread_options(
+        camera: Camera,
+        options: ThreadOptions,
+    ) -> Result<Self, NokhwaError> {
+        let current_camera = camera.info().clone();
+        let (ready, _) = flume::bounded(1);
+        let (requests, handle, thread_priority_warning) =
+            Self::spawn(move || Ok(camera), ready, &options)?;
+
+        Ok(AsyncCamera {
+            current_camera,
+            requests,
+            pending_frame: Mutex::new(None),
+            thread_priority_warning,
+            handle: Some(handle),
+        })
+    }
+
+    /// Gets why the priority from [`with_thread_options()`](AsyncCamera::with_thread_options) could not be set on the
+    /// camera thread, if it could not. The thread runs at its default priority then.
+    #[must_use]
+    pub fn thread_priority_warning(&self) -> Option<&NokhwaError> {
+        self.thread_priority_warning.as_ref()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn spawn(
+        camera: impl FnOnce() -> Result<Camera, NokhwaError> + Send + 'static,
+        ready: ReplySender<CameraInfo>,
+        options: &ThreadOptions,
+    ) -> Result<
+        (
+            Sender<AsyncCameraRequest>,
+            JoinHandle<()>,
+            Option<NokhwaError>,
+        ),
+        NokhwaError,
+    > {
+        let (requests, request_receiver) = flume::unbounded();
+        let (handle, thread_priority_warning) = options.spawn(move || {
+            let mut camera = match camera() {
+                Ok(camera) => {
+                    let _ = ready.send(Ok(camera.info().clone()));
+                    camera
+                }
+                Err(why) => {
+                    let _ = ready.send(Err(why));
+                    return;
+                }
+            };
+            // the reply receivers may have been dropped by a cancelled future, which is fine.
+            for request in request_receiver.iter() {
+                match request {
+                    AsyncCameraRequest::OpenStream(reply) => {
+                        let _ = reply.send(camera.open_stream());
+                    }
+                    AsyncCameraRequest::Frame(reply) => {
+                        let _ = reply.send(camera.frame());
+                    }
+                    AsyncCameraRequest::StopStream(reply) => {
+                        let _ = reply.send(camera.stop_stream());
+                    }
+                    AsyncCameraRequest::CameraRequest(request, reply) => {
+                        let _ = reply.send(camera.set_camera_request(request));
+                    }
+                    AsyncCameraRequest::CameraControl(id, control, reply) => {
+                        let _ = reply.send(camera.set_camera_control(id, control));
+                    }
+                    AsyncCameraRequest::Shutdown => break,
+                }
+            }
+            if camera.is_stream_open() {
+                let _stop_stream_err = camera.stop_stream();
+            }
+        })?;
+
+        Ok((requests, handle, thread_priority_warning))
+    }
+
+    /// Gets the camera information such as Name and Index as a [`CameraInfo`].
+    pub fn info(&self) -> &CameraInfo {
+        &self.current_camera
+    }
+
+    /// Will open the camera stream with set parameters.
+    /// # Errors
+    /// If the specific backend fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error.
+    pub async fn open_stream(&self) -> Result<(), NokhwaError> {
+        self.request(AsyncCameraRequest::OpenStream).await
+    }
+
+    /// Waits for the next frame from the camera without blocking the async runtime.
+    ///
+    /// If a previous call was cancelled, this returns the frame that call was waiting for, which may be older than the
+    /// camera's latest one.
+    /// # Errors
+    /// If the stream is not open or the backend fails to capture a frame, this will error.
+    pub async fn frame(&self) -> Result<Buffer, NokhwaError> {
+        loop {
+            let reply_receiver = self.pending_frame()?;
+            let reply = reply_receiver.recv_async().await;
+            // only forget the request once its reply is taken, so a cancelled call leaves it for the next one
+            if let Ok(mut pending) = self.pending_frame.lock() {
+                if pending
+                    .as_ref()
+                    .map_or(false, |pending| pending.same_channel(&reply_receiver))
+                {
+                    *pending = None;
+                }
+            }
+            match reply {
+                Ok(frame) => return frame,
+                // another call took the reply, or the thread stopped, which the next request finds out
+                Err(_) => continue,
+            }
+        }
+    }
+
+    /// Waits for the next frame like [`frame()`](AsyncCamera::frame), but for at most `timeout`. Timing out is a cancellation,
+    /// so the frame is not lost, the next call returns it.
+    /// # Errors
+    /// If no frame arrived within `timeout`, this will error with [`NokhwaError::TimeoutError`]. Otherwise, like [`frame()`](AsyncCamera::frame).
+    #[cfg(feature = "output-tokio")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+    pub async fn frame_timeout(&self, timeout: Duration) -> Result<Buffer, NokhwaError> {
+        tokio::time::timeout(timeout, self.frame())
+            .await
+            .map_err(|_| NokhwaError::TimeoutError(format!("No frame within {timeout:?}")))?
+    }
+
+    fn pending_frame(&self) -> Result<Receiver<Result<Buffer, NokhwaError>>, NokhwaError> {
+        let mut pending = self
+            .pending_frame
+            .lock()
+            .map_err(|why| NokhwaError::GeneralError(why.to_string()))?;
+        if let Some(reply_receiver) = pending.as_ref() {
+            return Ok(reply_receiver.clone());
+        }
+        let (reply, reply_receiver) = flume::bounded(1);
+        self.requests
+            .send(AsyncCameraRequest::Frame(reply))
+            .map_err(|why| NokhwaError::GeneralError(format!("Camera thread stopped: {why}")))?;
+        *pending = Some(reply_receiver.clone());
+        Ok(reply_receiver)
+    }
+
+    /// Will drop the stream.
+    /// # Errors
+    /// Please check the `Quirks` section of each backend.
+    pub async fn stop_stream(&self) -> Result<(), NokhwaError> {
+        self.request(AsyncCameraRequest::StopStream).await
+    }
+
+    /// Will set the current [`CameraFormat`], using a [`RequestedFormat.`]
+    ///
+    /// This will return the new [`CameraFormat`]
+    /// # Errors
+    /// If nothing fits the requested criteria, this will return an error.
+    pub async fn set_camera_request(
+        &self,
+        request: RequestedFormat,
+    ) -> Result<CameraFormat, NokhwaError> {
+        self.request(|reply| AsyncCameraRequest::CameraRequest(request, reply))
+            .await
+    }
+
+    /// Sets the control to `control` in the camera.
+    /// # Errors
+    /// If the `control` is not supported, the value is invalid (less than min, greater than max, not in step), or there was an error setting the control,
+    /// this will error.
+    pub async fn set_camera_control(
+        &self,
+        id: KnownCameraControl,
+        control: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        self.request(|reply| AsyncCameraRequest::CameraControl(id, control, reply))
+            .await
+    }
+
+    /// Waits for the next frame like [`frame()`](AsyncCamera::frame), then decodes it with `F` on `tokio`'s blocking
+    /// thread pool, see [`decode_frame()`].
+    /// # Errors
+    /// If the stream is not open, the backend fails to capture a frame, or the frame could not be decoded, this will error.
+    #[cfg(feature = "output-tokio")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+    pub async fn frame_decoded<F>(&self) -> Result<ImageBuffer<F::Output, Vec<u8>>, NokhwaError>
+    where
+        F: FormatDecoder + 'static,
+        F::Output: Send + 'static,
+    {
+        decode_frame::<F>(self.frame().await?).await
+    }
+
+    /// Stops the stream and waits for the camera thread to finish on `tokio`'s blocking thread pool, instead of
+    /// blocking the async runtime like dropping the `AsyncCamera` does.
+    /// # Errors
+    /// If the camera thread panicked, this will error.
+    #[cfg(feature = "output-tokio")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+    pub async fn shutdown(mut self) -> Result<(), NokhwaError> {
+        let _ = self.requests.send(AsyncCameraRequest::Shutdown);
+        let handle = match self.handle.take() {
+            Some(handle) => handle,
+            None => return Ok(()),
+        };
+        tokio::task::spawn_blocking(move || handle.join())
+            .await
+            .map_err(|why| NokhwaError::StreamShutdownError(why.to_string()))?
+            .map_err(|_| NokhwaError::StreamShutdownError("Camera thread panicked".to_string()))
+    }
+
+    async fn request<T>(
+        &self,
+        request: impl FnOnce(ReplySender<T>) -> AsyncCameraRequest,
+    ) -> Result<T, NokhwaError> {
+        let (reply, reply_receiver) = flume::bounded(1);
+        self.requests
+            .send(request(reply))
+            .map_err(|why| NokhwaError::GeneralError(format!("Camera thread stopped: {why}")))?;
+        reply_receiver
+            .recv_async()
+            .await
+            .map_err(|why| NokhwaError::GeneralError(format!("Camera thread stopped: {why}")))?
+    }
+}
+
+impl Drop for AsyncCamera {
+    fn drop(&mut self) {
+        let _ = self.requests.send(AsyncCameraRequest::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Decodes `frame` with `F` on `tokio`'s blocking thread pool, so decoding (e.g. MJPEG) does not stall the async runtime.
+/// # Errors
+/// If the frame could not be decoded, this will error.
+#[cfg(feature = "output-tokio")]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+pub async fn decode_frame<F>(frame: Buffer) -> Result<ImageBuffer<F::Output, Vec<u8>>, NokhwaError>
+where
+    F: FormatDecoder + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || frame.decode_image::<F>())
+        .await
+        .map_err(|why| NokhwaError::GeneralError(format!("Decoding task failed: {why}")))?
+}
+
+// How often a `FrameStream` waiting for room in its buffer checks whether it was stopped.
+const BUFFER_DIE_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+// How long dropping a `FrameStream` waits for its capture thread to stop.
+const DROP_JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What a [`FrameStream`] does with new frames while the consumer has not taken the previous ones yet.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FrameStreamBackpressure {
+    /// Only the latest frame is kept, older ones are dropped.
+    #[default]
+    SkipToLatest,
+    /// Up to this many frames are kept. When they are full, capturing waits for the consumer, and the frames the camera delivers meanwhile are skipped.
+    Buffer(usize),
+}
+
+/// A [`Stream`] of the frames of a [`Camera`], captured on a dedicated thread. It works with any async runtime, e.g. `tokio` or `async-std`.
+///
+/// Each frame is yielded once. Errors while capturing are yielded as items. The stream ends once
+/// [`stop_stream()`](FrameStream::stop_stream) is called, or the camera's stream closes (e.g. because the device was disconnected).
+///
+/// Dropping it waits up to 5 seconds for the capture thread to stop, so a camera that hangs in `frame()` cannot block the drop.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub struct FrameStream {
+    current_camera: CameraInfo,
+    frames: RecvStream<'static, Result<Buffer, NokhwaError>>,
+    die_bool: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FrameStream {
+    /// Opens the stream of `camera`, if it is not open yet, and starts capturing.
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the stream could not be opened, this will error.
+    pub fn new(
+        mut camera: Camera,
+        backpressure: FrameStreamBackpressure,
+    ) -> Result<Self, NokhwaError> {
+        let current_camera = camera.info().clone();
+        if !camera.is_stream_open() {
+            camera.open_stream()?;
+        }
+
+        let (sender, receiver) = match backpressure {
+            FrameStreamBackpressure::SkipToLatest => flume::bounded(1),
+            FrameStreamBackpressure::Buffer(frames) => flume::bounded(frames.max(1)),
+        };
+        let die_bool = Arc::new(AtomicBool::default());
+
+        let latest_receiver = receiver.clone();
+        let die_bool_clone = die_bool.clone();
+        let handle = std::thread::spawn(move || {
+            while !die_bool_clone.load(Ordering::SeqCst) {
+                let frame = camera.frame();
+                let stream_closed = frame.is_err() && !camera.is_stream_open();
+
+                // this thread holds a receiver, so the channel never disconnects. Dropping the `FrameStream` sets `die_bool`.
+                let mut frame = frame;
+                match backpressure {
+                    FrameStreamBackpressure::SkipToLatest => {
+                        while let Err(TrySendError::Full(item)) = sender.try_send(frame) {
+                            let _ = latest_receiver.try_recv();
+                            frame = item;
+                        }
+                    }
+                    FrameStreamBackpressure::Buffer(_) => {
+                        // wakes up now and then, so a stopped stream is noticed while the consumer takes no frames
+                        while let Err(SendTimeoutError::Timeout(item)) =
+                            sender.send_timeout(frame, BUFFER_DIE_CHECK_INTERVAL)
+                        {
+                            if die_bool_clone.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            frame = item;
+                        }
+                    }
+                }
+
+                if stream_closed {
+                    break;
+                }
+            }
+            if camera.is_stream_open() {
+                let _stop_stream_err = camera.stop_stream();
+            }
+        });
+
+        Ok(FrameStream {
+            current_camera,
+            frames: receiver.into_stream(),
+            die_bool,
+            handle: Some(handle),
+        })
+    }
+
+    /// Gets the camera information such as Name and Index as a [`CameraInfo`].
+    pub fn info(&self) -> &CameraInfo {
+        &self.current_camera
+    }
+
+    /// Stops capturing and the camera's stream. The stream ends once the frames that were already captured are taken.
+    pub fn stop_stream(&mut self) {
+        self.die_bool.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Stream for FrameStream {
+    type Item = Result<Buffer, NokhwaError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.frames).poll_next(cx)
+    }
+}
+
+impl Drop for FrameStream {
+    // The capture thread may be stuck in `frame()` on a camera that stopped delivering. It is left running then, and stops
+    // the camera's stream once the frame is in.
+    fn drop(&mut self) {
+        self.die_bool.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = join_with_timeout(handle, DROP_JOIN_TIMEOUT);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "output-tokio"))]
+mod tests {
+    use super::*;
+    use crate::mock::{mock_sequence, MockCamera};
+    use std::{sync::atomic::Ordering, time::Instant};
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn cancelled_frames_are_not_lost() {
+        let (camera, stats) = MockCamera::camera(Duration::from_micros(50));
+        let async_camera = AsyncCamera::with_custom(camera);
+        runtime().block_on(async {
+            async_camera.open_stream().await.unwrap();
+            let mut expected = 1;
+            let mut cancelled = 0;
+            for _ in 0..5000 {
+                match async_camera.frame_timeout(Duration::ZERO).await {
+                    Ok(frame) => {
+                        assert_eq!(mock_sequence(&frame), expected);
+                        expected += 1;
+                    }
+                    Err(NokhwaError::TimeoutError(_)) => cancelled += 1,
+                    Err(why) => panic!("{why}"),
+                }
+            }
+            assert!(cancelled > 0);
+
+            let frame = async_camera.frame().await.unwrap();
+            assert_eq!(mock_sequence(&frame), expected);
+            // every frame the camera read was returned, none went down with a cancelled future
+            assert_eq!(stats.frames.load(Ordering::SeqCst) as u64, expected);
+        });
+    }
+
+    #[test]
+    fn cancelled_frame_does_not_poison_the_camera() {
+        let (camera, stats) = MockCamera::camera(Duration::from_millis(20));
+        let async_camera = AsyncCamera::with_custom(camera);
+        runtime().block_on(async {
+            async_camera.open_stream().await.unwrap();
+            assert!(matches!(
+                async_camera.frame_timeout(Duration::from_millis(1)).await,
+                Err(NokhwaError::TimeoutError(_))
+            ));
+            async_camera.stop_stream().await.unwrap();
+            async_camera.open_stream().await.unwrap();
+            // the frame the cancelled call asked for was read before the stream stopped
+            assert_eq!(mock_sequence(&async_camera.frame().await.unwrap()), 1);
+            assert_eq!(mock_sequence(&async_camera.frame().await.unwrap()), 2);
+        });
+        drop(async_camera);
+        assert_eq!(stats.open_streams.load(Ordering::SeqCst), 0);
+        assert_eq!(stats.devices.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn full_frame_stream_buffer_waits_and_stops() {
+        let (camera, stats) = MockCamera::camera(Duration::from_millis(1));
+        let frame_stream = FrameStream::new(camera, FrameStreamBackpressure::Buffer(2)).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        // two frames are buffered, the capture thread waits with the third
+        assert_eq!(stats.frames.load(Ordering::SeqCst), 3);
+
+        let dropped_at = Instant::now();
+        drop(frame_stream);
+        assert!(dropped_at.elapsed() < BUFFER_DIE_CHECK_INTERVAL * 4);
+        assert_eq!(stats.open_streams.load(Ordering::SeqCst), 0);
+        assert_eq!(stats.devices.load(Ordering::SeqCst), 0);
+    }
+}
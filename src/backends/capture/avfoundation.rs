@@ -43,6 +43,7 @@ use std::{borrow::Cow, collections::HashMap};
 /// - This only works on 64 bit platforms.
 /// - FPS adjustment does not work.
 /// - If permission has not been granted and you call `init()` it will error.
+/// - The device is not `Send`, as it holds Objective-C objects. Create and use it on the same thread.
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-avfoundation")))]
 #[cfg(target_os = "macos")]
 pub struct AVFoundationCaptureDevice {
@@ -232,10 +233,7 @@ impl CaptureTrait for AVFoundationCaptureDevice {
         id: KnownCameraControl,
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError> {
-        self.device.lock()?;
-        let res = self.device.set_control(id, value);
-        self.device.unlock();
-        res
+        self.device.set_control(id, value)
     }
 
     fn open_stream(&mut self) -> Result<(), NokhwaError> {
@@ -164,23 +164,26 @@ fn set_autoplay_inline(element: &Element) -> Result<(), NokhwaError> {
     Ok(())
 }
 
-fn control_to_str(control: KnownCameraControl) -> &'static str {
-    match control {
+// The name of the `MediaTrackConstraints` member for `control`. Errors for controls that browsers do not have.
+fn control_to_str(control: KnownCameraControl) -> Result<&'static str, NokhwaError> {
+    let name = match control {
         KnownCameraControl::Brightness => "brightness",
         KnownCameraControl::Contrast => "contrast",
         KnownCameraControl::Hue => "colorTemprature",
         KnownCameraControl::Saturation => "saturation",
         KnownCameraControl::Sharpness => "sharpness",
         KnownCameraControl::Gamma => "exposureTime",
-        KnownCameraControl::WhiteBalance => "whiteBalanceMode",
+        KnownCameraControl::WhiteBalanceAuto | KnownCameraControl::WhiteBalance => {
+            "whiteBalanceMode"
+        }
         KnownCameraControl::BacklightComp => "exposureCompensation",
         KnownCameraControl::Gain => "iso",
         KnownCameraControl::Pan => "pan",
         KnownCameraControl::Tilt => "tilt",
         KnownCameraControl::Zoom => "zoom",
-        KnownCameraControl::Exposure => "exposureMode",
+        KnownCameraControl::ExposureMode | KnownCameraControl::Exposure => "exposureMode",
         KnownCameraControl::Iris => "focusDistance",
-        KnownCameraControl::Focus => "focusMode",
+        KnownCameraControl::FocusMode | KnownCameraControl::Focus => "focusMode",
         KnownCameraControl::Other(u) => match u {
             0 => "frameRate",
             1 => "width",
@@ -191,9 +194,19 @@ fn control_to_str(control: KnownCameraControl) -> &'static str {
             64 => "attachedCanvasMode",
             128 => "pointsOfInterest",
             8192 => "torch",
-            _ => "",
+            _ => return Err(NokhwaError::UnsupportedOperationError(ApiBackend::Browser)),
         },
-    }
+        KnownCameraControl::WhiteBalanceRed
+        | KnownCameraControl::WhiteBalanceBlue
+        | KnownCameraControl::PowerLineFrequency
+        | KnownCameraControl::PanRelative
+        | KnownCameraControl::TiltRelative
+        | KnownCameraControl::ZoomRelative
+        | KnownCameraControl::ExposurePriority => {
+            return Err(NokhwaError::UnsupportedOperationError(ApiBackend::Browser))
+        }
+    };
+    Ok(name)
 }
 
 #[derive(Copy, Clone, Hash, Ord, PartialOrd, Eq, PartialEq::Zoom)]
@@ -476,7 +489,7 @@ impl CaptureTrait for BrowserCamera {
             }
         }
 
-        let cam_str = control_to_str(contorl);
+        let cam_str = control_to_str(contorl)?;
         let capabilities_fn = match unsafe { Reflect::get(&self.track, "getCapabilities") } {
             Ok(v) => match v.dyn_ref::<Function>() {
                 Some(fx) => fx,
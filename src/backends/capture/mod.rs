@@ -142,7 +142,15 @@ pub use avfoundation::AVFoundationCaptureDevice;
 mod uvc_backend;
 #[cfg(feature = "input-uvc")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
-pub use uvc_backend::UVCCaptureDevice;
+pub use uvc_backend::{
+    BufferPoolStats, CameraHandle, CaptureFrame, DecodeLocation, FormatSelectionPolicy,
+    FrameIntervals, FrameRingBuffer, MultiCamera, PartialFramePolicy, PooledBuffer,
+    RingBufferStorage, TimingProfile, UVCCaptureDevice, UvcContext,
+    DEFAULT_YUYV_MAX_PIXELS, EXPOSURE_HISTORY_LEN, USB2_MAX_ISOCHRONOUS_BANDWIDTH,
+    WAIT_FOR_DEVICE_POLL_INTERVAL,
+};
+#[cfg(feature = "input-uvc")]
+pub(crate) use uvc_backend::uvc_is_camera;
 // #[cfg(feature = "input-gst")]
 // mod gst_backend;
 // #[cfg(feature = "input-gst")]
@@ -168,3 +176,19 @@ mod opencv_backend;
 #[cfg(feature = "input-opencv")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-opencv")))]
 pub use opencv_backend::OpenCvCaptureDevice;
+
+// Whether a backend can be moved to another thread, checked at compile time. `MediaFoundationCaptureDevice` holds COM
+// interfaces and `AVFoundationCaptureDevice` holds Objective-C objects, neither of which are `Send`, so they stay on the thread
+// that created them.
+#[allow(dead_code)]
+fn assert_send<T: Send>() {}
+
+#[allow(dead_code)]
+fn assert_backends_send() {
+    #[cfg(all(feature = "input-v4l", target_os = "linux"))]
+    assert_send::<V4LCaptureDevice<'static>>();
+    #[cfg(feature = "input-uvc")]
+    assert_send::<UVCCaptureDevice<'static>>();
+    #[cfg(feature = "input-opencv")]
+    assert_send::<OpenCvCaptureDevice>();
+}
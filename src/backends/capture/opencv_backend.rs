@@ -53,6 +53,7 @@ pub fn known_camera_control_to_video_capture_property(
         KnownCameraControl::Zoom => Ok(VideoCaptureProperties::CAP_PROP_ZOOM),
         KnownCameraControl::Exposure => Ok(VideoCaptureProperties::CAP_PROP_EXPOSURE),
         KnownCameraControl::Iris => Ok(VideoCaptureProperties::CAP_PROP_IRIS),
+        KnownCameraControl::FocusMode => Ok(VideoCaptureProperties::CAP_PROP_AUTOFOCUS),
         KnownCameraControl::Focus => Ok(VideoCaptureProperties::CAP_PROP_FOCUS),
         _ => Err(NokhwaError::UnsupportedOperationError(ApiBackend::OpenCv)),
     }
@@ -73,6 +74,7 @@ pub fn known_camera_control_to_video_capture_property(
 ///  - [`CameraInfo`]'s human name will be "`OpenCV` Capture Device {location}"
 ///  - [`CameraInfo`]'s description will contain the Camera's Index or IP.
 ///  - The API Preference order is the native OS API (linux => `v4l2`, mac => `AVFoundation`, windows => `MSMF`) than [`CAP_AUTO`](https://docs.opencv.org/4.5.2/d4/d15/group__videoio__flags__base.html#gga023786be1ee68a9105bf2e48c700294da77ab1fe260fd182f8ec7655fab27a31d)
+///  - The device is `Send`, as `OpenCV`'s `VideoCapture` is, so it can be moved to another thread.
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-opencv")))]
 pub struct OpenCvCaptureDevice {
     camera_format: CameraFormat,
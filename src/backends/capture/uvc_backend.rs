@@ -17,8 +17,18 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::{
-    ApiBackend, CameraControl, CameraFormat, CameraInfo, CaptureBackendTrait, FrameFormat,
-    KnownCameraControl, KnownCameraControlFlag, NokhwaError, Resolution,
+    buf_mjpeg_to_rgb, buf_yuyv422_to_rgb, buf_yuyv422_to_rgb_with,
+    capture_buffers::{
+        queue_frame, reorder_frame, BackpressureCounters, BufferPool, FrameReorderBuffer,
+        InjectionClock,
+    },
+    mjpeg_to_rgb, rgb_to_i420,
+    thread_utils::{panic_message, StallMonitor},
+    yuyv422_to_i420, yuyv422_to_rgb, yuyv422_to_rgb_with, ApiBackend, BackpressurePolicy,
+    BackpressureStats, BayerPattern, CameraCapabilities, CameraControl, CameraFormat, CameraInfo,
+    CaptureBackendTrait, ControlValueSetter, FrameFormat, FrameRate, KnownCameraControl,
+    KnownCameraControlFlag, NokhwaError, Rect, Resolution, YuvColorSpace, YuvConversion,
+    YuvPlanarFrame, YuvRange, FRAME_INTERVAL_UNITS_PER_SECOND,
 };
 use flume::{Receiver, Sender};
 use image::{ImageBuffer, Rgb};
@@ -27,27 +37,360 @@ use std::{
     any::Any,
     borrow::Cow,
     cell::{Cell, RefCell},
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     mem::MaybeUninit,
-    sync::{atomic::AtomicUsize, Arc},
+    ops::RangeInclusive,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
 };
 use uvc::{
     ActiveStream, Context, DescriptionSubtype, Device, DeviceHandle, StreamFormat, StreamHandle,
 };
 
+pub use crate::capture_buffers::{
+    BufferPoolStats, CaptureFrame, FrameRingBuffer, PooledBuffer, RingBufferStorage, TimingProfile,
+};
+
+/// How often [`UvcContext::wait_for_device()`] looks for the camera.
+pub const WAIT_FOR_DEVICE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 // ignore the IDE, this compiles
+/// A `libuvc` (and by extension `libusb`) context that can be shared between several [`UVCCaptureDevice`]s.
+///
+/// Creating one is comparatively expensive, so create it once, wrap it in an [`Arc`] and pass it to
+/// [`UVCCaptureDevice::create_in_context()`] for every camera you open.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct UvcContext<'a> {
+    context: Context<'a>,
+}
+
+impl<'a> UvcContext<'a> {
+    /// Creates a new [`UvcContext`].
+    /// # Errors
+    /// If `libuvc` fails to initialize (e.g. `libusb` is not available), this will error.
+    pub fn new() -> Result<Self, NokhwaError> {
+        Context::new()
+            .map(|context| UvcContext { context })
+            .map_err(|why| NokhwaError::InitializeError {
+                backend: ApiBackend::UniversalVideoClass,
+                error: why.to_string(),
+            })
+    }
+
+    /// Gets the number of UVC devices visible in this context.
+    /// # Errors
+    /// If `libuvc` fails to list the devices, this will error.
+    pub fn device_count(&self) -> Result<usize, NokhwaError> {
+        self.context
+            .devices()
+            .map(|devices| devices.count())
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "Devices".to_string(),
+                error: why.to_string(),
+            })
+    }
+
+    /// Waits for the camera with the USB `vendor` and `product` ID to appear, e.g. because the application started before
+    /// the camera was enumerated. The device list is polled every [`WAIT_FOR_DEVICE_POLL_INTERVAL`].
+    ///
+    /// The returned [`CameraInfo`]'s index can be passed to [`create_in_context()`](UVCCaptureDevice::create_in_context()).
+    /// # Errors
+    /// If `libuvc` fails to list the devices, or the camera does not appear within `timeout`, this will error.
+    pub fn wait_for_device(
+        &self,
+        vendor: u16,
+        product: u16,
+        timeout: Duration,
+    ) -> Result<CameraInfo, NokhwaError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let found = self
+                .context
+                .devices()
+                .map_err(|why| NokhwaError::GetPropertyError {
+                    property: "Devices".to_string(),
+                    error: why.to_string(),
+                })?
+                .into_iter()
+                .enumerate()
+                .find(|(_, device)| {
+                    device.description().map_or(false, |desc| {
+                        desc.vendor_id == vendor && desc.product_id == product
+                    }) && uvc_is_camera(device)
+                });
+            if let Some((index, device)) = found {
+                return uvc_camera_info(&device, index);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(NokhwaError::TimeoutError(format!(
+                    "Camera {vendor:04x}:{product:04x} did not appear within {timeout:?}"
+                )));
+            }
+            std::thread::sleep(WAIT_FOR_DEVICE_POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Reads the [`CameraCapabilities`] of the device at `index` from its format descriptors, without opening a stream.
+    ///
+    /// The `uvc` crate does not expose the camera terminal and processing unit descriptors, so the controls, pan/tilt, zoom,
+    /// and focus flags are left `false`.
+    /// # Errors
+    /// If the device does not exist or cannot be opened, this will error.
+    pub fn capabilities(&self, index: usize) -> Result<CameraCapabilities, NokhwaError> {
+        let device = self
+            .context
+            .devices()
+            .map_err(|why| NokhwaError::OpenDeviceError(index.to_string(), why.to_string()))?
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| {
+                NokhwaError::OpenDeviceError(index.to_string(), "Not Found".to_string())
+            })?;
+        let devh = device
+            .open()
+            .map_err(|why| NokhwaError::OpenDeviceError(index.to_string(), why.to_string()))?;
+
+        let mut capabilities = CameraCapabilities::default();
+        for format in devh.supported_formats() {
+            for frame_desc in format.supported_formats() {
+                match frame_desc.subtype() {
+                    DescriptionSubtype::FormatUncompressed
+                    | DescriptionSubtype::FrameUncompressed => capabilities.raw_formats = true,
+                    DescriptionSubtype::StillImageFrame => capabilities.still_capture = true,
+                    _ => {}
+                }
+            }
+        }
+        Ok(capabilities)
+    }
+
+    pub(crate) fn inner(&self) -> &Context<'a> {
+        &self.context
+    }
+}
+
+// the frames the buffer pool is sized for when the frame queue is unbounded, and the most memory it holds
+const BUFFER_POOL_UNBOUNDED_DEPTH: usize = 4;
+const BUFFER_POOL_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// Where a [`UVCCaptureDevice`] decodes frames to RGB.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub enum DecodeLocation {
+    /// Frames are decoded by `libuvc` in its capture thread, before they are queued. This is the default.
+    ///
+    /// Every frame is decoded, including the ones [`frame()`](CaptureBackendTrait::frame()) skips.
+    #[default]
+    Callback,
+    /// Frames are queued as the camera sent them, and decoded in [`frame()`](CaptureBackendTrait::frame()) on your thread.
+    ///
+    /// Only frames you actually read are decoded. [`frame_raw()`](CaptureBackendTrait::frame_raw()) returns the undecoded data.
+    Consumer,
+}
+
+/// What the frame callback of a [`UVCCaptureDevice`] does with frames that arrived incomplete.
+///
+/// A frame is incomplete if it is shorter than its resolution needs (YUYV), or does not end with an end of image marker (MJPEG).
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub enum PartialFramePolicy {
+    /// The frame is dropped. This is the default, as a partial frame usually decodes torn or half green.
+    ///
+    /// Incomplete YUYV frames are dropped with [`DecodeLocation::Callback`] under either policy.
+    #[default]
+    Drop,
+    /// The frame is queued, with [`CaptureFrame::is_partial()`] set.
+    Deliver,
+}
+
+/// The largest frame, in pixels, that [`FormatSelectionPolicy::default_bandwidth_aware()`] still streams as YUYV (640x480).
+pub const DEFAULT_YUYV_MAX_PIXELS: u32 = 640 * 480;
+
+/// The most isochronous (streaming) bandwidth a USB 2.0 high speed endpoint can get, in bytes per second, i.e. three 1024
+/// byte transactions per 125 µs microframe.
+pub const USB2_MAX_ISOCHRONOUS_BANDWIDTH: u64 = 24_576_000;
+
+/// How many recent frames [`UVCCaptureDevice::is_exposure_stable()`] can look back over.
+pub const EXPOSURE_HISTORY_LEN: usize = 64;
+
+/// How [`open_stream_auto()`](UVCCaptureDevice::open_stream_auto()) picks a [`FrameFormat`] for the current [`Resolution`].
+///
+/// Either way, if the camera does not offer the preferred format at the resolution, the other one is used.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub enum FormatSelectionPolicy {
+    /// [`FrameFormat::MJPEG`] is preferred, as it needs the least USB bandwidth. This is the default.
+    #[default]
+    PreferMjpeg,
+    /// [`FrameFormat::YUYV`] is preferred for frames of up to `yuyv_max_pixels` pixels, as it needs no decoding and has no
+    /// compression artifacts, and [`FrameFormat::MJPEG`] for larger ones, which would not fit in the USB bandwidth otherwise.
+    BandwidthAware { yuyv_max_pixels: u32 },
+}
+
+impl FormatSelectionPolicy {
+    /// [`FormatSelectionPolicy::BandwidthAware`] with [`DEFAULT_YUYV_MAX_PIXELS`].
+    #[must_use]
+    pub fn default_bandwidth_aware() -> Self {
+        FormatSelectionPolicy::BandwidthAware {
+            yuyv_max_pixels: DEFAULT_YUYV_MAX_PIXELS,
+        }
+    }
+
+    /// The formats to try at `resolution`, in order of preference.
+    #[must_use]
+    pub fn preference(self, resolution: Resolution) -> [FrameFormat; 2] {
+        match self {
+            FormatSelectionPolicy::BandwidthAware { yuyv_max_pixels }
+                if u64::from(resolution.width()) * u64::from(resolution.height())
+                    <= u64::from(yuyv_max_pixels) =>
+            {
+                [FrameFormat::YUYV, FrameFormat::MJPEG]
+            }
+            _ => [FrameFormat::MJPEG, FrameFormat::YUYV],
+        }
+    }
+}
+
+/// The frame intervals (in 100ns units) a UVC camera advertises for one [`Resolution`] of a [`FrameFormat`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub enum FrameIntervals {
+    /// A list of supported intervals.
+    Discrete(Vec<u32>),
+    /// Every interval from `min` to `max` (inclusive) that is a multiple of `step` away from `min`.
+    Continuous { min: u32, max: u32, step: u32 },
+}
+
+impl FrameIntervals {
+    /// Whether the camera supports exactly `interval`.
+    #[must_use]
+    pub fn contains(&self, interval: u32) -> bool {
+        match self {
+            FrameIntervals::Discrete(intervals) => intervals.contains(&interval),
+            FrameIntervals::Continuous { min, max, step } => {
+                (*min..=*max).contains(&interval) && (interval - min) % (*step).max(1) == 0
+            }
+        }
+    }
+
+    /// Gets the supported interval closest to `requested`, or `None` if there are none.
+    #[must_use]
+    pub fn closest(&self, requested: u32) -> Option<u32> {
+        match self {
+            FrameIntervals::Discrete(intervals) => intervals
+                .iter()
+                .copied()
+                .filter(|interval| *interval != 0)
+                .min_by_key(|interval| interval.abs_diff(requested)),
+            FrameIntervals::Continuous { min, max, step } => {
+                let step = (*step).max(1);
+                let clamped = requested.clamp(*min, *max);
+                let below = min + (clamped - min) / step * step;
+                let above = below.saturating_add(step).min(*max);
+                [below, above]
+                    .into_iter()
+                    .filter(|interval| *interval != 0)
+                    .min_by_key(|interval| interval.abs_diff(requested))
+            }
+        }
+    }
+
+    /// Gets the whole frame rates that can be streamed at.
+    ///
+    /// For [`FrameIntervals::Continuous`], this is every whole FPS whose interval lies in the range and is aligned to its step.
+    #[must_use]
+    pub fn frame_rates(&self) -> Vec<u32> {
+        match self {
+            FrameIntervals::Discrete(intervals) => intervals
+                .iter()
+                .map(|interval| FrameRate::Interval(*interval).as_u32())
+                .collect(),
+            FrameIntervals::Continuous { min, max, .. } => {
+                let fastest = FRAME_INTERVAL_UNITS_PER_SECOND / (*min).max(1);
+                let slowest = FRAME_INTERVAL_UNITS_PER_SECOND / (*max).max(1);
+                (slowest.max(1)..=fastest)
+                    .filter(|fps| self.contains(FRAME_INTERVAL_UNITS_PER_SECOND / fps))
+                    .collect()
+            }
+        }
+    }
+}
+
+// The latest raw frame, double buffered: the callback copies into `back`, then swaps it with `front` unless a reader
+// holds `front`, so the callback never waits on a reader.
+#[derive(Default)]
+struct LatestRawSlot {
+    back: Mutex<Vec<u8>>,
+    front: RwLock<Vec<u8>>,
+    has_frame: AtomicBool,
+}
+
+impl LatestRawSlot {
+    fn publish(&self, data: &[u8]) {
+        let mut back = match self.back.lock() {
+            Ok(back) => back,
+            Err(_) => return,
+        };
+        back.clear();
+        back.extend_from_slice(data);
+        if let Ok(mut front) = self.front.try_write() {
+            std::mem::swap(&mut *front, &mut *back);
+            self.has_frame.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+struct BacklogWarning {
+    threshold: usize,
+    callback: Box<dyn FnMut(usize) + Send>,
+    // whether the backlog is above the threshold, so the callback fires once per crossing
+    above: bool,
+}
+
+impl BacklogWarning {
+    fn check(&mut self, backlog: usize) {
+        if backlog < self.threshold {
+            self.above = false;
+        } else if !self.above {
+            self.above = true;
+            (self.callback)(backlog);
+        }
+    }
+}
+
 /// The backend struct that interfaces with `libuvc`.
 /// To see what this does, please see [`CaptureBackendTrait`]
+///
+/// The device is `Send` but not `Sync`. Share it between threads with a `Mutex`, or create it with [`CameraHandle::spawn()`],
+/// which owns it on a thread of its own.
 /// # Quirks
 /// - You may need administrator/superuser privileges to access a UVC device.
 /// - The indexing for this backend is based off of `libuvc`'s device ordering, not the OS.
+/// - UVC-class devices without a video streaming interface (e.g. audio interfaces) are not listed by `query()`, and error in [create()](UVCCaptureDevice::create()). Indices still count them.
+/// - `libuvc` only uses the first USB configuration of a device. Cameras that park modes behind another configuration have to be
+///   switched to it (e.g. with `libusb_set_configuration()`) before [`create()`](UVCCaptureDevice::create()).
+/// - The `libusb` backend cannot be pinned (e.g. `LIBUSB_OPTION_NO_DEVICE_DISCOVERY` in containers), as the `uvc` crate lets
+///   `libuvc` create its `libusb` context without a way to set options on it first.
 /// - You must call [create()](UVCCaptureDevice::create()) instead `new()`, some methods are auto-generated by the self-referencer and are not meant to be used.
 /// - The [create()](UVCCaptureDevice::create()) method will open the device twice.
 /// - Calling [`set_resolution()`](CaptureBackendTrait::set_resolution()), [`set_frame_rate()`](crate::CaptureBackendTrait::set_frame_rate()), or [`set_frame_format()`](crate::CaptureBackendTrait::set_frame_format()) each internally calls [`set_camera_format()`](crate::CaptureBackendTrait::set_camera_format()).
-/// - [`frame_raw()`](crate::CaptureBackendTrait::frame_raw()) returns the same raw data as [`get_frame()`](crate::CaptureBackendTrait::frame()), a.k.a. no custom decoding required, all data is automatically RGB
+/// - Frame rates are negotiated by the closest frame interval the camera advertises.
+/// - If a high resolution stream fails to open, it may not fit in the USB bandwidth. Check with [`check_bandwidth_feasible()`](UVCCaptureDevice::check_bandwidth_feasible()) first.
+/// - YUYV formats must have an even width.
+/// - `libuvc` negotiates [`FrameFormat::Custom`] formats by resolution and frame rate, so avoid one that shares a resolution with another format.
+/// - [`frame_raw()`](crate::CaptureBackendTrait::frame_raw()) returns the same raw data as [`get_frame()`](crate::CaptureBackendTrait::frame()), a.k.a. no custom decoding required, all data is automatically RGB. This does not apply with [`DecodeLocation::Consumer`].
 /// - The [`frame_raw()`](crate::CaptureBackendTrait::frame_raw()) and by extension [`frame()`](crate::CaptureBackendTrait::frame()) functions block.
-/// - Setting controls is not supported.
 /// - This backend, once stream is open, will constantly collect frames. When you call [`frame()`](crate::CaptureBackendTrait::frame()) or one of its variants, it will only give you the latest frame.
+/// - Frames are queued in the order their transfers complete, which can differ from the order the camera captured them in, see [`set_reorder_window()`](UVCCaptureDevice::set_reorder_window()).
+/// - Setting controls is not supported, as the `uvc` crate does not expose the UVC control requests.
+/// - UVC has no control for the activity LED. Cameras that let you switch it do so through a vendor extension unit, which the
+///   `uvc` crate gives no access to.
 /// # Safety
 /// This backend requires use of `unsafe` due to the self-referencing structs involved.
 /// - If [`open_stream()`](crate::CaptureBackendTrait::open_stream()) and [`frame()`](crate::CaptureBackendTrait::frame()) are called in the wrong order this will cause undefined behaviour.
@@ -61,17 +404,48 @@ use uvc::{
 pub struct UVCCaptureDevice<'a> {
     camera_format: CameraFormat,
     camera_info: CameraInfo<'a>,
-    frame_receiver: Receiver<Vec<u8>>,
-    frame_sender: Sender<Vec<u8>>,
+    frame_receiver: Arc<Receiver<CaptureFrame>>,
+    frame_sender: Sender<CaptureFrame>,
+    frame_queue_capacity: Cell<Option<usize>>,
+    backpressure_policy: Cell<BackpressurePolicy>,
+    backpressure_counters: Arc<BackpressureCounters>,
     stream_handle_init: Cell<bool>,
     active_stream_init: Cell<bool>,
-    context: Context<'a>,
+    recovery_threshold: Cell<Option<usize>>,
+    consecutive_errors: Cell<usize>,
+    negotiated_format: Cell<Option<uvc::FrameFormat>>,
+    decode_location: Cell<DecodeLocation>,
+    format_selection_policy: Cell<FormatSelectionPolicy>,
+    last_frame: Arc<Mutex<Instant>>,
+    consumer_disconnected: Arc<AtomicBool>,
+    conversion_error_count: Arc<AtomicUsize>,
+    partial_frame_policy: Cell<PartialFramePolicy>,
+    partial_frame_count: Arc<AtomicUsize>,
+    backlog_warning: Arc<Mutex<Option<BacklogWarning>>>,
+    callback_panic: Arc<Mutex<Option<String>>>,
+    latest_raw: Arc<LatestRawSlot>,
+    ring_buffer: Arc<Mutex<Option<FrameRingBuffer>>>,
+    reorder_buffer: Arc<Mutex<Option<FrameReorderBuffer>>>,
+    late_frame_count: Arc<AtomicUsize>,
+    buffer_pool: Arc<BufferPool>,
+    stall_watchdog: RefCell<Option<StallMonitor>>,
+    frame_timing: RefCell<Option<InjectionClock>>,
+    capability_cache: RefCell<HashMap<FrameFormat, HashMap<Resolution, FrameIntervals>>>,
+    luma_history: RefCell<VecDeque<f32>>,
+    yuv_conversion: Cell<Option<YuvConversion>>,
+    format_change_callback: Option<Box<dyn FnMut(CameraFormat) + Send + 'a>>,
+    context: Arc<UvcContext<'a>>,
     #[not_covariant]
     #[borrows(context)]
     device: Device<'this>,
+    /// The `libuvc` handle of the opened device, for calls this backend does not wrap. It is only reachable through the
+    /// generated `with_device_handle(|devh| ...)`, and cannot be stored beyond that closure.
+    ///
+    /// Changing the stream or the controls through it leaves this backend's state (the [`CameraFormat`], the cached
+    /// capabilities) out of date.
     #[not_covariant]
     #[borrows(device)]
-    device_handle: DeviceHandle<'this>,
+    pub device_handle: DeviceHandle<'this>,
     stream_handle: RefCell<MaybeUninit<StreamHandle<'a>>>,
     active_stream: RefCell<MaybeUninit<ActiveStream<'a, Arc<AtomicUsize>>>>,
 }
@@ -84,18 +458,23 @@ impl<'a> UVCCaptureDevice<'a> {
     /// # Errors
     /// This may error when the `libuvc` backend fails to retrieve the device or its data.
     pub fn create(index: usize, cam_fmt: Option<CameraFormat>) -> Result<Self, NokhwaError> {
-        let context = match Context::new() {
-            Ok(ctx) => ctx,
-            Err(why) => {
-                return Err(NokhwaError::OpenDeviceError(
-                    index.to_string(),
-                    why.to_string(),
-                ))
-            }
-        };
+        UVCCaptureDevice::create_in_context(Arc::new(UvcContext::new()?), index, cam_fmt)
+    }
 
+    /// Creates a UVC Camera device with optional [`CameraFormat`] in an existing, shared [`UvcContext`].
+    ///
+    /// Use this when opening several cameras, so `libusb` is only initialized once. The context is kept alive for as long as any device created in it.
+    /// # Panics
+    /// This operation may panic! If the UVC Context fails to retrieve the device from the gotten IDs, this operation will panic.
+    /// # Errors
+    /// This may error when the `libuvc` backend fails to retrieve the device or its data.
+    pub fn create_in_context(
+        context: Arc<UvcContext<'a>>,
+        index: usize,
+        cam_fmt: Option<CameraFormat>,
+    ) -> Result<Self, NokhwaError> {
         let (camera_info, frame_receiver, frame_sender) = {
-            let device_list = match context.devices() {
+            let device_list = match context.inner().devices() {
                 Ok(device_list) => device_list,
                 Err(why) => {
                     return Err(NokhwaError::OpenDeviceError(
@@ -114,42 +493,17 @@ impl<'a> UVCCaptureDevice<'a> {
                     ))
                 }
             };
+            if !uvc_is_camera(&device) {
+                return Err(NokhwaError::OpenDeviceError(
+                    index.to_string(),
+                    "Not a camera, the device has no video streaming interface".to_string(),
+                ));
+            }
 
-            let device_desc = match device.description() {
-                Ok(desc) => desc,
-                Err(why) => {
-                    return Err(NokhwaError::OpenDeviceError(
-                        index.to_string(),
-                        why.to_string(),
-                    ))
-                }
-            };
-
-            let device_name = match (device_desc.manufacturer, device_desc.product) {
-                (Some(manu), Some(prod)) => {
-                    format!("{} {}", manu, prod)
-                }
-                (_, Some(prod)) => prod,
-                (Some(manu), _) => {
-                    format!(
-                        "{}:{} {}",
-                        device_desc.vendor_id, device_desc.product_id, manu
-                    )
-                }
-                (_, _) => {
-                    format!("{}:{}", device_desc.vendor_id, device_desc.product_id)
-                }
-            };
-
-            let camera_info = CameraInfo::new(
-                device_name,
-                "".to_string(),
-                format!("{}:{}", device_desc.vendor_id, device_desc.product_id),
-                index,
-            );
+            let camera_info = uvc_camera_info(&device, index)?;
 
             let (frame_sender, frame_receiver) = {
-                let (a, b) = flume::unbounded::<Vec<u8>>();
+                let (a, b) = flume::unbounded::<CaptureFrame>();
                 (a, b)
             };
             (camera_info, frame_receiver, frame_sender)
@@ -163,40 +517,1276 @@ impl<'a> UVCCaptureDevice<'a> {
         Ok(UVCCaptureDeviceBuilder {
             camera_format,
             camera_info,
-            frame_receiver,
+            frame_receiver: Arc::new(frame_receiver),
             frame_sender,
+            frame_queue_capacity: Cell::new(None),
+            backpressure_policy: Cell::new(BackpressurePolicy::default()),
+            backpressure_counters: Arc::new(BackpressureCounters::default()),
             context,
             stream_handle_init: Cell::new(false),
             active_stream_init: Cell::new(false),
+            recovery_threshold: Cell::new(None),
+            consecutive_errors: Cell::new(0),
+            negotiated_format: Cell::new(None),
+            decode_location: Cell::new(DecodeLocation::default()),
+            format_selection_policy: Cell::new(FormatSelectionPolicy::default()),
+            last_frame: Arc::new(Mutex::new(Instant::now())),
+            consumer_disconnected: Arc::new(AtomicBool::new(false)),
+            conversion_error_count: Arc::new(AtomicUsize::new(0)),
+            partial_frame_policy: Cell::new(PartialFramePolicy::default()),
+            partial_frame_count: Arc::new(AtomicUsize::new(0)),
+            backlog_warning: Arc::new(Mutex::new(None)),
+            callback_panic: Arc::new(Mutex::new(None)),
+            latest_raw: Arc::new(LatestRawSlot::default()),
+            ring_buffer: Arc::new(Mutex::new(None)),
+            reorder_buffer: Arc::new(Mutex::new(None)),
+            late_frame_count: Arc::new(AtomicUsize::new(0)),
+            buffer_pool: Arc::new(BufferPool::default()),
+            stall_watchdog: RefCell::new(None),
+            frame_timing: RefCell::new(None),
+            capability_cache: RefCell::new(HashMap::new()),
+            luma_history: RefCell::new(VecDeque::with_capacity(EXPOSURE_HISTORY_LEN)),
+            yuv_conversion: Cell::new(None),
+            format_change_callback: None,
             device_builder: |context_builder| {
                 context_builder
+                    .inner()
                     .devices()
                     .unwrap()
                     .into_iter()
                     .nth(index)
                     .unwrap()
             },
-            device_handle_builder: |device_builder| device_builder.open().unwrap(),
-            stream_handle: RefCell::new(MaybeUninit::uninit()),
-            active_stream: RefCell::new(MaybeUninit::uninit()),
+            device_handle_builder: |device_builder| device_builder.open().unwrap(),
+            stream_handle: RefCell::new(MaybeUninit::uninit()),
+            active_stream: RefCell::new(MaybeUninit::uninit()),
+        }
+        .build())
+    }
+
+    /// Create a UVC Camera with desired settings.
+    /// # Panics
+    /// This operation may panic! If the UVC Context fails to retrieve the device from the gotten IDs, this operation will panic.
+    /// # Errors
+    /// This may error when the `libuvc` backend fails to retrieve the device or its data.
+    pub fn create_with(
+        index: usize,
+        width: u32,
+        height: u32,
+        fps: u32,
+        fourcc: FrameFormat,
+    ) -> Result<Self, NokhwaError> {
+        let camera_format = Some(CameraFormat::new_from(width, height, fourcc, fps));
+        UVCCaptureDevice::create(index, camera_format)
+    }
+
+    /// Gets the [`FrameIntervals`] (in 100ns units) the camera supports for each [`Resolution`] of a [`FrameFormat`].
+    ///
+    /// Unlike [`compatible_list_by_resolution()`](CaptureBackendTrait::compatible_list_by_resolution()), these are not
+    /// rounded to a whole FPS, so e.g. 29.97 FPS (`333667`) can be told apart from 30 FPS (`333333`), and continuous ranges
+    /// are kept as ranges.
+    ///
+    /// The descriptors are only read the first time a [`FrameFormat`] is queried, see [`refresh_capabilities()`](UVCCaptureDevice::refresh_capabilities()).
+    /// # Errors
+    /// This does not currently error, the [`Result`] is kept for parity with the other query functions.
+    pub fn compatible_intervals_by_resolution(
+        &mut self,
+        fourcc: FrameFormat,
+    ) -> Result<HashMap<Resolution, FrameIntervals>, NokhwaError> {
+        Ok(self.cached_frame_intervals(fourcc))
+    }
+
+    /// Drops the cached resolutions and frame intervals, so the next query reads them from the camera's descriptors again.
+    pub fn refresh_capabilities(&mut self) {
+        self.borrow_capability_cache().borrow_mut().clear();
+    }
+
+    // The cache is keyed by `FrameFormat`, as that is what the descriptors are grouped by.
+    fn cached_frame_intervals(&self, fourcc: FrameFormat) -> HashMap<Resolution, FrameIntervals> {
+        if let Some(intervals) = self.borrow_capability_cache().borrow().get(&fourcc) {
+            return intervals.clone();
+        }
+        let intervals = self.with_device_handle(|devh| uvc_frame_intervals(devh, fourcc));
+        self.borrow_capability_cache()
+            .borrow_mut()
+            .insert(fourcc, intervals.clone());
+        intervals
+    }
+
+    /// Estimates the USB bandwidth a [`CameraFormat`] needs, in bytes per second.
+    ///
+    /// Uncompressed formats are exact, e.g. YUYV is `width * height * 2 * fps`. MJPEG frames change size with the scene, so
+    /// they are assumed to be one byte per pixel, which very few cameras exceed.
+    #[must_use]
+    pub fn estimate_bandwidth(fmt: &CameraFormat) -> u64 {
+        // bytes per pixel, as numerator and denominator
+        let (bytes, per_pixels) = match fmt.format() {
+            FrameFormat::MJPEG | FrameFormat::Bayer8(_) => (1, 1),
+            FrameFormat::YUYV => (2, 1),
+            FrameFormat::Bayer10Packed(_) => (5, 4),
+            FrameFormat::Bayer12Packed(_) => (3, 2),
+            _ => (3, 1),
+        };
+        let frame_size = u64::from(fmt.width()) * u64::from(fmt.height()) * bytes / per_pixels;
+        frame_size * u64::from(FRAME_INTERVAL_UNITS_PER_SECOND)
+            / u64::from(fmt.frame_interval().max(1))
+    }
+
+    /// Describes the camera's configuration in one line for logs and bug reports, e.g.
+    /// `1280x720 MJPEG @30fps (negotiated: 1280x720 @24fps), device Logitech C920 046d:082d`.
+    ///
+    /// The negotiated format is only included while the stream is open, and the IDs only if `libusb` reports them.
+    #[must_use]
+    pub fn format_summary(&self) -> String {
+        let camera_format = self.borrow_camera_format();
+        let mut summary = format!(
+            "{} {} @{}fps",
+            camera_format.resolution(),
+            camera_format.format(),
+            uvc_fps_summary(camera_format.frame_rate_f32())
+        );
+        if self.borrow_active_stream_init().get() {
+            let negotiated =
+                self.with_device_handle(|devh| uvc_negotiated_format(devh, camera_format));
+            summary.push_str(&format!(
+                " (negotiated: {} @{}fps)",
+                negotiated.resolution(),
+                uvc_fps_summary(negotiated.frame_rate_f32())
+            ));
+        }
+        summary.push_str(&format!(
+            ", device {}",
+            self.borrow_camera_info().human_name()
+        ));
+        if let Ok(desc) = self.with_device(|device| device.description()) {
+            summary.push_str(&format!(" {:04x}:{:04x}", desc.vendor_id, desc.product_id));
+        }
+        summary
+    }
+
+    /// Checks whether `fmt` fits in the bandwidth of a USB 2.0 connection, without opening a stream.
+    ///
+    /// This compares [`estimate_bandwidth()`](UVCCaptureDevice::estimate_bandwidth()) against [`USB2_MAX_ISOCHRONOUS_BANDWIDTH`].
+    /// `Ok(false)` means opening a stream with `fmt` will most likely fail, unless the camera is connected through USB 3.
+    /// # Errors
+    /// If the camera does not offer `fmt`'s [`FrameFormat`] at its [`Resolution`], this will error.
+    pub fn check_bandwidth_feasible(&self, fmt: &CameraFormat) -> Result<bool, NokhwaError> {
+        if !self
+            .cached_frame_intervals(fmt.format())
+            .contains_key(&fmt.resolution())
+        {
+            return Err(NokhwaError::GetPropertyError {
+                property: "CameraFormat".to_string(),
+                error: format!("{fmt} is not supported"),
+            });
+        }
+        Ok(Self::estimate_bandwidth(fmt) <= USB2_MAX_ISOCHRONOUS_BANDWIDTH)
+    }
+
+    /// Whether the camera advertises exactly `fmt`: its [`FrameFormat`] at its [`Resolution`], with its frame interval in the
+    /// discrete list, or in the continuous range and aligned to its step.
+    ///
+    /// Other frame rates still open, at the closest supported interval.
+    #[must_use]
+    pub fn is_format_supported(&self, fmt: &CameraFormat) -> bool {
+        self.cached_frame_intervals(fmt.format())
+            .get(&fmt.resolution())
+            .map_or(false, |intervals| intervals.contains(fmt.frame_interval()))
+    }
+
+    /// Opens the stream like [`open_stream()`](CaptureBackendTrait::open_stream()), then waits up to `timeout` for the first
+    /// frame to arrive. The frame is left queued for [`frame()`](CaptureBackendTrait::frame()).
+    ///
+    /// If no frame arrives in time, the stream is stopped again, so calling this again (or [`open_stream()`](CaptureBackendTrait::open_stream()))
+    /// is safe. The stream negotiation itself is bounded by `libusb`'s control transfer timeouts, not by `timeout`.
+    /// # Errors
+    /// If the stream fails to open, or no frame arrives within `timeout`, this will error.
+    pub fn open_stream_timeout(&mut self, timeout: Duration) -> Result<(), NokhwaError> {
+        let deadline = Instant::now() + timeout;
+        self.open_stream()?;
+
+        while self.borrow_frame_receiver().is_empty() {
+            if self.consumer_disconnected() || Instant::now() >= deadline {
+                self.stop_stream()?;
+                return Err(NokhwaError::TimeoutError(format!(
+                    "No frame arrived within {timeout:?} of opening the stream"
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        Ok(())
+    }
+
+    /// Gets the [`FormatSelectionPolicy`] [`open_stream_auto()`](UVCCaptureDevice::open_stream_auto()) uses.
+    #[must_use]
+    pub fn format_selection_policy(&self) -> FormatSelectionPolicy {
+        self.borrow_format_selection_policy().get()
+    }
+
+    /// Sets the [`FormatSelectionPolicy`] [`open_stream_auto()`](UVCCaptureDevice::open_stream_auto()) uses.
+    pub fn set_format_selection_policy(&mut self, policy: FormatSelectionPolicy) {
+        self.borrow_format_selection_policy().set(policy);
+    }
+
+    /// Opens the stream at the current [`Resolution`] and frame rate, picking the [`FrameFormat`] for you.
+    ///
+    /// [`FrameFormat::MJPEG`] and [`FrameFormat::YUYV`] are tried in the order the [`format_selection_policy()`](UVCCaptureDevice::format_selection_policy())
+    /// prefers at the current [`Resolution`], using the first one the camera offers there. By default, that is MJPEG first,
+    /// as it needs the least USB bandwidth.
+    ///
+    /// The frame rate is negotiated as usual, so the closest one the camera supports is used. The chosen format is stored,
+    /// so [`camera_format()`](CaptureBackendTrait::camera_format()) returns it afterwards, and it is returned here too.
+    /// Use [`set_camera_format()`](CaptureBackendTrait::set_camera_format()) and [`open_stream()`](CaptureBackendTrait::open_stream())
+    /// instead if you need a specific format.
+    /// # Errors
+    /// If neither format is offered at the current [`Resolution`], or the stream fails to open, this will error.
+    pub fn open_stream_auto(&mut self) -> Result<CameraFormat, NokhwaError> {
+        let mut camera_format = self.camera_format();
+        let offered = self.compatible_fourcc()?;
+        let fourcc = self
+            .format_selection_policy()
+            .preference(camera_format.resolution())
+            .into_iter()
+            .filter(|fourcc| offered.contains(fourcc))
+            .find(|fourcc| {
+                self.compatible_intervals_by_resolution(*fourcc)
+                    .map_or(false, |list| list.contains_key(&camera_format.resolution()))
+            })
+            .ok_or_else(|| {
+                NokhwaError::OpenStreamError(format!(
+                    "Neither MJPEG nor YUYV is supported at {}",
+                    camera_format.resolution()
+                ))
+            })?;
+
+        camera_format.set_format(fourcc);
+        self.set_camera_format(camera_format)?;
+        // `set_camera_format()` already reopens an open stream
+        if !self.is_stream_open() {
+            self.open_stream()?;
+        }
+        Ok(camera_format)
+    }
+
+    /// Whether the auto exposure has settled, i.e. the mean luma of each of the last `window` decoded frames differs from the
+    /// one before by less than `threshold`. Luma is in `0.0..=1.0`, so e.g. `0.01` allows a 1% change between frames.
+    ///
+    /// Only frames decoded to RGB (e.g. by [`frame()`](CaptureBackendTrait::frame())) are measured, so keep reading frames while
+    /// waiting for this. It is `false` until `window` frames were read.
+    /// # Errors
+    /// If the stream is not open, no frame was read yet, or `window` is `0` or more than [`EXPOSURE_HISTORY_LEN`], this will error.
+    pub fn is_exposure_stable(&self, threshold: f32, window: usize) -> Result<bool, NokhwaError> {
+        if window == 0 || window > EXPOSURE_HISTORY_LEN {
+            return Err(NokhwaError::GetPropertyError {
+                property: "exposure stability".to_string(),
+                error: format!(
+                    "The window must be between 1 and {EXPOSURE_HISTORY_LEN} frames, got {window}"
+                ),
+            });
+        }
+        if !self.borrow_active_stream_init().get() {
+            return Err(NokhwaError::ReadFrameError(
+                "Please call `open_stream()` first!".to_string(),
+            ));
+        }
+        let luma_history = self.borrow_luma_history().borrow();
+        if luma_history.is_empty() {
+            return Err(NokhwaError::ReadFrameError(
+                "No frame was read yet".to_string(),
+            ));
+        }
+        if luma_history.len() < window {
+            return Ok(false);
+        }
+
+        let recent = luma_history.iter().skip(luma_history.len() - window);
+        Ok(recent
+            .clone()
+            .zip(recent.skip(1))
+            .all(|(previous, next)| (next - previous).abs() < threshold))
+    }
+
+    /// Tries each of `candidates` in order, e.g. 1080p60, then 1080p30, then 720p30, and opens the stream with the first one
+    /// that works. The [`CameraFormat`] that was used is returned, with the frame rate as it was negotiated.
+    ///
+    /// An open stream is reopened with the new format.
+    /// # Errors
+    /// If no candidate works, this will error with the reason each one failed, and the previous [`CameraFormat`] is restored.
+    /// The stream is left closed, even if it was open before.
+    pub fn open_stream_with_fallback(
+        &mut self,
+        candidates: impl IntoIterator<Item = CameraFormat>,
+    ) -> Result<CameraFormat, NokhwaError> {
+        let previous = self.camera_format();
+        let mut failures = vec![];
+        for candidate in candidates {
+            // `set_camera_format()` already reopens an open stream
+            let opened = self.set_camera_format(candidate).and_then(|_| {
+                if self.is_stream_open() {
+                    Ok(())
+                } else {
+                    self.open_stream()
+                }
+            });
+            match opened {
+                Ok(()) => {
+                    return Ok(
+                        self.with_device_handle(|devh| uvc_negotiated_format(devh, &candidate))
+                    )
+                }
+                Err(why) => failures.push(format!("{candidate}: {why}")),
+            }
+        }
+
+        if failures.is_empty() {
+            return Err(NokhwaError::OpenStreamError(
+                "No candidate formats were given".to_string(),
+            ));
+        }
+        let _restore_err = self.set_camera_format(previous);
+        Err(NokhwaError::OpenStreamError(format!(
+            "No candidate format worked: {}",
+            failures.join("; ")
+        )))
+    }
+
+    /// Gets the latest frame, like [`frame()`](CaptureBackendTrait::frame()), along with the number of frames that were
+    /// discarded since the last call.
+    ///
+    /// A non-zero count means the consumer is not keeping up with the camera's frame rate.
+    /// # Errors
+    /// If the stream is not open or the frame could not be read, this will error.
+    pub fn frame_with_skipped(
+        &mut self,
+    ) -> Result<(ImageBuffer<Rgb<u8>, Vec<u8>>, usize), NokhwaError> {
+        self.check_decodable()?;
+
+        let (frame, skipped) = self.capture_frame_with_skipped()?;
+        self.decode_to_rgb(frame)
+            .map(|imagebuf| (imagebuf, skipped))
+    }
+
+    /// Triggers a still image capture through the camera's UVC still image trigger control while the stream runs, and returns
+    /// the still.
+    /// # Errors
+    /// The `uvc` crate does not expose the still image trigger control, so this errors with
+    /// [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError).
+    pub fn capture_still(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Captures a frame and saves it to `path` as a JPEG.
+    ///
+    /// With `with_metadata`, the capture time (as UTC), resolution, and the camera's name and `vendor:product` from its [`CameraInfo`]
+    /// are written as the `DateTimeOriginal` and `UserComment` EXIF tags. If they cannot be encoded, a plain JPEG is written instead.
+    /// # Errors
+    /// If the frame could not be read, decoded, or encoded, or the file could not be written, this will error.
+    #[cfg(feature = "output-save")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-save")))]
+    pub fn save_frame(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        with_metadata: bool,
+    ) -> Result<(), NokhwaError> {
+        use image::codecs::jpeg::JpegEncoder;
+        use std::time::SystemTime;
+
+        self.check_decodable()?;
+        let frame = self.capture_frame()?;
+        let captured_at = SystemTime::now()
+            .checked_sub(frame.captured_at().elapsed())
+            .unwrap_or_else(SystemTime::now);
+        let image = self.decode_to_rgb(frame)?;
+
+        let mut jpeg = Vec::new();
+        JpegEncoder::new(&mut jpeg)
+            .encode_image(&image)
+            .map_err(|why| NokhwaError::ProcessFrameError {
+                src: self.borrow_camera_format().format(),
+                destination: "JPEG".to_string(),
+                error: why.to_string(),
+            })?;
+        // the EXIF segment goes right after the start of image marker
+        if with_metadata && jpeg.starts_with(&[0xFF, 0xD8]) {
+            let resolution = Resolution::new(image.width(), image.height());
+            if let Some(exif) = exif_segment(captured_at, resolution, self.borrow_camera_info()) {
+                jpeg.splice(2..2, exif);
+            }
+        }
+
+        std::fs::write(path.as_ref(), jpeg).map_err(|why| {
+            NokhwaError::GeneralError(format!(
+                "Could not write {}: {}",
+                path.as_ref().display(),
+                why
+            ))
+        })
+    }
+
+    /// Captures `count` consecutive frames, starting with the latest one, and encodes each as a PNG in memory, e.g. for an
+    /// animated preview or test fixtures.
+    ///
+    /// All frames are captured before any is encoded, so slow encoding does not make the camera skip frames. With the
+    /// `output-save-parallel` feature, they are encoded on all cores.
+    /// # Errors
+    /// If a frame could not be read, decoded, or encoded, this will error.
+    #[cfg(feature = "output-save")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-save")))]
+    pub fn capture_png_sequence(&mut self, count: usize) -> Result<Vec<Vec<u8>>, NokhwaError> {
+        #[cfg(feature = "output-save-parallel")]
+        use rayon::prelude::*;
+
+        self.check_decodable()?;
+        let mut frames = Vec::with_capacity(count);
+        for idx in 0..count {
+            let frame = if idx == 0 {
+                self.capture_frame()?
+            } else {
+                self.capture_next_frame()?
+            };
+            frames.push(frame);
+        }
+        let images = frames
+            .into_iter()
+            .map(|frame| self.decode_to_rgb(frame))
+            .collect::<Result<Vec<_>, NokhwaError>>()?;
+
+        let format = self.borrow_camera_format().format();
+        let encode = |image: &ImageBuffer<Rgb<u8>, Vec<u8>>| {
+            uvc_encode_png(image).map_err(|why| NokhwaError::ProcessFrameError {
+                src: format,
+                destination: "PNG".to_string(),
+                error: why,
+            })
+        };
+        #[cfg(feature = "output-save-parallel")]
+        let images = images.par_iter();
+        #[cfg(not(feature = "output-save-parallel"))]
+        let images = images.iter();
+        images.map(encode).collect()
+    }
+
+    fn check_decodable(&self) -> Result<(), NokhwaError> {
+        let format = self.borrow_camera_format().format();
+        if format.bayer_pattern().is_some() {
+            return Err(NokhwaError::ProcessFrameError {
+                src: format,
+                destination: "RGB".to_string(),
+                error: "Raw Bayer frames are not decoded, use `frame_bayer()` instead".to_string(),
+            });
+        }
+        if let Some(guid) = format.guid() {
+            return Err(NokhwaError::ProcessFrameError {
+                src: format,
+                destination: "RGB".to_string(),
+                error: format!(
+                    "Custom format {} is not decoded, use `frame_raw()` instead",
+                    uvc_guid_string(guid)
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn decode_to_rgb(
+        &mut self,
+        frame: CaptureFrame,
+    ) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+        let resolution: Resolution = self.borrow_camera_format().resolution();
+        let data = match self.borrow_decode_location().get() {
+            DecodeLocation::Callback => frame.into_data(),
+            DecodeLocation::Consumer => {
+                // the raw frame's buffer goes back to the pool once decoded
+                let data = frame.data();
+                let decoded = match self.borrow_negotiated_format().get() {
+                    Some(uvc::FrameFormat::MJPEG) => mjpeg_to_rgb(data, false),
+                    Some(uvc::FrameFormat::YUYV) => uvc_check_yuyv_len(resolution, data.len())
+                        .and_then(|_| match self.borrow_yuv_conversion().get() {
+                            Some(conversion) => yuyv422_to_rgb_with(data, false, conversion),
+                            None => yuyv422_to_rgb(data, false),
+                        }),
+                    format => Err(NokhwaError::ReadFrameError(format!(
+                        "Cannot decode {format:?} outside of libuvc"
+                    ))),
+                };
+                match decoded {
+                    Ok(decoded) => decoded,
+                    Err(why) => return Err(self.recover_from_error(why)),
+                }
+            }
+        };
+
+        let imagebuf: ImageBuffer<Rgb<u8>, Vec<u8>> =
+            match ImageBuffer::from_vec(resolution.width(), resolution.height(), data) {
+                Some(img) => img,
+                None => {
+                    return Err(self.recover_from_error(NokhwaError::ReadFrameError(
+                        "ImageBuffer too small! This is probably a bug, please report it!"
+                            .to_string(),
+                    )))
+                }
+            };
+
+        self.borrow_consecutive_errors().set(0);
+        let mut luma_history = self.borrow_luma_history().borrow_mut();
+        if luma_history.len() == EXPOSURE_HISTORY_LEN {
+            luma_history.pop_front();
+        }
+        luma_history.push_back(mean_luma(&imagebuf));
+        drop(luma_history);
+        Ok(imagebuf)
+    }
+
+    /// Gets the latest raw Bayer frame, undecoded, along with its colour filter array pattern.
+    ///
+    /// The data is laid out as the [`FrameFormat`] says, i.e. one byte per sample for [`FrameFormat::Bayer8`] and
+    /// MIPI packed samples for [`FrameFormat::Bayer10Packed`] and [`FrameFormat::Bayer12Packed`]. Demosaicing is left to you.
+    /// # Errors
+    /// If the current [`FrameFormat`] is not a Bayer format, or the frame could not be read, this will error.
+    pub fn frame_bayer(&mut self) -> Result<(Vec<u8>, BayerPattern), NokhwaError> {
+        let format = self.borrow_camera_format().format();
+        let pattern = format
+            .bayer_pattern()
+            .ok_or_else(|| NokhwaError::ProcessFrameError {
+                src: format,
+                destination: "Bayer".to_string(),
+                error: "Not a raw Bayer format".to_string(),
+            })?;
+        let (data, _) = self.frame_raw_with_skipped()?;
+        Ok((data.into_owned(), pattern))
+    }
+
+    /// Gets the latest frame in planar I420 (YUV 4:2:0) layout, e.g. to hand it to a video encoder.
+    ///
+    /// YUYV frames decoded with [`DecodeLocation::Consumer`] are repacked directly. Other frames are decoded to RGB first,
+    /// then converted with the [`yuv_conversion()`](UVCCaptureDevice::yuv_conversion()) (BT.601, limited range if unset).
+    /// # Errors
+    /// If the frame could not be read or decoded, this will error.
+    pub fn get_frame_i420(&mut self) -> Result<YuvPlanarFrame, NokhwaError> {
+        let resolution = self.borrow_camera_format().resolution();
+        let frame = self.capture_frame()?;
+        let packed_yuyv = self.borrow_decode_location().get() == DecodeLocation::Consumer
+            && self.borrow_negotiated_format().get() == Some(uvc::FrameFormat::YUYV);
+        if packed_yuyv {
+            return uvc_check_yuyv_len(resolution, frame.data().len())
+                .and_then(|_| yuyv422_to_i420(frame.data(), resolution))
+                .map_err(|why| self.recover_from_error(why));
+        }
+
+        let rgb = self.decode_to_rgb(frame)?;
+        let conversion = self.borrow_yuv_conversion().get().unwrap_or_default();
+        rgb_to_i420(rgb.as_raw(), resolution, conversion)
+    }
+
+    /// Decodes the latest frame and writes it into `texture` as RGBA, without allocating a texture.
+    ///
+    /// `texture` must be a 2D [`TextureFormat::Rgba8Unorm`](wgpu::TextureFormat::Rgba8Unorm) (or [`Rgba8UnormSrgb`](wgpu::TextureFormat::Rgba8UnormSrgb))
+    /// texture with [`COPY_DST`](wgpu::TextureUsages::COPY_DST) usage, and exactly the size of the frame's [`Resolution`].
+    /// Rows are padded to [`COPY_BYTES_PER_ROW_ALIGNMENT`](wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) for you.
+    /// # Errors
+    /// If the frame could not be read or decoded, or the texture's format or size does not match, this will error.
+    #[cfg(feature = "output-wgpu")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-wgpu")))]
+    pub fn upload_frame(
+        &mut self,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) -> Result<(), NokhwaError> {
+        let texture_error = |error: String| NokhwaError::ProcessFrameError {
+            src: self.borrow_camera_format().format(),
+            destination: "wgpu Texture".to_string(),
+            error,
+        };
+
+        match texture.format() {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => {}
+            other => return Err(texture_error(format!("Expected Rgba8Unorm, got {other:?}"))),
+        }
+        let resolution = self.borrow_camera_format().resolution();
+        let texture_size = texture.size();
+        if texture_size.width != resolution.width() || texture_size.height != resolution.height() {
+            return Err(texture_error(format!(
+                "Texture is {}x{}, the frame is {resolution}",
+                texture_size.width, texture_size.height
+            )));
+        }
+
+        let frame = self.frame()?;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_row = (4 * frame.width() + align - 1) / align * align;
+        let mut data = vec![0_u8; padded_row as usize * frame.height() as usize];
+        for (row, padded) in frame.rows().zip(data.chunks_exact_mut(padded_row as usize)) {
+            for (pixel, rgba) in row.zip(padded.chunks_exact_mut(4)) {
+                rgba[..3].copy_from_slice(&pixel.0);
+                rgba[3] = 255;
+            }
+        }
+
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(frame.height()),
+            },
+            wgpu::Extent3d {
+                width: frame.width(),
+                height: frame.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+        Ok(())
+    }
+
+    /// Sets a callback that is called with the new [`CameraFormat`] whenever [`set_camera_format()`](CaptureBackendTrait::set_camera_format())
+    /// (or one of the setters that call it) reopens the stream with it.
+    ///
+    /// The format passed is the one the camera negotiated, e.g. with the frame rate it actually streams at. Changing the format
+    /// while the stream is closed does not call this, as nothing is negotiated until [`open_stream()`](CaptureBackendTrait::open_stream()).
+    /// Setting a new callback replaces the old one. The callback is dropped with the device.
+    pub fn set_format_change_callback(&mut self, callback: impl FnMut(CameraFormat) + Send + 'a) {
+        self.with_format_change_callback_mut(|cb| *cb = Some(Box::new(callback)));
+    }
+
+    /// Removes the callback set with [`set_format_change_callback()`](UVCCaptureDevice::set_format_change_callback()).
+    pub fn clear_format_change_callback(&mut self) {
+        self.with_format_change_callback_mut(|cb| *cb = None);
+    }
+
+    /// Gets where frames are decoded to RGB, see [`DecodeLocation`].
+    #[must_use]
+    pub fn decode_location(&self) -> DecodeLocation {
+        self.borrow_decode_location().get()
+    }
+
+    /// The color space and range YUYV frames are decoded with, if set by [`set_yuv_conversion()`](UVCCaptureDevice::set_yuv_conversion()).
+    #[must_use]
+    pub fn yuv_conversion(&self) -> Option<YuvConversion> {
+        self.borrow_yuv_conversion().get()
+    }
+
+    /// Sets the color space and range YUYV frames are decoded with, e.g. [`YuvColorSpace::Bt709`] and [`YuvRange::Limited`]
+    /// for most HD webcams. Without this, YUYV frames are decoded by `libuvc` or, with [`DecodeLocation::Consumer`],
+    /// [`yuyv422_to_rgb()`](crate::utils::yuyv422_to_rgb()).
+    ///
+    /// With [`DecodeLocation::Consumer`], this applies from the next frame on. With [`DecodeLocation::Callback`], the frame
+    /// callback decodes YUYV frames with it instead of leaving them to `libuvc`, from the next
+    /// [`open_stream()`](CaptureBackendTrait::open_stream()) on.
+    pub fn set_yuv_conversion(&mut self, color_space: YuvColorSpace, range: YuvRange) {
+        self.borrow_yuv_conversion()
+            .set(Some(YuvConversion::new(color_space, range)));
+    }
+
+    /// Sets where frames are decoded to RGB, see [`DecodeLocation`]. If the stream is open, it is restarted and
+    /// frames queued in the old form are discarded.
+    /// # Errors
+    /// If the stream fails to restart, this will error.
+    pub fn set_decode_location(&mut self, location: DecodeLocation) -> Result<(), NokhwaError> {
+        if self.borrow_decode_location().replace(location) == location {
+            return Ok(());
+        }
+
+        if self.borrow_active_stream_init().get() {
+            self.stop_stream()?;
+            self.borrow_frame_receiver().drain();
+            self.open_stream()?;
+        }
+        Ok(())
+    }
+
+    /// Checks if frames are only decoded when you read them, i.e. if the [`DecodeLocation`] is [`DecodeLocation::Consumer`].
+    #[must_use]
+    pub fn lazy_decode(&self) -> bool {
+        self.decode_location() == DecodeLocation::Consumer
+    }
+
+    /// Sets whether frames are only decoded when you read them. This is shorthand for
+    /// [`set_decode_location()`](UVCCaptureDevice::set_decode_location()) with [`DecodeLocation::Consumer`] (`true`)
+    /// or [`DecodeLocation::Callback`] (`false`).
+    ///
+    /// Lazy decoding saves the CPU time spent on frames that are never read, e.g. when frames are only taken on a trigger.
+    /// # Errors
+    /// If the stream fails to restart, this will error.
+    pub fn set_lazy_decode(&mut self, lazy: bool) -> Result<(), NokhwaError> {
+        self.set_decode_location(if lazy {
+            DecodeLocation::Consumer
+        } else {
+            DecodeLocation::Callback
+        })
+    }
+
+    /// Requests a format by the 16 byte `guidFormat` of its UVC format descriptor, in descriptor byte order. This is
+    /// for formats that nokhwa does not know, which [`compatible_fourcc()`](CaptureBackendTrait::compatible_fourcc()) lists as [`FrameFormat::Custom`].
+    ///
+    /// Their frames are not decoded, read them with [`frame_raw()`](CaptureBackendTrait::frame_raw()).
+    /// # Errors
+    /// If the camera has no format with this GUID, or it is not offered at the current resolution, this will error.
+    pub fn set_frame_format_guid(&mut self, guid: [u8; 16]) -> Result<(), NokhwaError> {
+        let format = FrameFormat::from_guid(guid);
+        if !self.compatible_fourcc()?.contains(&format) {
+            return Err(NokhwaError::SetPropertyError {
+                property: "FrameFormat".to_string(),
+                value: uvc_guid_string(guid),
+                error: "The camera has no format with this GUID".to_string(),
+            });
+        }
+        self.set_frame_format(format)
+    }
+
+    /// Gets the `libuvc` [`FrameFormat`](uvc::FrameFormat) that the current [`FrameFormat`] is requested as.
+    ///
+    /// This is an interop escape hatch for code that dispatches on the full `libuvc` enum. It is tied to the version of the
+    /// `uvc` crate this library depends on, and may change along with it.
+    #[must_use]
+    pub fn to_uvc_frame_format(&self) -> uvc::FrameFormat {
+        uvc_frame_format(self.borrow_camera_format().format())
+    }
+
+    /// Gets the `libuvc` [`FrameFormat`](uvc::FrameFormat) the open stream was negotiated with, or `None` if the stream is not open.
+    ///
+    /// Same as [`to_uvc_frame_format()`](UVCCaptureDevice::to_uvc_frame_format()), this is tied to the `uvc` crate version.
+    #[must_use]
+    pub fn negotiated_uvc_frame_format(&self) -> Option<uvc::FrameFormat> {
+        self.borrow_negotiated_format().get()
+    }
+
+    /// Sets both [`KnownCameraControl::Pan`] and [`KnownCameraControl::Tilt`], in arc-seconds, with a single USB request.
+    /// # Errors
+    /// The `uvc` crate does not expose the pan/tilt controls, so this errors with
+    /// [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError) before looking at either value.
+    pub fn set_pan_tilt(&mut self, _pan: i32, _tilt: i32) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Gets the white balance temperatures the camera can be set to, in Kelvin, e.g. `2800..=6500`.
+    /// # Errors
+    /// The `uvc` crate does not expose the white balance temperature control, so this errors with
+    /// [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError).
+    pub fn white_balance_kelvin_range(&self) -> Result<RangeInclusive<u16>, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Gets the current white balance temperature, in Kelvin.
+    /// # Errors
+    /// See [`white_balance_kelvin_range()`](UVCCaptureDevice::white_balance_kelvin_range()).
+    pub fn get_white_balance_kelvin(&self) -> Result<u16, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Sets the white balance temperature to `temp` Kelvin.
+    /// # Errors
+    /// See [`white_balance_kelvin_range()`](UVCCaptureDevice::white_balance_kelvin_range()).
+    pub fn set_white_balance_kelvin(&mut self, _temp: u16) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Sets the region auto exposure meters on, e.g. a face instead of the bright window behind it.
+    /// `None` goes back to metering on the full frame.
+    /// # Errors
+    /// The `uvc` crate does not expose the UVC 1.5 region of interest control, so this errors with
+    /// [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError).
+    pub fn set_ae_roi(&mut self, _roi: Option<Rect>) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Sets the region auto focus focuses on. `None` goes back to focusing on the full frame.
+    /// # Errors
+    /// See [`set_ae_roi()`](UVCCaptureDevice::set_ae_roi()).
+    pub fn set_af_roi(&mut self, _roi: Option<Rect>) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
+    }
+
+    /// Writes the device reported default back to every control in
+    /// [`supported_camera_controls()`](CaptureBackendTrait::supported_camera_controls()).
+    ///
+    /// Mode controls (e.g. [`KnownCameraControl::ExposureMode`]) are written last, as setting the controls they govern switches them to manual.
+    /// # Errors
+    /// If a control cannot be read or its default fails to be set, this will error. Controls before the failing one stay reset.
+    pub fn reset_to_default(&mut self) -> Result<(), NokhwaError> {
+        let mut controls = self.supported_camera_controls()?;
+        controls.sort_by_key(|control| control.manual_mode_value().is_some());
+        for control in controls {
+            let camera_control = self.camera_control(control)?;
+            if camera_control
+                .flag()
+                .contains(&KnownCameraControlFlag::WriteOnly)
+            {
+                continue;
+            }
+            if let Some(default) = camera_control.description().default_value() {
+                self.set_camera_control(control, default)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes `data` through the same channel the `libuvc` frame callback uses, so the next
+    /// [`frame()`](CaptureBackendTrait::frame()) call returns it as if the camera had sent it.
+    ///
+    /// This is meant for testing the decode path against crafted (e.g. truncated or mis-sized) frames.
+    /// The stream still has to be open for the frame to be read.
+    /// # Errors
+    /// If the receiving end has been dropped, this will error.
+    #[cfg(any(test, feature = "test-backend"))]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "test-backend")))]
+    pub fn inject_frame(&self, data: Vec<u8>) -> Result<(), NokhwaError> {
+        self.inject(data, None)
+    }
+
+    /// Pushes `data` like [`inject_frame()`](UVCCaptureDevice::inject_frame()), as a frame the camera stamped with
+    /// `device_timestamp`, e.g. to test [`set_reorder_window()`](UVCCaptureDevice::set_reorder_window()) with frames
+    /// injected out of order.
+    /// # Errors
+    /// If the receiving end has been dropped, this will error.
+    #[cfg(any(test, feature = "test-backend"))]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "test-backend")))]
+    pub fn inject_frame_with_timestamp(
+        &self,
+        data: Vec<u8>,
+        device_timestamp: u32,
+    ) -> Result<(), NokhwaError> {
+        self.inject(data, Some(device_timestamp))
+    }
+
+    #[cfg(any(test, feature = "test-backend"))]
+    fn inject(&self, data: Vec<u8>, device_timestamp: Option<u32>) -> Result<(), NokhwaError> {
+        let mut captured_at = Instant::now();
+        if let Some(clock) = self.borrow_frame_timing().borrow_mut().as_mut() {
+            match clock.next_frame() {
+                Some(due) => {
+                    if let Some(wait) = due.checked_duration_since(Instant::now()) {
+                        std::thread::sleep(wait);
+                    }
+                    captured_at = due;
+                }
+                None => {
+                    self.borrow_backpressure_counters()
+                        .dropped_frames
+                        .fetch_add(1, Ordering::SeqCst);
+                    return Ok(());
+                }
+            }
+        }
+
+        let frame = CaptureFrame {
+            data: PooledBuffer::unpooled(data),
+            captured_at,
+            device_timestamp,
+            partial: false,
+        };
+        for frame in reorder_frame(
+            self.borrow_reorder_buffer(),
+            frame,
+            self.borrow_late_frame_count(),
+        ) {
+            self.borrow_frame_sender()
+                .send(frame)
+                .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Simulates the timing of a real camera for [`inject_frame()`](UVCCaptureDevice::inject_frame()), so frame pacing and
+    /// watchdogs (e.g. [`set_stall_watchdog()`](UVCCaptureDevice::set_stall_watchdog())) can be tested. `None` delivers
+    /// injected frames right away again.
+    ///
+    /// Each injected frame waits for its slot: the `n`th frame is due `n` intervals after this call, offset by up to the
+    /// [`jitter`](TimingProfile::jitter), and [`captured_at()`](CaptureFrame::captured_at) is the time it was due. Dropped frames are
+    /// discarded and counted in [`backpressure_stats()`](UVCCaptureDevice::backpressure_stats()), like frames a
+    /// full queue drops. The schedule is the same in every run, only how late the thread wakes up varies.
+    #[cfg(any(test, feature = "test-backend"))]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "test-backend")))]
+    pub fn set_frame_timing(&mut self, timing: Option<TimingProfile>) {
+        *self.borrow_frame_timing().borrow_mut() = timing.map(InjectionClock::new);
+    }
+
+    /// Starts keeping the frames of the last `window` in a [`FrameRingBuffer`], replacing any frames it held before.
+    ///
+    /// This can be called before or while the stream is open. With [`RingBufferStorage::Decoded`], frames are decoded in the
+    /// frame callback even with [`DecodeLocation::Consumer`], and raw Bayer frames are kept raw.
+    pub fn set_ring_buffer(&mut self, window: Duration, storage: RingBufferStorage) {
+        if let Ok(mut ring_buffer) = self.borrow_ring_buffer().lock() {
+            *ring_buffer = Some(FrameRingBuffer::new(window, storage));
+        }
+    }
+
+    /// Stops keeping frames in the [`FrameRingBuffer`], and drops the ones it held.
+    pub fn clear_ring_buffer(&mut self) {
+        if let Ok(mut ring_buffer) = self.borrow_ring_buffer().lock() {
+            *ring_buffer = None;
+        }
+    }
+
+    /// Gets copies of the frames in the [`FrameRingBuffer`] that were captured at or after `since`, oldest first.
+    /// Use e.g. `Instant::now() - Duration::from_secs(5)` to get the last 5 seconds.
+    /// # Errors
+    /// If [`set_ring_buffer()`](UVCCaptureDevice::set_ring_buffer()) was not called, this will error.
+    pub fn frames_since(&self, since: Instant) -> Result<Vec<CaptureFrame>, NokhwaError> {
+        let ring_buffer = self
+            .borrow_ring_buffer()
+            .lock()
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+        ring_buffer
+            .as_ref()
+            .map(|ring_buffer| ring_buffer.frames_since(since))
+            .ok_or_else(|| {
+                NokhwaError::ReadFrameError(
+                    "The ring buffer is not enabled, call `set_ring_buffer()` first".to_string(),
+                )
+            })
+    }
+
+    /// The number of frames the callback dropped because `libuvc` could not convert them to RGB, e.g. a corrupt MJPEG frame.
+    /// Only frames decoded in the callback are counted, see [`DecodeLocation`]. This is not reset when the stream is reopened.
+    #[must_use]
+    pub fn conversion_error_count(&self) -> usize {
+        self.borrow_conversion_error_count().load(Ordering::SeqCst)
+    }
+
+    /// The number of frames that arrived incomplete, whether they were dropped or delivered. This is not reset when the
+    /// stream is reopened.
+    #[must_use]
+    pub fn partial_frame_count(&self) -> usize {
+        self.borrow_partial_frame_count().load(Ordering::SeqCst)
+    }
+
+    /// Gets how long frames are held back to put them in device timestamp order, if at all. See
+    /// [`set_reorder_window()`](UVCCaptureDevice::set_reorder_window()).
+    #[must_use]
+    pub fn reorder_window(&self) -> Option<Duration> {
+        self.borrow_reorder_buffer()
+            .lock()
+            .ok()
+            .and_then(|reorder_buffer| reorder_buffer.as_ref().map(|reorder| reorder.window))
+    }
+
+    /// Holds each frame back for up to `window` before queueing it, so frames that completed out of order are queued in the
+    /// order of their [`device_timestamp()`](CaptureFrame::device_timestamp). `None`, the default, queues frames as they arrive.
+    ///
+    /// This adds up to `window` of latency, so only enable it if you need strictly ordered frames. A frame that arrives after a
+    /// newer one was queued is dropped and counted in [`late_frame_count()`](UVCCaptureDevice::late_frame_count()). Frames
+    /// without a device timestamp cannot be ordered, and are queued right away along with every frame held before them.
+    ///
+    /// This can be called before or while the stream is open. Frames held back when it is called are dropped.
+    pub fn set_reorder_window(&mut self, window: Option<Duration>) {
+        if let Ok(mut reorder_buffer) = self.borrow_reorder_buffer().lock() {
+            *reorder_buffer = window.map(FrameReorderBuffer::new);
+        }
+    }
+
+    /// The number of frames dropped because they arrived after a newer frame was already queued, see
+    /// [`set_reorder_window()`](UVCCaptureDevice::set_reorder_window()). This is not reset when the stream is reopened.
+    #[must_use]
+    pub fn late_frame_count(&self) -> usize {
+        self.borrow_late_frame_count().load(Ordering::SeqCst)
+    }
+
+    /// Gets how many frames the frame callback wrote into a recycled buffer from the frame buffer pool (hits), and how many
+    /// found it empty and allocated (misses). The pool holds a buffer per frame the queue can hold (see
+    /// [`set_frame_queue()`](UVCCaptureDevice::set_frame_queue())) and two more, as far as the frames fit in 256 MiB, and is
+    /// refilled for the format whenever the stream is opened. The counters are not reset when the stream is reopened.
+    ///
+    /// A buffer only goes back to the pool when its frame is dropped, so holding on to frames, or taking their data with
+    /// [`frame_raw()`](CaptureBackendTrait::frame_raw()) or [`CaptureFrame::into_data()`], shows up as misses.
+    #[must_use]
+    pub fn buffer_pool_stats(&self) -> BufferPoolStats {
+        self.borrow_buffer_pool().stats()
+    }
+
+    /// Gets what is done with incomplete frames.
+    #[must_use]
+    pub fn partial_frame_policy(&self) -> PartialFramePolicy {
+        self.borrow_partial_frame_policy().get()
+    }
+
+    /// Sets what is done with incomplete frames, see [`PartialFramePolicy`]. This applies from the next
+    /// [`open_stream()`](CaptureBackendTrait::open_stream()) on.
+    pub fn set_partial_frame_policy(&mut self, policy: PartialFramePolicy) {
+        self.borrow_partial_frame_policy().set(policy);
+    }
+
+    /// Gets the message of the last panic caught in the frame callback, if any. The frame it happened on is dropped,
+    /// and the stream keeps running.
+    #[must_use]
+    pub fn last_callback_panic(&self) -> Option<String> {
+        self.borrow_callback_panic()
+            .lock()
+            .ok()
+            .and_then(|last_panic| last_panic.clone())
+    }
+
+    /// Whether the frame callback found the receiving end of the frame channel gone.
+    /// While this is `true`, incoming frames are dropped without being decoded and [`is_stream_open()`](CaptureBackendTrait::is_stream_open())
+    /// returns `false`. It is reset by [`open_stream()`](CaptureBackendTrait::open_stream()).
+    #[must_use]
+    pub fn consumer_disconnected(&self) -> bool {
+        self.borrow_consumer_disconnected().load(Ordering::SeqCst)
+    }
+
+    /// Calls `callback` with the number of queued frames when it reaches `threshold`, i.e. when the consumer falls behind the
+    /// camera. It fires once each time the queue grows past `threshold`, and again only after the queue has drained below it.
+    ///
+    /// `callback` runs on the `libuvc` callback thread, so it should return quickly. Calling this again replaces the previous warning.
+    pub fn set_backlog_warning(
+        &mut self,
+        threshold: usize,
+        callback: impl FnMut(usize) + Send + 'static,
+    ) {
+        if let Ok(mut backlog_warning) = self.borrow_backlog_warning().lock() {
+            *backlog_warning = Some(BacklogWarning {
+                threshold,
+                callback: Box::new(callback),
+                above: false,
+            });
+        }
+    }
+
+    /// Removes the warning set by [`set_backlog_warning()`](UVCCaptureDevice::set_backlog_warning()).
+    pub fn clear_backlog_warning(&mut self) {
+        if let Ok(mut backlog_warning) = self.borrow_backlog_warning().lock() {
+            *backlog_warning = None;
+        }
+    }
+
+    /// Starts a watchdog thread that calls `callback` once the stream has gone `timeout` without delivering a frame,
+    /// e.g. because the camera hung without disconnecting. It fires once per stall, and again if frames resume and stop again.
+    ///
+    /// Call this after [`open_stream()`](CaptureBackendTrait::open_stream()). The watchdog is stopped by
+    /// [`stop_stream()`](CaptureBackendTrait::stop_stream()), when this is called again, or when the device is dropped.
+    pub fn set_stall_watchdog(
+        &mut self,
+        timeout: Duration,
+        mut callback: impl FnMut() + Send + 'static,
+    ) {
+        let last_frame = self.borrow_last_frame().clone();
+        let mut fired = false;
+        let watchdog = StallMonitor::spawn(timeout, move || {
+            let stalled = last_frame
+                .lock()
+                .map_or(false, |last| last.elapsed() >= timeout);
+            if stalled && !fired {
+                callback();
+            }
+            fired = stalled;
+        });
+        // the old one, if any, is dropped (and joined) here
+        self.borrow_stall_watchdog().replace(Some(watchdog));
+    }
+
+    /// Gets the number of consecutive frame errors after which the stream is restarted, if any.
+    #[must_use]
+    pub fn error_recovery_threshold(&self) -> Option<usize> {
+        self.borrow_recovery_threshold().get()
+    }
+
+    /// Sets the number of consecutive frame errors after which the stream is restarted with
+    /// [`stop_stream()`](CaptureBackendTrait::stop_stream()) and [`open_stream()`](CaptureBackendTrait::open_stream())
+    /// to re-sync it. `None` (the default) disables this.
+    ///
+    /// When this happens, the frame call returns [`NokhwaError::RecoveredAfterErrors`] and the next call reads from the new stream.
+    pub fn set_error_recovery_threshold(&mut self, threshold: Option<usize>) {
+        self.borrow_recovery_threshold().set(threshold);
+        self.borrow_consecutive_errors().set(0);
+    }
+
+    /// Stops the stream and opens it again with the current [`CameraFormat`], on the device handle that is already open.
+    ///
+    /// This is lighter than creating the device again, e.g. to recover from a glitch, but cannot recover from the camera
+    /// disconnecting, which needs the device to be created again. The stall watchdog and all settings
+    /// are kept. If the stream fails to open again, it is left closed as after [`stop_stream()`](CaptureBackendTrait::stop_stream()),
+    /// never half open.
+    /// # Errors
+    /// If the stream could not be opened again, this will error.
+    pub fn restart_stream(&mut self) -> Result<(), NokhwaError> {
+        let stall_watchdog = self.borrow_stall_watchdog().borrow_mut().take();
+        self.stop_stream()?;
+        *self.borrow_stall_watchdog().borrow_mut() = stall_watchdog;
+        if let Err(why) = self.open_stream() {
+            // the stream handle may have been negotiated before starting the stream failed
+            let _ = self.stop_stream();
+            return Err(why);
+        }
+        Ok(())
+    }
+
+    fn recover_from_error(&mut self, error: NokhwaError) -> NokhwaError {
+        let errors = self.borrow_consecutive_errors().get() + 1;
+        self.borrow_consecutive_errors().set(errors);
+
+        match self.borrow_recovery_threshold().get() {
+            Some(threshold) if errors >= threshold => {
+                self.borrow_consecutive_errors().set(0);
+                if let Err(why) = self.restart_stream() {
+                    return why;
+                }
+                NokhwaError::RecoveredAfterErrors {
+                    errors,
+                    last_error: error.to_string(),
+                }
+            }
+            _ => error,
+        }
+    }
+
+    /// Gets the latest raw frame, like [`frame_raw()`](CaptureBackendTrait::frame_raw()), along with the number of frames
+    /// that were discarded since the last call.
+    /// # Errors
+    /// If the stream is not open or all senders have been dropped, this will error.
+    pub fn frame_raw_with_skipped(&mut self) -> Result<(Cow<[u8]>, usize), NokhwaError> {
+        self.capture_frame_with_skipped()
+            .map(|(frame, skipped)| (Cow::from(frame.into_data()), skipped))
+    }
+
+    /// Gets the latest frame as it was queued, like [`frame_raw()`](CaptureBackendTrait::frame_raw()), along with its
+    /// timestamps. See [`CaptureFrame`].
+    /// # Errors
+    /// If the stream is not open or the frame could not be read, this will error.
+    pub fn capture_frame(&mut self) -> Result<CaptureFrame, NokhwaError> {
+        self.capture_frame_with_skipped().map(|(frame, _)| frame)
+    }
+
+    /// Calls `f` with the latest raw frame, as the camera sent it, in place. Unlike the other frame reads, this does not take
+    /// the frame from the queue, so the same frame can be read more than once.
+    ///
+    /// The callback copies each frame into a back buffer, then swaps it with the one `f` reads. While `f` runs, the swap is
+    /// skipped, so keep it short, or newer frames are only seen once `f` returns and the next frame arrives.
+    ///
+    /// This returns `None` until the first frame of the stream arrives.
+    pub fn with_latest_raw<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let latest_raw = self.borrow_latest_raw();
+        if !latest_raw.has_frame.load(Ordering::SeqCst) {
+            return None;
+        }
+        let front = latest_raw.front.read().ok()?;
+        Some(f(&front))
+    }
+
+    /// Sets how many frames are queued between the `libuvc` callback and the reads (`None`, the default, is unbounded), and what
+    /// happens to new frames when the queue is full. This takes effect the next time the stream is opened.
+    ///
+    /// The frame reads return the latest queued frame and drop the rest, except [`capture_next_frame()`](UVCCaptureDevice::capture_next_frame()),
+    /// so use that one to get every frame that was kept.
+    pub fn set_frame_queue(&mut self, capacity: Option<usize>, policy: BackpressurePolicy) {
+        self.borrow_frame_queue_capacity().set(capacity);
+        self.borrow_backpressure_policy().set(policy);
+    }
+
+    /// Gets the frame queue capacity and [`BackpressurePolicy`], see [`set_frame_queue()`](UVCCaptureDevice::set_frame_queue()).
+    #[must_use]
+    pub fn frame_queue(&self) -> (Option<usize>, BackpressurePolicy) {
+        (
+            self.borrow_frame_queue_capacity().get(),
+            self.borrow_backpressure_policy().get(),
+        )
+    }
+
+    /// Gets how often the [`BackpressurePolicy`] had to act since the device was created. Frames that the reads skip to get
+    /// to the latest one are not counted here, see [`frame_with_skipped()`](UVCCaptureDevice::frame_with_skipped()) for those.
+    #[must_use]
+    pub fn backpressure_stats(&self) -> BackpressureStats {
+        let counters = self.borrow_backpressure_counters();
+        BackpressureStats {
+            dropped_frames: counters.dropped_frames.load(Ordering::SeqCst),
+            blocked_for: Duration::from_nanos(counters.blocked_nanos.load(Ordering::SeqCst)),
+        }
+    }
+
+    /// Takes the oldest queued frame instead of the latest one, waiting for one if the queue is empty. Together with a
+    /// [`BackpressurePolicy`] that does not drop queued frames, this gets every frame.
+    /// # Errors
+    /// If the stream is not open, the queue overflowed with [`BackpressurePolicy::Error`], or the frame could not be read, this will error.
+    pub fn capture_next_frame(&mut self) -> Result<CaptureFrame, NokhwaError> {
+        self.check_frame_queue()?;
+        self.borrow_frame_receiver()
+            .recv()
+            .map_err(|why| NokhwaError::ReadFrameError(format!("All sender dropped: {}", why)))
+    }
+
+    fn check_frame_queue(&self) -> Result<(), NokhwaError> {
+        if !self.borrow_active_stream_init().get() {
+            return Err(NokhwaError::ReadFrameError(
+                "Please call `open_stream()` first!".to_string(),
+            ));
+        }
+        if self.consumer_disconnected() {
+            return Err(NokhwaError::ReadFrameError(
+                "The frame receiver was disconnected, please reopen the stream".to_string(),
+            ));
+        }
+        if self
+            .borrow_backpressure_counters()
+            .overflowed
+            .swap(false, Ordering::SeqCst)
+        {
+            return Err(NokhwaError::ReadFrameError(
+                "The frame queue overflowed, frames were dropped".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Gets the latest frame, like [`capture_frame()`](UVCCaptureDevice::capture_frame()), along with the number of frames
+    /// that were discarded since the last call.
+    /// # Errors
+    /// If the stream is not open or the frame could not be read, this will error.
+    pub fn capture_frame_with_skipped(&mut self) -> Result<(CaptureFrame, usize), NokhwaError> {
+        self.check_frame_queue()?;
+
+        let f_recv = self.borrow_frame_receiver();
+        let messages_iter = f_recv.drain();
+        // every queued frame except the one we return is dropped
+        let skipped = messages_iter.len().saturating_sub(1);
+        match messages_iter.last() {
+            Some(msg) => Ok((msg, skipped)),
+            None => match f_recv.recv() {
+                Ok(msg) => Ok((msg, 0)),
+                Err(why) => Err(NokhwaError::ReadFrameError(format!(
+                    "All sender dropped: {}",
+                    why
+                ))),
+            },
         }
-        .build())
     }
 
-    /// Create a UVC Camera with desired settings.
-    /// # Panics
-    /// This operation may panic! If the UVC Context fails to retrieve the device from the gotten IDs, this operation will panic.
+    /// Waits for the latest frame like [`capture_frame()`](UVCCaptureDevice::capture_frame()), but yields to the async
+    /// runtime instead of blocking while no frame is queued.
+    ///
+    /// This is cancel-safe: if the future is dropped, no frame is lost.
     /// # Errors
-    /// This may error when the `libuvc` backend fails to retrieve the device or its data.
-    pub fn create_with(
-        index: usize,
-        width: u32,
-        height: u32,
-        fps: u32,
-        fourcc: FrameFormat,
-    ) -> Result<Self, NokhwaError> {
-        let camera_format = Some(CameraFormat::new_from(width, height, fourcc, fps));
-        UVCCaptureDevice::create(index, camera_format)
+    /// If the stream is not open or the frame could not be read, this will error.
+    #[cfg(feature = "output-async")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+    pub async fn capture_frame_async(&self) -> Result<CaptureFrame, NokhwaError> {
+        self.check_frame_queue()?;
+
+        let f_recv = self.borrow_frame_receiver();
+        match f_recv.drain().last() {
+            Some(msg) => Ok(msg),
+            None => f_recv
+                .recv_async()
+                .await
+                .map_err(|why| NokhwaError::ReadFrameError(format!("All sender dropped: {}", why))),
+        }
     }
 }
 
@@ -216,6 +1806,7 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
     }
 
     fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
+        uvc_validate_format(&new_fmt)?;
         let prev_fmt = *self.borrow_camera_format();
 
         self.with_camera_format_mut(|cfmt| {
@@ -226,7 +1817,16 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
 
         if is_streamh_some {
             return match self.open_stream() {
-                Ok(_) => Ok(()),
+                Ok(_) => {
+                    let negotiated =
+                        self.with_device_handle(|devh| uvc_negotiated_format(devh, &new_fmt));
+                    self.with_format_change_callback_mut(|callback| {
+                        if let Some(callback) = callback {
+                            callback(negotiated);
+                        }
+                    });
+                    Ok(())
+                }
                 Err(why) => {
                     // revert
                     self.with_camera_format_mut(|cfmt| {
@@ -243,54 +1843,25 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
         Ok(())
     }
 
-    #[allow(clippy::cast_possible_truncation)]
     fn compatible_list_by_resolution(
         &mut self,
         fourcc: FrameFormat,
     ) -> Result<HashMap<Resolution, Vec<u32>>, NokhwaError> {
-        let mut resolution_fps_map: HashMap<Resolution, Vec<u32>> = HashMap::new();
-        for fmt in self.with_device_handle(|devh| devh).supported_formats() {
-            for frame_desc in fmt.supported_formats() {
-                // FIXME: Verify that this is correct way to interpret DescriptionSubtype!
-                let format = match frame_desc.subtype() {
-                    DescriptionSubtype::FormatMJPEG | DescriptionSubtype::FrameMJPEG => {
-                        FrameFormat::MJPEG
-                    }
-                    DescriptionSubtype::FormatUncompressed
-                    | DescriptionSubtype::FrameUncompressed => FrameFormat::YUYV,
-                    _ => continue,
-                };
-
-                if format != fourcc {
-                    continue;
-                }
-
-                let resolution =
-                    Resolution::new(frame_desc.width().into(), frame_desc.height().into());
-                let fps: Vec<u32> = frame_desc
-                    .intervals_duration()
-                    .into_iter()
-                    .map(|duration| (1000 / duration.as_millis()) as u32)
-                    .collect();
-                resolution_fps_map.insert(resolution, fps);
-            }
-        }
-        Ok(resolution_fps_map)
+        Ok(self
+            .compatible_intervals_by_resolution(fourcc)?
+            .into_iter()
+            .map(|(resolution, intervals)| (resolution, intervals.frame_rates()))
+            .collect())
     }
 
     fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError> {
         let mut frameformats = vec![];
         for fmt in self.with_device_handle(|devh| devh).supported_formats() {
             for frame_desc in fmt.supported_formats() {
-                // FIXME: Verify that this is correct way to interpret DescriptionSubtype!
-                match frame_desc.subtype() {
-                    DescriptionSubtype::FormatMJPEG | DescriptionSubtype::FrameMJPEG => {
-                        frameformats.push(FrameFormat::MJPEG);
-                    }
-                    DescriptionSubtype::FormatUncompressed
-                    | DescriptionSubtype::FrameUncompressed => frameformats.push(FrameFormat::YUYV),
-                    _ => continue,
-                };
+                if let Some(format) = uvc_descriptor_format(frame_desc.subtype(), fmt.guid_format())
+                {
+                    frameformats.push(format);
+                }
             }
         }
 
@@ -329,48 +1900,22 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
         self.set_camera_format(current_format)
     }
 
+    // The `uvc` crate does not expose the UVC control requests.
     fn supported_camera_controls(&self) -> Result<Vec<KnownCameraControl>, NokhwaError> {
-        Ok(vec![
-            KnownCameraControl::Exposure,
-            KnownCameraControl::Focus,
-        ])
-    }
-
-    fn camera_control(&self, control: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
-        match control {
-            KnownCameraControl::Focus => match self.with_device_handle(|x| x).exposure_rel() {
-                Ok(v) => {
-                    let v: i8 = v;
-                    match CameraControl::new(
-                        control,
-                        i32::from(i8::MIN),
-                        i32::from(i8::MAX),
-                        i32::from(v),
-                        1_i32,
-                        i32::from(v),
-                        KnownCameraControlFlag::Automatic,
-                        true,
-                    ) {
-                        Ok(cc) => Ok(cc),
-                        Err(why) => Err(NokhwaError::GetPropertyError {
-                            property: control.to_string(),
-                            error: why.to_string(),
-                        }),
-                    }
-                }
-                Err(why) => Err(NokhwaError::GetPropertyError {
-                    property: control.to_string(),
-                    error: why.to_string(),
-                }),
-            },
-            _ => Err(NokhwaError::GetPropertyError {
-                property: control.to_string(),
-                error: "Not Supported".to_string(),
-            }),
-        }
+        Ok(vec![])
+    }
+
+    fn camera_control(&self, _control: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(
+            ApiBackend::UniversalVideoClass,
+        ))
     }
 
-    fn set_camera_control(&mut self, _control: CameraControl) -> Result<(), NokhwaError> {
+    fn set_camera_control(
+        &mut self,
+        _id: KnownCameraControl,
+        _value: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
         Err(NokhwaError::UnsupportedOperationError(
             ApiBackend::UniversalVideoClass,
         ))
@@ -399,16 +1944,21 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
     }
 
     fn open_stream(&mut self) -> Result<(), NokhwaError> {
+        uvc_validate_format(self.borrow_camera_format())?;
         let ret: Result<(), NokhwaError> = self.with_mut(|fields| {
             let stream_format: StreamFormat = StreamFormat {
                 width: (*fields.camera_format).width(),
                 height: (*fields.camera_format).height(),
-                fps: (*fields.camera_format).frame_rate(),
-                format: (*fields.camera_format).format().into(),
+                fps: uvc_stream_fps(fields.device_handle, fields.camera_format),
+                format: uvc_frame_format((*fields.camera_format).format()),
             };
 
             // first, drop the existing stream by setting it to None
             {
+                fields
+                    .backpressure_counters
+                    .stopping
+                    .store(true, Ordering::SeqCst);
                 if fields.active_stream_init.get() {
                     let innard_value = fields.active_stream.replace(MaybeUninit::uninit());
                     unsafe {
@@ -434,11 +1984,20 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
                     Ok(mut streamh_raw) => {
                         *streamh_raw = MaybeUninit::new(streamh);
                         fields.stream_handle_init.set(true);
+                        fields.negotiated_format.set(Some(stream_format.format));
                     }
                     Err(why) => return Err(NokhwaError::OpenStreamError(why.to_string())),
                 },
                 Err(why) => return Err(NokhwaError::OpenStreamError(why.to_string())),
             }
+
+            // the old queue may have been for another capacity
+            let (frame_sender, frame_receiver) = match fields.frame_queue_capacity.get() {
+                Some(capacity) => flume::bounded(capacity.max(1)),
+                None => flume::unbounded(),
+            };
+            *fields.frame_sender = frame_sender;
+            *fields.frame_receiver = Arc::new(frame_receiver);
             Ok(())
         });
 
@@ -449,7 +2008,57 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
         let ret_2: Result<(), NokhwaError> = self.with(|fields| {
             // finally, get the active stream
             let counter = Arc::new(AtomicUsize::new(0));
-            let frame_sender: Sender<Vec<u8>> = self.with_frame_sender(Clone::clone);
+            let frame_sender: Sender<CaptureFrame> = self.with_frame_sender(Clone::clone);
+            let frame_receiver = Arc::downgrade(fields.frame_receiver);
+            let backpressure_policy = fields.backpressure_policy.get();
+            let backpressure_counters = fields.backpressure_counters.clone();
+            backpressure_counters
+                .stopping
+                .store(false, Ordering::SeqCst);
+            let last_frame = fields.last_frame.clone();
+            let consumer_disconnected = fields.consumer_disconnected.clone();
+            consumer_disconnected.store(false, Ordering::SeqCst);
+            let latest_raw = fields.latest_raw.clone();
+            // the previous stream's frame may be in another format
+            latest_raw.has_frame.store(false, Ordering::SeqCst);
+            let ring_buffer = fields.ring_buffer.clone();
+            let reorder_buffer = fields.reorder_buffer.clone();
+            if let Ok(mut reorder_buffer) = reorder_buffer.lock() {
+                if let Some(reorder_buffer) = reorder_buffer.as_mut() {
+                    reorder_buffer.clear();
+                }
+            }
+            let late_frame_count = fields.late_frame_count.clone();
+            let buffer_pool = fields.buffer_pool.clone();
+            let conversion_error_count = fields.conversion_error_count.clone();
+            let partial_frame_policy = fields.partial_frame_policy.get();
+            let partial_frame_count = fields.partial_frame_count.clone();
+            let backlog_warning = fields.backlog_warning.clone();
+            let callback_panic = fields.callback_panic.clone();
+            // libuvc cannot convert raw Bayer or custom formats, so those are always passed through
+            let format = fields.camera_format.format();
+            let decode_location = if format.bayer_pattern().is_some() || format.guid().is_some() {
+                DecodeLocation::Consumer
+            } else {
+                fields.decode_location.get()
+            };
+            let resolution = fields.camera_format.resolution();
+            let negotiated_format = fields.negotiated_format.get();
+            let is_yuyv = negotiated_format == Some(uvc::FrameFormat::YUYV);
+            let yuv_conversion = fields.yuv_conversion.get();
+            // a buffer per queued frame, plus the ones being filled and read, sized for RGB or (at most) YUYV frames
+            let buffer_len = resolution.width() as usize
+                * resolution.height() as usize
+                * if decode_location == DecodeLocation::Callback { 3 } else { 2 };
+            let depth = fields
+                .frame_queue_capacity
+                .get()
+                .unwrap_or(BUFFER_POOL_UNBOUNDED_DEPTH);
+            let buffers = (depth + 2).min((BUFFER_POOL_MAX_BYTES / buffer_len.max(1)).max(2));
+            buffer_pool.reset(buffers, buffer_len);
+            if let Ok(mut last) = last_frame.lock() {
+                *last = Instant::now();
+            }
             let streamh = unsafe {
                 let raw_ptr =
                     (*fields.stream_handle.borrow_mut()).as_ptr() as *mut MaybeUninit<StreamHandle>;
@@ -470,15 +2079,131 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
 
             let active_stream = match streamh_init.start_stream(
                 move |frame, _count| {
-                    let vec_frame = frame.to_rgb().unwrap().to_bytes().to_vec();
-                    if frame_sender.send(vec_frame).is_err() {
-                        // do nothing
+                    // a panic must not unwind into libuvc, drop the frame instead
+                    let caught = catch_unwind(AssertUnwindSafe(|| {
+                        // nobody is listening anymore, don't bother decoding
+                        if frame_sender.is_disconnected() {
+                            consumer_disconnected.store(true, Ordering::SeqCst);
+                            return;
+                        }
+                        let partial = uvc_is_partial(negotiated_format, resolution, frame.to_bytes());
+                        if partial {
+                            partial_frame_count.fetch_add(1, Ordering::SeqCst);
+                            // libuvc would decode a truncated YUYV frame misaligned
+                            if partial_frame_policy == PartialFramePolicy::Drop
+                                || (is_yuyv && decode_location == DecodeLocation::Callback)
+                            {
+                                return;
+                            }
+                        }
+                        let captured_at = Instant::now();
+                        latest_raw.publish(frame.to_bytes());
+                        let vec_frame = match decode_location {
+                            DecodeLocation::Callback => {
+                                // libuvc always decodes YUYV as BT.601, so a set conversion is done by us instead
+                                let decoded = match yuv_conversion {
+                                    Some(conversion) if is_yuyv => {
+                                        yuyv422_to_rgb_with(frame.to_bytes(), false, conversion)
+                                            .ok()
+                                            .map(|rgb| buffer_pool.copy_from(&rgb))
+                                    }
+                                    _ => frame
+                                        .to_rgb()
+                                        .ok()
+                                        .map(|rgb| buffer_pool.copy_from(rgb.to_bytes())),
+                                };
+                                match decoded {
+                                    Some(decoded) => decoded,
+                                    // a panic here would take down the whole process, drop the frame instead
+                                    None => {
+                                        conversion_error_count.fetch_add(1, Ordering::SeqCst);
+                                        return;
+                                    }
+                                }
+                            }
+                            DecodeLocation::Consumer => buffer_pool.copy_from(frame.to_bytes()),
+                        };
+                        let capture_frame = CaptureFrame {
+                            data: vec_frame,
+                            captured_at,
+                            device_timestamp: uvc_device_timestamp(frame),
+                            partial,
+                        };
+                        if let Ok(mut ring_buffer) = ring_buffer.lock() {
+                            if let Some(ring_buffer) = ring_buffer.as_mut() {
+                                let data = match (ring_buffer.storage(), decode_location) {
+                                    (RingBufferStorage::Raw, DecodeLocation::Consumer)
+                                    | (RingBufferStorage::Decoded, DecodeLocation::Callback) => {
+                                        capture_frame.data.to_vec()
+                                    }
+                                    (RingBufferStorage::Raw, DecodeLocation::Callback) => {
+                                        frame.to_bytes().to_vec()
+                                    }
+                                    (RingBufferStorage::Decoded, DecodeLocation::Consumer) => {
+                                        let decoded = match yuv_conversion {
+                                            Some(conversion) if is_yuyv => yuyv422_to_rgb_with(
+                                                frame.to_bytes(),
+                                                false,
+                                                conversion,
+                                            )
+                                            .ok(),
+                                            _ => frame.to_rgb().ok().map(|rgb| rgb.to_bytes().to_vec()),
+                                        };
+                                        decoded.unwrap_or_else(|| capture_frame.data.to_vec())
+                                    }
+                                };
+                                ring_buffer.push(CaptureFrame {
+                                    data: PooledBuffer::unpooled(data),
+                                    captured_at: capture_frame.captured_at,
+                                    device_timestamp: capture_frame.device_timestamp,
+                                    partial,
+                                });
+                            }
+                        }
+                        for capture_frame in
+                            reorder_frame(&reorder_buffer, capture_frame, &late_frame_count)
+                        {
+                            if queue_frame(
+                                &frame_sender,
+                                &frame_receiver,
+                                capture_frame,
+                                backpressure_policy,
+                                &backpressure_counters,
+                            )
+                            .is_err()
+                            {
+                                consumer_disconnected.store(true, Ordering::SeqCst);
+                                return;
+                            }
+                        }
+                        if let Ok(mut backlog_warning) = backlog_warning.lock() {
+                            if let Some(backlog_warning) = backlog_warning.as_mut() {
+                                backlog_warning.check(frame_sender.len());
+                            }
+                        }
+                        if let Ok(mut last) = last_frame.lock() {
+                            *last = Instant::now();
+                        }
+                    }));
+                    if let Err(payload) = caught {
+                        if let Ok(mut last_panic) = callback_panic.lock() {
+                            *last_panic = Some(panic_message(&*payload));
+                        }
                     }
                 },
                 counter,
             ) {
                 Ok(active) => active,
-                Err(why) => return Err(NokhwaError::OpenStreamError(why.to_string())),
+                Err(why) => {
+                    let needed = Self::estimate_bandwidth(fields.camera_format);
+                    if needed > USB2_MAX_ISOCHRONOUS_BANDWIDTH {
+                        return Err(NokhwaError::OpenStreamError(format!(
+                            "{why}: {} needs about {needed} bytes/s, more than USB 2.0 can stream ({USB2_MAX_ISOCHRONOUS_BANDWIDTH} bytes/s)",
+                            fields.camera_format
+                        )));
+                    }
+                    return Err(NokhwaError::OpenStreamError(why.to_string()));
+                }
             };
             *fields.active_stream.borrow_mut() = MaybeUninit::new(active_stream);
             Ok(())
@@ -493,57 +2218,85 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
     }
 
     fn is_stream_open(&self) -> bool {
-        self.with_active_stream_init(Cell::get)
+        self.with_active_stream_init(Cell::get) && !self.consumer_disconnected()
     }
 
     fn frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
-        let resolution: Resolution = self.borrow_camera_format().resolution();
-
-        let data = match self.frame_raw() {
-            Ok(d) => d,
-            Err(why) => return Err(why),
-        };
+        self.frame_with_skipped().map(|(frame, _)| frame)
+    }
 
-        let imagebuf: ImageBuffer<Rgb<u8>, Vec<u8>> =
-            match ImageBuffer::from_vec(resolution.width(), resolution.height(), data.to_vec()) {
-                Some(img) => img,
-                None => {
-                    return Err(NokhwaError::ReadFrameError(
-                        "ImageBuffer too small! This is probably a bug, please report it!"
-                            .to_string(),
-                    ))
-                }
-            };
+    fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+        self.frame_raw_with_skipped().map(|(frame, _)| frame)
+    }
 
-        Ok(imagebuf)
+    fn write_frame_to(&mut self, buffer: &mut [u8]) -> Result<usize, NokhwaError> {
+        let frame = self.capture_frame()?;
+        let data = frame.data();
+        let provided = buffer.len();
+        buffer
+            .get_mut(..data.len())
+            .ok_or(NokhwaError::BufferTooSmall {
+                required: data.len(),
+                provided,
+            })?
+            .copy_from_slice(data);
+        Ok(data.len())
     }
 
-    fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
-        // assertions
-        if !self.borrow_active_stream_init().get() {
-            return Err(NokhwaError::ReadFrameError(
-                "Please call `open_stream()` first!".to_string(),
-            ));
-        }
+    fn write_frame_rgb_to(&mut self, buffer: &mut [u8]) -> Result<usize, NokhwaError> {
+        self.check_decodable()?;
+        let resolution: Resolution = self.borrow_camera_format().resolution();
+        let required = resolution.width() as usize * resolution.height() as usize * 3;
+        let provided = buffer.len();
+        let dest = buffer
+            .get_mut(..required)
+            .ok_or(NokhwaError::BufferTooSmall { required, provided })?;
 
-        let f_recv = self.borrow_frame_receiver();
-        let messages_iter = f_recv.drain();
-        match messages_iter.last() {
-            Some(msg) => Ok(Cow::from(msg)),
-            None => match f_recv.recv() {
-                Ok(msg) => Ok(Cow::from(msg)),
-                Err(why) => {
-                    return Err(NokhwaError::ReadFrameError(format!(
-                        "All sender dropped: {}",
-                        why.to_string()
-                    )))
-                }
+        // the queued frame is decoded straight into `dest`, there is no intermediate `ImageBuffer`
+        let frame = self.capture_frame()?;
+        let data = frame.data();
+        let written = match self.borrow_decode_location().get() {
+            DecodeLocation::Callback if data.len() == required => {
+                dest.copy_from_slice(data);
+                Ok(())
+            }
+            DecodeLocation::Callback => Err(NokhwaError::ReadFrameError(format!(
+                "Decoded frame is {} bytes, expected {required}. This is probably a bug, please report it!",
+                data.len()
+            ))),
+            DecodeLocation::Consumer => match self.borrow_negotiated_format().get() {
+                Some(uvc::FrameFormat::MJPEG) => buf_mjpeg_to_rgb(data, dest, false),
+                Some(uvc::FrameFormat::YUYV) => uvc_check_yuyv_len(resolution, data.len())
+                    .and_then(|_| match self.borrow_yuv_conversion().get() {
+                        Some(conversion) => buf_yuyv422_to_rgb_with(data, dest, false, conversion),
+                        None => buf_yuyv422_to_rgb(data, dest, false),
+                    }),
+                format => Err(NokhwaError::ReadFrameError(format!(
+                    "Cannot decode {format:?} outside of libuvc"
+                ))),
             },
+        };
+
+        match written {
+            Ok(()) => {
+                self.borrow_consecutive_errors().set(0);
+                Ok(required)
+            }
+            Err(why) => Err(self.recover_from_error(why)),
         }
     }
 
     fn stop_stream(&mut self) -> Result<(), NokhwaError> {
         self.with(|fields| {
+            // dropping it joins the thread
+            fields.stall_watchdog.borrow_mut().take();
+            // the next stream may be exposed differently
+            fields.luma_history.borrow_mut().clear();
+            fields
+                .backpressure_counters
+                .stopping
+                .store(true, Ordering::SeqCst);
+
             if fields.active_stream_init.get() {
                 let innard_value = fields.active_stream.replace(MaybeUninit::uninit());
                 unsafe {
@@ -559,7 +2312,575 @@ impl<'a> CaptureBackendTrait for UVCCaptureDevice<'a> {
                 };
                 fields.stream_handle_init.set(false);
             }
+            fields.negotiated_format.set(None);
         });
         Ok(())
     }
 }
+
+// SAFETY: The `Cell`s and `RefCell`s are `Send` already, they only keep the device from being `Sync`. What is not `Send` are
+// the raw `libuvc` handles, but neither `libuvc` nor `libusb` tie a handle to the thread that opened it. The stream callback
+// runs on `libuvc`'s own thread either way, and only shares `Send` data (channels, `Arc`s of atomics and `Mutex`es) with us.
+// The `UvcContext` is shared through an `Arc`, which `libusb` contexts are safe for.
+unsafe impl<'a> Send for UVCCaptureDevice<'a> {}
+
+/// Several [`UVCCaptureDevice`]s that are streamed together, for grabbing one frame from each as close together as possible
+/// (e.g. stereo or camera arrays).
+///
+/// # Quirks
+/// - UVC cameras cannot be triggered, so frames are not captured at the same time, only picked as close together as the
+///   cameras' frame rates allow. Use the [`CaptureFrame`] timestamps (and [`skew()`](MultiCamera::skew())) to check how far apart they are.
+/// - Create the devices in one [`UvcContext`] with [`create_in_context()`](UVCCaptureDevice::create_in_context()), so `libusb` is only initialized once.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct MultiCamera<'a> {
+    devices: Vec<UVCCaptureDevice<'a>>,
+}
+
+impl<'a> MultiCamera<'a> {
+    /// Creates a new [`MultiCamera`] from already created devices. Their streams are not opened yet.
+    #[must_use]
+    pub fn new(devices: Vec<UVCCaptureDevice<'a>>) -> Self {
+        MultiCamera { devices }
+    }
+
+    /// Gets the devices, in the order they were given in.
+    #[must_use]
+    pub fn devices(&self) -> &[UVCCaptureDevice<'a>] {
+        &self.devices
+    }
+
+    /// Gets the devices mutably, e.g. to change a control on one of them.
+    pub fn devices_mut(&mut self) -> &mut [UVCCaptureDevice<'a>] {
+        &mut self.devices
+    }
+
+    /// Takes the devices back out.
+    #[must_use]
+    pub fn into_devices(self) -> Vec<UVCCaptureDevice<'a>> {
+        self.devices
+    }
+
+    /// Opens the stream of every device.
+    /// # Errors
+    /// If any stream fails to open, the ones already opened are stopped again and this will error.
+    pub fn open_streams(&mut self) -> Result<(), NokhwaError> {
+        for idx in 0..self.devices.len() {
+            if let Err(why) = self.devices[idx].open_stream() {
+                for device in &mut self.devices[..idx] {
+                    let _ = device.stop_stream();
+                }
+                return Err(why);
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops the stream of every device.
+    /// # Errors
+    /// If any stream fails to stop, this will error after trying to stop the rest.
+    pub fn stop_streams(&mut self) -> Result<(), NokhwaError> {
+        self.devices
+            .iter_mut()
+            .map(CaptureBackendTrait::stop_stream)
+            .fold(Ok(()), Result::and)
+    }
+
+    /// Gets the latest frame of every device, in the same order as [`devices()`](MultiCamera::devices()).
+    ///
+    /// Each camera's queue is drained back to back, so the frames are the newest ones at nearly the same moment. If a camera
+    /// has no frame queued yet, this blocks until it sends one.
+    /// # Errors
+    /// If a stream is not open or a frame could not be read, this will error.
+    pub fn grab_synchronized(&mut self) -> Result<Vec<CaptureFrame>, NokhwaError> {
+        self.devices
+            .iter_mut()
+            .map(UVCCaptureDevice::capture_frame)
+            .collect()
+    }
+
+    /// Gets how far apart in host time the frames were received, i.e. the difference between the earliest and latest
+    /// [`captured_at()`](CaptureFrame::captured_at()). This is zero for less than two frames.
+    #[must_use]
+    pub fn skew(frames: &[CaptureFrame]) -> Duration {
+        let earliest = frames.iter().map(CaptureFrame::captured_at).min();
+        let latest = frames.iter().map(CaptureFrame::captured_at).max();
+        match (earliest, latest) {
+            (Some(earliest), Some(latest)) => latest.duration_since(earliest),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+type CameraCommand = Box<dyn FnOnce(&mut UVCCaptureDevice<'static>) + Send>;
+
+/// A handle to a [`UVCCaptureDevice`] that is owned by a thread of its own, for controlling it from any other thread (e.g. a
+/// settings dialog changing the resolution while another thread reads frames).
+///
+/// Every call is sent to the owning thread as a command and waits for its result, so calls from different threads are run
+/// one at a time, in the order they arrive. The handle is cheap to clone, and the thread stops (dropping the device, which
+/// stops its stream) once every clone is dropped.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+#[derive(Clone)]
+pub struct CameraHandle {
+    commands: Sender<CameraCommand>,
+}
+
+impl CameraHandle {
+    /// Creates the device like [`UVCCaptureDevice::create()`], on a new thread that owns it.
+    /// # Errors
+    /// If the device could not be created, this will error.
+    pub fn spawn(index: usize, cam_fmt: Option<CameraFormat>) -> Result<Self, NokhwaError> {
+        let (commands, command_receiver) = flume::unbounded::<CameraCommand>();
+        let (created_sender, created_receiver) = flume::bounded(1);
+        std::thread::spawn(move || {
+            let mut device = match UVCCaptureDevice::create(index, cam_fmt) {
+                Ok(device) => {
+                    let _ = created_sender.send(Ok(()));
+                    device
+                }
+                Err(why) => {
+                    let _ = created_sender.send(Err(why));
+                    return;
+                }
+            };
+            // ends once every handle, and with it every sender, is dropped
+            for command in command_receiver.iter() {
+                command(&mut device);
+            }
+        });
+
+        created_receiver.recv().map_err(|_| {
+            NokhwaError::OpenDeviceError(
+                index.to_string(),
+                "The camera thread panicked".to_string(),
+            )
+        })??;
+        Ok(CameraHandle { commands })
+    }
+
+    /// Runs `f` with the device on its owning thread and waits for the result, for anything the other methods do not cover.
+    /// # Errors
+    /// If the owning thread has exited (e.g. `f` or an earlier command panicked), this will error.
+    pub fn call<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut UVCCaptureDevice<'static>) -> R + Send + 'static,
+    ) -> Result<R, NokhwaError> {
+        let (result_sender, result_receiver) = flume::bounded(1);
+        let command: CameraCommand = Box::new(move |device| {
+            let _ = result_sender.send(f(device));
+        });
+        let thread_exited =
+            || NokhwaError::GeneralError("The camera thread has exited".to_string());
+        self.commands.send(command).map_err(|_| thread_exited())?;
+        result_receiver.recv().map_err(|_| thread_exited())
+    }
+
+    /// Gets the current [`CameraFormat`].
+    /// # Errors
+    /// If the owning thread has exited, this will error.
+    pub fn camera_format(&self) -> Result<CameraFormat, NokhwaError> {
+        self.call(|device| device.camera_format())
+    }
+
+    /// Sets the [`CameraFormat`], see [`set_camera_format()`](CaptureBackendTrait::set_camera_format()).
+    /// # Errors
+    /// If the format could not be set, or the owning thread has exited, this will error.
+    pub fn set_camera_format(&self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
+        self.call(move |device| device.set_camera_format(new_fmt))?
+    }
+
+    /// Gets the formats the camera supports, see [`compatible_camera_formats()`](CaptureBackendTrait::compatible_camera_formats()).
+    /// # Errors
+    /// If the formats could not be read, or the owning thread has exited, this will error.
+    pub fn compatible_camera_formats(&self) -> Result<Vec<CameraFormat>, NokhwaError> {
+        self.call(|device| device.compatible_camera_formats())?
+    }
+
+    /// Gets a control, see [`camera_control()`](CaptureBackendTrait::camera_control()).
+    /// # Errors
+    /// If the control could not be read, or the owning thread has exited, this will error.
+    pub fn camera_control(
+        &self,
+        control: KnownCameraControl,
+    ) -> Result<CameraControl, NokhwaError> {
+        self.call(move |device| device.camera_control(control))?
+    }
+
+    /// Sets a control, see [`set_camera_control()`](CaptureBackendTrait::set_camera_control()).
+    /// # Errors
+    /// If the control could not be set, or the owning thread has exited, this will error.
+    pub fn set_camera_control(
+        &self,
+        id: KnownCameraControl,
+        value: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        self.call(move |device| device.set_camera_control(id, value))?
+    }
+
+    /// Opens the stream, see [`open_stream()`](CaptureBackendTrait::open_stream()).
+    /// # Errors
+    /// If the stream could not be opened, or the owning thread has exited, this will error.
+    pub fn open_stream(&self) -> Result<(), NokhwaError> {
+        self.call(|device| device.open_stream())?
+    }
+
+    /// Gets whether the stream is open.
+    /// # Errors
+    /// If the owning thread has exited, this will error.
+    pub fn is_stream_open(&self) -> Result<bool, NokhwaError> {
+        self.call(|device| device.is_stream_open())
+    }
+
+    /// Gets the next frame, see [`capture_frame()`](UVCCaptureDevice::capture_frame()). The owning thread is busy until it
+    /// arrives, so other commands wait for it.
+    /// # Errors
+    /// If the frame could not be read, or the owning thread has exited, this will error.
+    pub fn capture_frame(&self) -> Result<CaptureFrame, NokhwaError> {
+        self.call(|device| device.capture_frame())?
+    }
+
+    /// Stops the stream, see [`stop_stream()`](CaptureBackendTrait::stop_stream()).
+    /// # Errors
+    /// If the stream could not be stopped, or the owning thread has exited, this will error.
+    pub fn stop_stream(&self) -> Result<(), NokhwaError> {
+        self.call(|device| device.stop_stream())?
+    }
+}
+
+fn uvc_frame_format(format: FrameFormat) -> uvc::FrameFormat {
+    match format {
+        FrameFormat::MJPEG => uvc::FrameFormat::MJPEG,
+        FrameFormat::YUYV => uvc::FrameFormat::YUYV,
+        FrameFormat::Bayer8(BayerPattern::Rggb) => uvc::FrameFormat::SRGGB8,
+        FrameFormat::Bayer8(BayerPattern::Bggr) => uvc::FrameFormat::SBGGR8,
+        FrameFormat::Bayer8(BayerPattern::Grbg) => uvc::FrameFormat::SGRBG8,
+        FrameFormat::Bayer8(BayerPattern::Gbrg) => uvc::FrameFormat::SGBRG8,
+        // libuvc has no packed Bayer formats, these are matched by their descriptor GUID
+        FrameFormat::Bayer10Packed(_) | FrameFormat::Bayer12Packed(_) => {
+            uvc::FrameFormat::Uncompressed
+        }
+        // libuvc only knows the GUIDs of its own formats, so a custom one is matched by its size
+        _ => uvc::FrameFormat::Any,
+    }
+}
+
+// `guidFormat` of YUY2 (`32595559-0000-0010-8000-00AA00389B71`), in descriptor byte order.
+const UVC_GUID_YUY2: [u8; 16] = [
+    0x59, 0x55, 0x59, 0x32, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+
+// Formats a GUID as it is usually written, `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`. The first three fields are little endian.
+fn uvc_guid_string(guid: [u8; 16]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:04X}-{:012X}",
+        u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]),
+        u16::from_le_bytes([guid[4], guid[5]]),
+        u16::from_le_bytes([guid[6], guid[7]]),
+        u16::from_be_bytes([guid[8], guid[9]]),
+        u64::from_be_bytes([0, 0, guid[10], guid[11], guid[12], guid[13], guid[14], guid[15]]),
+    )
+}
+
+// Uncompressed and frame based formats are told apart by the GUID of their format descriptor. The ones nokhwa
+// cannot decode are passed through as `FrameFormat::Custom`.
+fn uvc_descriptor_format(subtype: DescriptionSubtype, guid: [u8; 16]) -> Option<FrameFormat> {
+    match subtype {
+        DescriptionSubtype::FormatMJPEG | DescriptionSubtype::FrameMJPEG => {
+            Some(FrameFormat::MJPEG)
+        }
+        DescriptionSubtype::FormatUncompressed
+        | DescriptionSubtype::FrameUncompressed
+        | DescriptionSubtype::FormatFrameBased
+        | DescriptionSubtype::FrameFrameBased => {
+            if guid == UVC_GUID_YUY2 {
+                Some(FrameFormat::YUYV)
+            } else {
+                Some(FrameFormat::from_guid(guid))
+            }
+        }
+        _ => None,
+    }
+}
+
+// `libuvc` keeps the `dwPresentationTime` of the payload headers that made up the frame, `0` if there was none.
+fn uvc_device_timestamp(frame: &uvc::Frame) -> Option<u32> {
+    Some(frame.pts()).filter(|pts| *pts != 0)
+}
+
+// YUYV stores two pixels in each 4 byte macropixel, so an odd width cannot be represented.
+fn uvc_validate_format(camera_format: &CameraFormat) -> Result<(), NokhwaError> {
+    if camera_format.format() == FrameFormat::YUYV && camera_format.width() % 2 != 0 {
+        return Err(NokhwaError::SetPropertyError {
+            property: "CameraFormat".to_string(),
+            value: camera_format.to_string(),
+            error: "YUYV needs an even width, as every 4 bytes encode 2 pixels".to_string(),
+        });
+    }
+    Ok(())
+}
+
+// See `PartialFramePolicy`. Formats whose completeness cannot be told from the data are taken as complete.
+fn uvc_is_partial(format: Option<uvc::FrameFormat>, resolution: Resolution, data: &[u8]) -> bool {
+    match format {
+        Some(uvc::FrameFormat::YUYV) => uvc_check_yuyv_len(resolution, data.len()).is_err(),
+        Some(uvc::FrameFormat::MJPEG) => {
+            // some cameras pad the payload after the end of image marker with zeros
+            let end = data
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .map_or(0, |last| last + 1);
+            !data.starts_with(&[0xFF, 0xD8]) || !data[..end].ends_with(&[0xFF, 0xD9])
+        }
+        _ => false,
+    }
+}
+
+fn uvc_check_yuyv_len(resolution: Resolution, len: usize) -> Result<(), NokhwaError> {
+    let expected = resolution.width() as usize * resolution.height() as usize * 2;
+    if len != expected {
+        return Err(NokhwaError::ProcessFrameError {
+            src: FrameFormat::YUYV,
+            destination: "RGB".to_string(),
+            error: format!("Expected {expected} bytes for {resolution}, got {len}"),
+        });
+    }
+    Ok(())
+}
+
+// Builds an EXIF APP1 segment: a little endian TIFF structure whose IFD0 only points to an Exif IFD with the
+// DateTimeOriginal, UserComment, PixelXDimension and PixelYDimension tags. `None` if it does not fit in a segment.
+#[cfg(feature = "output-save")]
+fn exif_segment(
+    captured_at: std::time::SystemTime,
+    resolution: Resolution,
+    camera_info: &CameraInfo,
+) -> Option<Vec<u8>> {
+    const ASCII: u16 = 2;
+    const LONG: u16 = 4;
+    const UNDEFINED: u16 = 7;
+    const IFD0_OFFSET: u32 = 8;
+    const EXIF_IFD_OFFSET: u32 = IFD0_OFFSET + 2 + 12 + 4;
+    const EXIF_VALUES_OFFSET: u32 = EXIF_IFD_OFFSET + 2 + 4 * 12 + 4;
+
+    fn push_entry(tiff: &mut Vec<u8>, tag: u16, field_type: u16, count: u32, value: u32) {
+        tiff.extend_from_slice(&tag.to_le_bytes());
+        tiff.extend_from_slice(&field_type.to_le_bytes());
+        tiff.extend_from_slice(&count.to_le_bytes());
+        tiff.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let date_time = exif_date_time(captured_at)?;
+    let mut user_comment = b"ASCII\0\0\0".to_vec();
+    user_comment.extend_from_slice(
+        format!(
+            "{} ({}), {}",
+            camera_info.human_name(),
+            camera_info.misc(),
+            resolution
+        )
+        .as_bytes(),
+    );
+    let date_time_len = u32::try_from(date_time.len()).ok()?;
+    let user_comment_len = u32::try_from(user_comment.len()).ok()?;
+
+    let mut tiff = Vec::new();
+    tiff.extend_from_slice(b"II");
+    tiff.extend_from_slice(&42_u16.to_le_bytes());
+    tiff.extend_from_slice(&IFD0_OFFSET.to_le_bytes());
+    // IFD0
+    tiff.extend_from_slice(&1_u16.to_le_bytes());
+    push_entry(&mut tiff, 0x8769, LONG, 1, EXIF_IFD_OFFSET);
+    tiff.extend_from_slice(&0_u32.to_le_bytes());
+    // Exif IFD, the tags must be in ascending order
+    tiff.extend_from_slice(&4_u16.to_le_bytes());
+    push_entry(&mut tiff, 0x9003, ASCII, date_time_len, EXIF_VALUES_OFFSET);
+    push_entry(
+        &mut tiff,
+        0x9286,
+        UNDEFINED,
+        user_comment_len,
+        EXIF_VALUES_OFFSET + date_time_len,
+    );
+    push_entry(&mut tiff, 0xA002, LONG, 1, resolution.width());
+    push_entry(&mut tiff, 0xA003, LONG, 1, resolution.height());
+    tiff.extend_from_slice(&0_u32.to_le_bytes());
+    tiff.extend_from_slice(date_time.as_bytes());
+    tiff.extend_from_slice(&user_comment);
+
+    // the length counts itself and the "Exif" header, but not the marker
+    let length = u16::try_from(2 + 6 + tiff.len()).ok()?;
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&length.to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(&tiff);
+    Some(segment)
+}
+
+#[cfg(feature = "output-save")]
+fn uvc_encode_png(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<Vec<u8>, String> {
+    use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            ColorType::Rgb8,
+        )
+        .map_err(|why| why.to_string())?;
+    Ok(png)
+}
+
+// Formats `time` in UTC as an EXIF date, "YYYY:MM:DD HH:MM:SS" with its NUL terminator.
+// The date is from http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[cfg(feature = "output-save")]
+fn exif_date_time(time: std::time::SystemTime) -> Option<String> {
+    let secs = time.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    let secs_of_day = secs % 86_400;
+
+    let days = i64::try_from(secs / 86_400).ok()? + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    Some(format!(
+        "{:04}:{:02}:{:02} {:02}:{:02}:{:02}\0",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    ))
+}
+
+// Rec. 601 luma, averaged over the frame and scaled to `0.0..=1.0`.
+#[allow(clippy::cast_precision_loss)]
+fn mean_luma(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> f32 {
+    let pixels = u64::from(image.width()) * u64::from(image.height());
+    if pixels == 0 {
+        return 0.0;
+    }
+    let sum: u64 = image
+        .pixels()
+        .map(|Rgb([r, g, b])| 299 * u64::from(*r) + 587 * u64::from(*g) + 114 * u64::from(*b))
+        .sum();
+    (sum as f64 / (pixels as f64 * 1000.0 * 255.0)) as f32
+}
+
+fn uvc_camera_info(device: &Device, index: usize) -> Result<CameraInfo, NokhwaError> {
+    let device_desc = match device.description() {
+        Ok(desc) => desc,
+        Err(why) => {
+            return Err(NokhwaError::OpenDeviceError(
+                index.to_string(),
+                why.to_string(),
+            ))
+        }
+    };
+
+    let device_name = match (device_desc.manufacturer, device_desc.product) {
+        (Some(manu), Some(prod)) => {
+            format!("{} {}", manu, prod)
+        }
+        (_, Some(prod)) => prod,
+        (Some(manu), _) => {
+            format!(
+                "{}:{} {}",
+                device_desc.vendor_id, device_desc.product_id, manu
+            )
+        }
+        (_, _) => {
+            format!("{}:{}", device_desc.vendor_id, device_desc.product_id)
+        }
+    };
+
+    Ok(CameraInfo::new(
+        device_name,
+        "".to_string(),
+        format!("{}:{}", device_desc.vendor_id, device_desc.product_id),
+        index,
+    ))
+}
+
+// Some UVC-class devices (e.g. audio or oddly registered composite interfaces) have no video streaming interface, so they
+// have no formats. If the device cannot be opened to check (e.g. no permission), it is assumed to be a camera, so opening it
+// reports the real problem.
+pub(crate) fn uvc_is_camera(device: &Device) -> bool {
+    device
+        .open()
+        .map_or(true, |devh| devh.supported_formats().next().is_some())
+}
+
+fn uvc_frame_intervals(
+    devh: &DeviceHandle,
+    fourcc: FrameFormat,
+) -> HashMap<Resolution, FrameIntervals> {
+    let mut resolution_interval_map: HashMap<Resolution, FrameIntervals> = HashMap::new();
+    for fmt in devh.supported_formats() {
+        for frame_desc in fmt.supported_formats() {
+            let format = match uvc_descriptor_format(frame_desc.subtype(), fmt.guid_format()) {
+                Some(format) => format,
+                None => continue,
+            };
+
+            if format != fourcc {
+                continue;
+            }
+
+            let resolution = Resolution::new(frame_desc.width().into(), frame_desc.height().into());
+            // `bFrameIntervalType == 0` means the descriptor has a continuous range instead of a list
+            let intervals = if frame_desc.intervals().is_empty() {
+                FrameIntervals::Continuous {
+                    min: frame_desc.min_frame_interval(),
+                    max: frame_desc.max_frame_interval(),
+                    step: frame_desc.frame_interval_step(),
+                }
+            } else {
+                FrameIntervals::Discrete(frame_desc.intervals().to_vec())
+            };
+            resolution_interval_map.insert(resolution, intervals);
+        }
+    }
+    resolution_interval_map
+}
+
+// `libuvc` picks the descriptor interval for which `10_000_000 / interval == fps`, so rounding the requested frame rate
+// mis-negotiates rates like 29.97 FPS. Match the closest descriptor interval instead and hand `libuvc` its own key.
+fn uvc_stream_fps(devh: &DeviceHandle, camera_format: &CameraFormat) -> u32 {
+    uvc_closest_interval(devh, camera_format).map_or_else(
+        || camera_format.frame_rate().as_u32(),
+        |interval| FRAME_INTERVAL_UNITS_PER_SECOND / interval,
+    )
+}
+
+fn uvc_closest_interval(devh: &DeviceHandle, camera_format: &CameraFormat) -> Option<u32> {
+    uvc_frame_intervals(devh, camera_format.format())
+        .remove(&camera_format.resolution())?
+        .closest(camera_format.frame_interval())
+}
+
+// The format the camera actually streams for a requested `camera_format`, with the frame interval it was negotiated at.
+// Frame rates like 29.97 keep two decimals, whole ones none.
+fn uvc_fps_summary(fps: f32) -> f32 {
+    (fps * 100.0).round() / 100.0
+}
+
+fn uvc_negotiated_format(devh: &DeviceHandle, camera_format: &CameraFormat) -> CameraFormat {
+    let mut negotiated = *camera_format;
+    if let Some(interval) = uvc_closest_interval(devh, camera_format) {
+        negotiated.set_frame_rate(FrameRate::new_interval(interval));
+    }
+    negotiated
+}
@@ -44,81 +44,97 @@ impl Camera {
     pub fn with_api_backend() -> Result<Self, NokhwaError> {}
 
     pub fn with_custom_backend() -> Result<Self, NokhwaError> {}
+
+    /// Gets the [`CameraInfo`] of the camera.
+    #[must_use]
+    pub fn info(&self) -> &CameraInfo {
+        self.device.camera_info()
+    }
+
+    // Wraps an already created device, e.g. a mock backend in tests.
+    #[cfg(test)]
+    pub(crate) fn from_device(device: Box<dyn CaptureTrait + Backend>) -> Self {
+        Camera {
+            idx: device.camera_info().index().clone(),
+            api: device.backend(),
+            device,
+        }
+    }
 }
 
 impl CaptureTrait for Camera {
     fn init(&mut self) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.init()
     }
 
     fn init_with_format(&mut self, format: FormatFilter) -> Result<CameraFormat, NokhwaError> {
-        todo!()
+        self.device.init_with_format(format)
     }
 
     fn backend(&self) -> ApiBackend {
-        todo!()
+        self.device.backend()
     }
 
     fn camera_info(&self) -> &CameraInfo {
-        todo!()
+        self.device.camera_info()
     }
 
     fn refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.refresh_camera_format()
     }
 
     fn camera_format(&self) -> Option<CameraFormat> {
-        todo!()
+        self.device.camera_format()
     }
 
     fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.set_camera_format(new_fmt)
     }
 
     fn compatible_list_by_resolution(
         &mut self,
         fourcc: SourceFrameFormat,
     ) -> Result<HashMap<Resolution, Vec<u32>>, NokhwaError> {
-        todo!()
+        self.device.compatible_list_by_resolution(fourcc)
     }
 
     fn compatible_fourcc(&mut self) -> Result<Vec<SourceFrameFormat>, NokhwaError> {
-        todo!()
+        self.device.compatible_fourcc()
     }
 
     fn resolution(&self) -> Option<Resolution> {
-        todo!()
+        self.device.resolution()
     }
 
     fn set_resolution(&mut self, new_res: Resolution) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.set_resolution(new_res)
     }
 
     fn frame_rate(&self) -> Option<u32> {
-        todo!()
+        self.device.frame_rate()
     }
 
     fn set_frame_rate(&mut self, new_fps: u32) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.set_frame_rate(new_fps)
     }
 
     fn frame_format(&self) -> SourceFrameFormat {
-        todo!()
+        self.device.frame_format()
     }
 
     fn set_frame_format(
         &mut self,
         fourcc: impl Into<SourceFrameFormat>,
     ) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.set_frame_format(fourcc.into())
     }
 
     fn camera_control(&self, control: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
-        todo!()
+        self.device.camera_control(control)
     }
 
     fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
-        todo!()
+        self.device.camera_controls()
     }
 
     fn set_camera_control(
@@ -126,27 +142,31 @@ impl CaptureTrait for Camera {
         id: KnownCameraControl,
         value: ControlValueSetter,
     ) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.set_camera_control(id, value)
     }
 
     fn open_stream(&mut self) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.open_stream()
     }
 
     fn is_stream_open(&self) -> bool {
-        todo!()
+        self.device.is_stream_open()
     }
 
     fn frame(&mut self) -> Result<Buffer, NokhwaError> {
-        todo!()
+        self.device.frame()
     }
 
     fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
-        todo!()
+        self.device.frame_raw()
     }
 
     fn stop_stream(&mut self) -> Result<(), NokhwaError> {
-        todo!()
+        self.device.stop_stream()
+    }
+
+    fn restart_stream(&mut self) -> Result<(), NokhwaError> {
+        self.device.restart_stream()
     }
 }
 
@@ -157,3 +177,42 @@ impl Drop for Camera {
 }
 
 unsafe impl Send for Camera {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockCamera;
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    #[test]
+    fn restart_stream_does_not_leak_streams() {
+        let (mut camera, stats) = MockCamera::camera(Duration::ZERO);
+        camera.open_stream().unwrap();
+        for _ in 0..20 {
+            camera.restart_stream().unwrap();
+            assert!(camera.is_stream_open());
+            assert_eq!(stats.open_streams.load(Ordering::SeqCst), 1);
+            camera.frame().unwrap();
+        }
+        drop(camera);
+        assert_eq!(stats.open_streams.load(Ordering::SeqCst), 0);
+        assert_eq!(stats.devices.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn failed_restart_leaves_the_stream_closed() {
+        let (mut camera, stats) = MockCamera::camera(Duration::ZERO);
+        camera.open_stream().unwrap();
+        for _ in 0..5 {
+            stats.failing_opens.store(1, Ordering::SeqCst);
+            assert!(camera.restart_stream().is_err());
+            assert!(!camera.is_stream_open());
+            assert_eq!(stats.open_streams.load(Ordering::SeqCst), 0);
+            assert!(camera.frame().is_err());
+
+            camera.restart_stream().unwrap();
+            assert_eq!(stats.open_streams.load(Ordering::SeqCst), 1);
+            camera.frame().unwrap();
+        }
+    }
+}
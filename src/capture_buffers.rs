@@ -0,0 +1,941 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The frame queueing of the UVC backend: buffer pooling, the ring buffer, reordering by device timestamp, backpressure
+// and the timing of injected frames.
+// None of it touches `libuvc`, so it is built and tested without the `input-uvc` feature.
+
+use crate::BackpressurePolicy;
+use flume::{Receiver, SendTimeoutError, Sender, TrySendError};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Formatter},
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+/// The data of a [`CaptureFrame`], in a buffer from the device's frame buffer pool. Dropping it returns the buffer to the pool
+/// for the next frame, unless the pool was flushed since (the stream was reopened) or is full.
+///
+/// It dereferences to the frame data. See [`UVCCaptureDevice::buffer_pool_stats()`](crate::backends::capture::UVCCaptureDevice::buffer_pool_stats()).
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct PooledBuffer {
+    data: Vec<u8>,
+    pool: Weak<BufferPool>,
+    generation: u64,
+}
+
+impl PooledBuffer {
+    // A buffer that does not go back to a pool, e.g. for injected frames.
+    pub(crate) fn unpooled(data: Vec<u8>) -> Self {
+        PooledBuffer {
+            data,
+            pool: Weak::new(),
+            generation: 0,
+        }
+    }
+
+    /// Takes the data out of the buffer. The buffer does not go back to the pool then.
+    #[must_use]
+    pub fn into_vec(mut self) -> Vec<u8> {
+        self.pool = Weak::new();
+        std::mem::take(&mut self.data)
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl Clone for PooledBuffer {
+    // a copy is not pooled, so clones (e.g. kept in a ring buffer) cannot drain the pool
+    fn clone(&self) -> Self {
+        PooledBuffer::unpooled(self.data.clone())
+    }
+}
+
+impl Debug for PooledBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+impl PartialEq for PooledBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl Eq for PooledBuffer {}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.upgrade() {
+            pool.give_back(std::mem::take(&mut self.data), self.generation);
+        }
+    }
+}
+
+/// How well the frame buffer pool of a [`UVCCaptureDevice`](crate::backends::capture::UVCCaptureDevice) fits the stream, see
+/// [`UVCCaptureDevice::buffer_pool_stats()`](crate::backends::capture::UVCCaptureDevice::buffer_pool_stats())(crate::backends::capture::UVCCaptureDevice::buffer_pool_stats()).
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct BufferPoolStats {
+    /// The number of buffers the pool holds, chosen when the stream was opened.
+    pub buffers: usize,
+    /// The number of frames written into a recycled buffer.
+    pub hits: u64,
+    /// The number of frames that found the pool empty, and were written into a newly allocated buffer.
+    pub misses: u64,
+}
+
+/// A frame as it was queued by the `libuvc` frame callback, along with when it was captured.
+///
+/// The data is RGB or the camera's own format, depending on the [`DecodeLocation`](crate::backends::capture::DecodeLocation).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct CaptureFrame {
+    pub(crate) data: PooledBuffer,
+    pub(crate) captured_at: Instant,
+    pub(crate) device_timestamp: Option<u32>,
+    pub(crate) partial: bool,
+}
+
+impl CaptureFrame {
+    /// Gets the frame data.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Takes the frame data. Its buffer does not go back to the pool then, see [`into_buffer()`](CaptureFrame::into_buffer).
+    #[must_use]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data.into_vec()
+    }
+
+    /// Takes the frame data in its [`PooledBuffer`], which goes back to the pool once dropped.
+    #[must_use]
+    pub fn into_buffer(self) -> PooledBuffer {
+        self.data
+    }
+
+    /// Gets the host time the frame callback received the frame at.
+    #[must_use]
+    pub fn captured_at(&self) -> Instant {
+        self.captured_at
+    }
+
+    /// Gets the presentation timestamp (`dwPresentationTime`) the camera put in the frame's UVC payload header.
+    ///
+    /// This is in ticks of the camera's own clock, which runs at the `dwClockFrequency` of its video control interface
+    /// (commonly 48 MHz for USB 2.0 cameras), and wraps around at [`u32::MAX`]. Compare the difference between two frames'
+    /// device timestamps against the difference between their [`captured_at()`](CaptureFrame::captured_at) to correct for
+    /// USB buffering jitter or drift between cameras.
+    ///
+    /// This is `None` if the camera does not send presentation timestamps, which is optional in UVC.
+    #[must_use]
+    pub fn device_timestamp(&self) -> Option<u32> {
+        self.device_timestamp
+    }
+
+    /// Whether the frame arrived incomplete, e.g. cut short under heavy USB load. These are only queued with
+    /// [`PartialFramePolicy::Deliver`](crate::backends::capture::PartialFramePolicy::Deliver).
+    #[must_use]
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+}
+
+/// What a [`FrameRingBuffer`] keeps of each frame.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub enum RingBufferStorage {
+    /// The data as the camera sent it (e.g. MJPEG). This is the default, and takes the least memory.
+    #[default]
+    Raw,
+    /// RGB data. This takes `width * height * 3` bytes per frame, but the frames can be used right away.
+    Decoded,
+}
+
+/// The frames of the last `window` of a stream, for getting at what happened just before an event
+/// (e.g. "save the last 5 seconds").
+///
+/// Enable it with [`UVCCaptureDevice::set_ring_buffer()`](crate::backends::capture::UVCCaptureDevice::set_ring_buffer()), and the frame callback will feed it every frame, including the ones
+/// [`frame()`](CaptureBackendTrait::frame()) skips.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-uvc")))]
+pub struct FrameRingBuffer {
+    window: Duration,
+    storage: RingBufferStorage,
+    frames: VecDeque<CaptureFrame>,
+}
+
+impl FrameRingBuffer {
+    /// Creates an empty [`FrameRingBuffer`] that keeps frames for `window`.
+    #[must_use]
+    pub fn new(window: Duration, storage: RingBufferStorage) -> Self {
+        FrameRingBuffer {
+            window,
+            storage,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Gets how long frames are kept for.
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Gets what is kept of each frame.
+    #[must_use]
+    pub fn storage(&self) -> RingBufferStorage {
+        self.storage
+    }
+
+    /// Gets the number of frames held.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames are held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Adds a frame, dropping the ones that are more than [`window()`](FrameRingBuffer::window) older than it.
+    pub fn push(&mut self, frame: CaptureFrame) {
+        let newest = frame.captured_at;
+        self.frames.push_back(frame);
+        while let Some(oldest) = self.frames.front() {
+            if newest.saturating_duration_since(oldest.captured_at) <= self.window {
+                break;
+            }
+            self.frames.pop_front();
+        }
+    }
+
+    /// Gets copies of the frames captured at or after `since`, oldest first.
+    #[must_use]
+    pub fn frames_since(&self, since: Instant) -> Vec<CaptureFrame> {
+        self.frames
+            .iter()
+            .filter(|frame| frame.captured_at >= since)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes and returns all frames, oldest first.
+    pub fn drain(&mut self) -> Vec<CaptureFrame> {
+        self.frames.drain(..).collect()
+    }
+
+    /// Removes all frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+// Holds frames back for up to `window` to release them in device timestamp order, see
+// `UVCCaptureDevice::set_reorder_window()`.
+#[derive(Debug)]
+pub(crate) struct FrameReorderBuffer {
+    window: Duration,
+    // sorted by device timestamp, the first `ready` frames are due to be queued
+    pending: VecDeque<CaptureFrame>,
+    ready: usize,
+    last_released: Option<u32>,
+}
+
+impl FrameReorderBuffer {
+    pub(crate) fn new(window: Duration) -> Self {
+        FrameReorderBuffer {
+            window,
+            pending: VecDeque::new(),
+            ready: 0,
+            last_released: None,
+        }
+    }
+
+    // Adds `frame`, or drops and counts it if a newer frame was already released.
+    pub(crate) fn push(&mut self, frame: CaptureFrame, late_frame_count: &AtomicUsize) {
+        let timestamp = match frame.device_timestamp {
+            Some(timestamp) => timestamp,
+            // there is nothing to order on, so everything before it goes out with it
+            None => {
+                self.pending.push_back(frame);
+                self.release(self.pending.len());
+                return;
+            }
+        };
+        if let Some(last_released) = self.last_released {
+            if !timestamp_before(last_released, timestamp) {
+                late_frame_count.fetch_add(1, Ordering::SeqCst);
+                return;
+            }
+        }
+        let position = self.ready
+            + self
+                .pending
+                .range(self.ready..)
+                .take_while(|pending| {
+                    pending
+                        .device_timestamp
+                        .map_or(true, |pending| !timestamp_before(timestamp, pending))
+                })
+                .count();
+        self.pending.insert(position, frame);
+
+        // a frame that waited out the window releases every frame before it too, so the output stays in order
+        let now = Instant::now();
+        if let Some(expired) = self
+            .pending
+            .iter()
+            .rposition(|pending| now.saturating_duration_since(pending.captured_at) >= self.window)
+        {
+            self.release(expired + 1);
+        }
+    }
+
+    fn release(&mut self, count: usize) {
+        self.ready = self.ready.max(count);
+        if let Some(timestamp) = self
+            .pending
+            .range(..self.ready)
+            .rev()
+            .find_map(|released| released.device_timestamp)
+        {
+            self.last_released = Some(timestamp);
+        }
+    }
+
+    pub(crate) fn pop_ready(&mut self) -> Option<CaptureFrame> {
+        if self.ready == 0 {
+            return None;
+        }
+        self.ready -= 1;
+        self.pending.pop_front()
+    }
+
+    // The device clock may start over with a new stream.
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.ready = 0;
+        self.last_released = None;
+    }
+}
+
+// Shared with the frame callback, which does the counting.
+#[derive(Default)]
+pub(crate) struct BackpressureCounters {
+    pub(crate) dropped_frames: AtomicU64,
+    pub(crate) blocked_nanos: AtomicU64,
+    pub(crate) overflowed: AtomicBool,
+    // set while the stream is being torn down, so a blocked callback gives up instead of deadlocking
+    pub(crate) stopping: AtomicBool,
+}
+
+// The buffers the frame callback copies frames into, see `PooledBuffer`.
+#[derive(Debug, Default)]
+pub(crate) struct BufferPool {
+    state: Mutex<BufferPoolState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct BufferPoolState {
+    buffers: Vec<Vec<u8>>,
+    count: usize,
+    // bumped by every reset, so buffers of an older stream are dropped instead of returned
+    generation: u64,
+}
+
+impl BufferPool {
+    // Replaces the pooled buffers with `count` buffers of `capacity` bytes.
+    pub(crate) fn reset(&self, count: usize, capacity: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            state.generation = state.generation.wrapping_add(1);
+            state.count = count;
+            state.buffers = (0..count).map(|_| Vec::with_capacity(capacity)).collect();
+        }
+    }
+
+    // Copies `data` into a recycled buffer, or a new one if the pool is empty.
+    pub(crate) fn copy_from(self: &Arc<Self>, data: &[u8]) -> PooledBuffer {
+        let (recycled, generation) = match self.state.lock() {
+            Ok(mut state) => (state.buffers.pop(), state.generation),
+            Err(_) => (None, 0),
+        };
+        let mut buffer = match recycled {
+            Some(buffer) => {
+                self.hits.fetch_add(1, Ordering::SeqCst);
+                buffer
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::SeqCst);
+                Vec::with_capacity(data.len())
+            }
+        };
+        buffer.clear();
+        buffer.extend_from_slice(data);
+        PooledBuffer {
+            data: buffer,
+            pool: Arc::downgrade(self),
+            generation,
+        }
+    }
+
+    fn give_back(&self, buffer: Vec<u8>, generation: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.generation == generation && state.buffers.len() < state.count {
+                state.buffers.push(buffer);
+            }
+        }
+    }
+
+    pub(crate) fn stats(&self) -> BufferPoolStats {
+        BufferPoolStats {
+            buffers: self.state.lock().map_or(0, |state| state.count),
+            hits: self.hits.load(Ordering::SeqCst),
+            misses: self.misses.load(Ordering::SeqCst),
+        }
+    }
+}
+
+// Passes `frame` through the reorder buffer if there is one, returning the frames that are due to be queued, in order.
+// The lock is not held while queueing, as that may block on a full queue.
+pub(crate) fn reorder_frame(
+    reorder_buffer: &Mutex<Option<FrameReorderBuffer>>,
+    frame: CaptureFrame,
+    late_frame_count: &AtomicUsize,
+) -> Vec<CaptureFrame> {
+    let mut reorder_buffer = match reorder_buffer.lock() {
+        Ok(reorder_buffer) => reorder_buffer,
+        Err(_) => return vec![frame],
+    };
+    match reorder_buffer.as_mut() {
+        Some(reorder_buffer) => {
+            reorder_buffer.push(frame, late_frame_count);
+            std::iter::from_fn(|| reorder_buffer.pop_ready()).collect()
+        }
+        None => vec![frame],
+    }
+}
+
+// Whether device timestamp `a` is before `b`, allowing for the clock wrapping around.
+#[allow(clippy::cast_possible_wrap)]
+fn timestamp_before(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) < 0
+}
+
+// Queues `frame` as `policy` says if the queue is full. Errors if the receiving end is gone.
+pub(crate) fn queue_frame(
+    sender: &Sender<CaptureFrame>,
+    receiver: &Weak<Receiver<CaptureFrame>>,
+    frame: CaptureFrame,
+    policy: BackpressurePolicy,
+    counters: &BackpressureCounters,
+) -> Result<(), ()> {
+    let mut frame = match sender.try_send(frame) {
+        Ok(()) => return Ok(()),
+        Err(TrySendError::Disconnected(_)) => return Err(()),
+        Err(TrySendError::Full(frame)) => frame,
+    };
+
+    match policy {
+        BackpressurePolicy::DropOldest => {
+            let receiver = receiver.upgrade().ok_or(())?;
+            // the consumer may have made room in the meantime
+            if receiver.try_recv().is_ok() {
+                counters.dropped_frames.fetch_add(1, Ordering::SeqCst);
+            }
+            if sender.try_send(frame).is_err() {
+                counters.dropped_frames.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        BackpressurePolicy::DropNewest => {
+            counters.dropped_frames.fetch_add(1, Ordering::SeqCst);
+        }
+        BackpressurePolicy::Error => {
+            counters.dropped_frames.fetch_add(1, Ordering::SeqCst);
+            counters.overflowed.store(true, Ordering::SeqCst);
+        }
+        BackpressurePolicy::Block => {
+            let blocked_at = Instant::now();
+            loop {
+                if counters.stopping.load(Ordering::SeqCst) {
+                    counters.dropped_frames.fetch_add(1, Ordering::SeqCst);
+                    break;
+                }
+                frame = match sender.send_timeout(frame, Duration::from_millis(10)) {
+                    Ok(()) => break,
+                    Err(SendTimeoutError::Disconnected(_)) => return Err(()),
+                    Err(SendTimeoutError::Timeout(frame)) => frame,
+                };
+            }
+            let blocked_nanos = u64::try_from(blocked_at.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            counters
+                .blocked_nanos
+                .fetch_add(blocked_nanos, Ordering::SeqCst);
+        }
+    }
+    Ok(())
+}
+
+/// When [`inject_frame()`](crate::backends::capture::UVCCaptureDevice::inject_frame()) delivers frames, see [`set_frame_timing()`](crate::backends::capture::UVCCaptureDevice::set_frame_timing()).
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimingProfile {
+    /// The time between frames.
+    pub interval: Duration,
+    /// How far each frame may be early or late. The offsets come from a fixed seed, so they are the same in every run.
+    pub jitter: Duration,
+    /// Drops every `n`th frame, e.g. `Some(10)` drops the 10th, 20th, 30th... frame.
+    pub drop_every: Option<u64>,
+}
+
+// The schedule of injected frames, counted from when the `TimingProfile` was set.
+pub(crate) struct InjectionClock {
+    profile: TimingProfile,
+    started_at: Instant,
+    frames: u64,
+    // xorshift64 state for the jitter
+    rng: u64,
+}
+
+impl InjectionClock {
+    pub(crate) fn new(profile: TimingProfile) -> Self {
+        InjectionClock {
+            profile,
+            started_at: Instant::now(),
+            frames: 0,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    // When the next frame is due, or `None` if it is dropped. A dropped frame still takes its slot.
+    pub(crate) fn next_frame(&mut self) -> Option<Instant> {
+        let index = self.frames;
+        self.frames += 1;
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+
+        if let Some(n) = self.profile.drop_every {
+            if n != 0 && self.frames % n == 0 {
+                return None;
+            }
+        }
+
+        let interval = u64::try_from(self.profile.interval.as_nanos()).unwrap_or(u64::MAX);
+        let jitter = u64::try_from(self.profile.jitter.as_nanos()).unwrap_or(u64::MAX);
+        let offset = self.rng % jitter.saturating_mul(2).saturating_add(1);
+        let due = interval.saturating_mul(index).saturating_add(offset);
+        let due = self.started_at + Duration::from_nanos(due);
+        Some(
+            due.checked_sub(Duration::from_nanos(jitter))
+                .map_or(self.started_at, |due| due.max(self.started_at)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(10);
+
+    fn frame(device_timestamp: Option<u32>, age: Duration) -> CaptureFrame {
+        CaptureFrame {
+            data: PooledBuffer::unpooled(Vec::new()),
+            captured_at: Instant::now() - age,
+            device_timestamp,
+            partial: false,
+        }
+    }
+
+    fn timestamps(frames: &[CaptureFrame]) -> Vec<Option<u32>> {
+        frames.iter().map(|frame| frame.device_timestamp).collect()
+    }
+
+    #[test]
+    fn reorder_passes_through_without_window() {
+        let reorder_buffer = Mutex::new(None);
+        let late_frame_count = AtomicUsize::new(0);
+
+        for timestamp in [3, 1, 2] {
+            let released = reorder_frame(
+                &reorder_buffer,
+                frame(Some(timestamp), Duration::ZERO),
+                &late_frame_count,
+            );
+            assert_eq!(timestamps(&released), [Some(timestamp)]);
+        }
+        assert_eq!(late_frame_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn reorder_releases_in_timestamp_order() {
+        let reorder_buffer = Mutex::new(Some(FrameReorderBuffer::new(WINDOW)));
+        let late_frame_count = AtomicUsize::new(0);
+
+        for timestamp in [30, 10, 20] {
+            let released = reorder_frame(
+                &reorder_buffer,
+                frame(Some(timestamp), Duration::ZERO),
+                &late_frame_count,
+            );
+            assert!(released.is_empty());
+        }
+
+        // a frame older than the window releases itself and everything before it
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(Some(40), WINDOW * 2),
+            &late_frame_count,
+        );
+        assert_eq!(
+            timestamps(&released),
+            [Some(10), Some(20), Some(30), Some(40)]
+        );
+
+        // anything at or before the last released frame is too late to be put in order
+        for timestamp in [25, 40] {
+            let released = reorder_frame(
+                &reorder_buffer,
+                frame(Some(timestamp), WINDOW * 2),
+                &late_frame_count,
+            );
+            assert!(released.is_empty());
+        }
+        assert_eq!(late_frame_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn reorder_keeps_newer_frames_back() {
+        let reorder_buffer = Mutex::new(Some(FrameReorderBuffer::new(WINDOW)));
+        let late_frame_count = AtomicUsize::new(0);
+
+        reorder_frame(
+            &reorder_buffer,
+            frame(Some(50), Duration::ZERO),
+            &late_frame_count,
+        );
+        // only the frames up to the expired one go out, the newer one keeps waiting
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(Some(40), WINDOW * 2),
+            &late_frame_count,
+        );
+        assert_eq!(timestamps(&released), [Some(40)]);
+
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(Some(60), WINDOW * 2),
+            &late_frame_count,
+        );
+        assert_eq!(timestamps(&released), [Some(50), Some(60)]);
+        assert_eq!(late_frame_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn reorder_flushes_on_frame_without_timestamp() {
+        let reorder_buffer = Mutex::new(Some(FrameReorderBuffer::new(WINDOW)));
+        let late_frame_count = AtomicUsize::new(0);
+
+        for timestamp in [5, 3] {
+            reorder_frame(
+                &reorder_buffer,
+                frame(Some(timestamp), Duration::ZERO),
+                &late_frame_count,
+            );
+        }
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(None, Duration::ZERO),
+            &late_frame_count,
+        );
+        assert_eq!(timestamps(&released), [Some(3), Some(5), None]);
+    }
+
+    #[test]
+    fn reorder_handles_clock_wrap() {
+        let reorder_buffer = Mutex::new(Some(FrameReorderBuffer::new(WINDOW)));
+        let late_frame_count = AtomicUsize::new(0);
+
+        reorder_frame(
+            &reorder_buffer,
+            frame(Some(1), Duration::ZERO),
+            &late_frame_count,
+        );
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(Some(u32::MAX), WINDOW * 2),
+            &late_frame_count,
+        );
+        // `u32::MAX` is just before the clock wrapped to 1, so it goes out first and 1 waits
+        assert_eq!(timestamps(&released), [Some(u32::MAX)]);
+
+        let released = reorder_frame(
+            &reorder_buffer,
+            frame(Some(2), WINDOW * 2),
+            &late_frame_count,
+        );
+        assert_eq!(timestamps(&released), [Some(1), Some(2)]);
+        assert!(timestamp_before(u32::MAX, 0));
+        assert!(!timestamp_before(0, u32::MAX));
+    }
+
+    #[test]
+    fn ring_buffer_keeps_window() {
+        let mut ring_buffer = FrameRingBuffer::new(WINDOW, RingBufferStorage::Raw);
+        assert!(ring_buffer.is_empty());
+
+        ring_buffer.push(frame(Some(1), WINDOW * 3));
+        ring_buffer.push(frame(Some(2), WINDOW * 3 / 4));
+        ring_buffer.push(frame(Some(3), WINDOW / 4));
+        ring_buffer.push(frame(Some(4), Duration::ZERO));
+        // only the first frame is more than the window older than the newest one
+        assert_eq!(ring_buffer.len(), 3);
+
+        let since = Instant::now() - WINDOW / 2;
+        assert_eq!(
+            timestamps(&ring_buffer.frames_since(since)),
+            [Some(3), Some(4)]
+        );
+        assert_eq!(ring_buffer.len(), 3);
+
+        assert_eq!(
+            timestamps(&ring_buffer.drain()),
+            [Some(2), Some(3), Some(4)]
+        );
+        assert!(ring_buffer.is_empty());
+    }
+
+    #[test]
+    fn buffer_pool_recycles_buffers() {
+        let pool = Arc::new(BufferPool::default());
+        pool.reset(1, 16);
+
+        let first = pool.copy_from(&[1, 2, 3]);
+        assert_eq!(&*first, [1, 2, 3]);
+        // the only buffer is taken, so this one is allocated
+        let second = pool.copy_from(&[4]);
+        drop(first);
+        drop(second);
+        let third = pool.copy_from(&[5, 6]);
+        assert_eq!(&*third, [5, 6]);
+
+        assert_eq!(
+            pool.stats(),
+            BufferPoolStats {
+                buffers: 1,
+                hits: 2,
+                misses: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn buffer_pool_drops_buffers_of_older_stream() {
+        let pool = Arc::new(BufferPool::default());
+        pool.reset(1, 16);
+        let old = pool.copy_from(&[1]);
+        pool.reset(1, 16);
+        let _new = pool.copy_from(&[2]);
+        drop(old);
+
+        // the old buffer did not go back, so the pool is still empty
+        pool.copy_from(&[3]);
+        assert_eq!(pool.stats().hits, 2);
+        assert_eq!(pool.stats().misses, 1);
+    }
+
+    #[test]
+    fn buffer_pool_keeps_taken_and_cloned_buffers() {
+        let pool = Arc::new(BufferPool::default());
+        pool.reset(1, 16);
+        let buffer = pool.copy_from(&[1, 2]);
+        let copy = buffer.clone();
+        assert_eq!(buffer, copy);
+        assert_eq!(buffer.into_vec(), [1, 2]);
+        drop(copy);
+
+        // neither the taken buffer nor the clone went back
+        pool.copy_from(&[3]);
+        assert_eq!(pool.stats().misses, 1);
+    }
+
+    const POLICIES: [BackpressurePolicy; 4] = [
+        BackpressurePolicy::DropOldest,
+        BackpressurePolicy::DropNewest,
+        BackpressurePolicy::Block,
+        BackpressurePolicy::Error,
+    ];
+
+    #[test]
+    fn queue_frame_delivers_while_receiver_lives() {
+        for policy in POLICIES {
+            let (sender, receiver) = flume::bounded(1);
+            let receiver = Arc::new(receiver);
+            let counters = BackpressureCounters::default();
+
+            let queued = queue_frame(
+                &sender,
+                &Arc::downgrade(&receiver),
+                frame(Some(1), Duration::ZERO),
+                policy,
+                &counters,
+            );
+            assert_eq!(queued, Ok(()));
+            assert!(!sender.is_disconnected());
+            assert_eq!(
+                receiver.try_recv().map(|frame| frame.device_timestamp),
+                Ok(Some(1))
+            );
+        }
+    }
+
+    #[test]
+    fn queue_frame_errors_once_receiver_is_dropped() {
+        for policy in POLICIES {
+            let (sender, receiver) = flume::bounded(1);
+            let receiver = Arc::new(receiver);
+            let weak_receiver = Arc::downgrade(&receiver);
+            let counters = BackpressureCounters::default();
+            drop(receiver);
+
+            let queued = queue_frame(
+                &sender,
+                &weak_receiver,
+                frame(Some(1), Duration::ZERO),
+                policy,
+                &counters,
+            );
+            assert_eq!(queued, Err(()), "{policy:?}");
+            // what the frame callback checks before decoding
+            assert!(sender.is_disconnected());
+            assert_eq!(counters.dropped_frames.load(Ordering::SeqCst), 0);
+        }
+    }
+
+    #[test]
+    fn queue_frame_errors_when_receiver_is_dropped_while_full() {
+        for policy in POLICIES {
+            let (sender, receiver) = flume::bounded(1);
+            let receiver = Arc::new(receiver);
+            let weak_receiver = Arc::downgrade(&receiver);
+            let counters = BackpressureCounters::default();
+            sender.send(frame(Some(1), Duration::ZERO)).unwrap();
+            drop(receiver);
+
+            let queued = queue_frame(
+                &sender,
+                &weak_receiver,
+                frame(Some(2), Duration::ZERO),
+                policy,
+                &counters,
+            );
+            assert_eq!(queued, Err(()), "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn injection_clock_keeps_interval() {
+        let interval = Duration::from_millis(40);
+        let mut clock = InjectionClock::new(TimingProfile {
+            interval,
+            ..TimingProfile::default()
+        });
+        let started_at = clock.started_at;
+
+        for index in 0..4 {
+            assert_eq!(clock.next_frame(), Some(started_at + interval * index));
+        }
+    }
+
+    #[test]
+    fn injection_clock_jitters_within_bounds() {
+        let profile = TimingProfile {
+            interval: Duration::from_millis(40),
+            jitter: Duration::from_millis(5),
+            drop_every: None,
+        };
+        let mut clock = InjectionClock::new(profile);
+        let started_at = clock.started_at;
+
+        for index in 0..100 {
+            let due = clock.next_frame().unwrap();
+            let expected = started_at + profile.interval * index;
+            // the first frame cannot be early, as it would be due before the clock started
+            assert!(due >= started_at && due + profile.jitter >= expected);
+            assert!(due <= expected + profile.jitter);
+        }
+    }
+
+    #[test]
+    fn injection_clock_is_deterministic() {
+        let profile = TimingProfile {
+            interval: Duration::from_millis(40),
+            jitter: Duration::from_millis(5),
+            drop_every: None,
+        };
+        let mut first = InjectionClock::new(profile);
+        let mut second = InjectionClock::new(profile);
+
+        for _ in 0..10 {
+            let first_offset = first.next_frame().unwrap() - first.started_at;
+            let second_offset = second.next_frame().unwrap() - second.started_at;
+            assert_eq!(first_offset, second_offset);
+        }
+    }
+
+    #[test]
+    fn injection_clock_drops_every_nth_frame() {
+        let interval = Duration::from_millis(40);
+        let mut clock = InjectionClock::new(TimingProfile {
+            interval,
+            jitter: Duration::ZERO,
+            drop_every: Some(3),
+        });
+        let started_at = clock.started_at;
+
+        let schedule: Vec<_> = (0..6).map(|_| clock.next_frame()).collect();
+        // a dropped frame still takes its slot
+        assert_eq!(
+            schedule,
+            [
+                Some(started_at),
+                Some(started_at + interval),
+                None,
+                Some(started_at + interval * 3),
+                Some(started_at + interval * 4),
+                None,
+            ]
+        );
+    }
+}
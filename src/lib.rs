@@ -34,6 +34,8 @@
 pub mod backends;
 mod camera;
 mod init;
+#[cfg(test)]
+mod mock;
 /// A camera that uses native browser APIs meant for WASM applications.
 #[cfg(feature = "input-jscam")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "input-jscam")))]
@@ -41,16 +43,47 @@ pub mod js_camera;
 mod platform_resolver;
 
 pub use nokhwa_core::pixel_format::FormatDecoder;
-#[cfg(feature = "output-async")]
+/// A camera for async code, that captures on a dedicated thread.
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
 pub mod async_camera;
+/// A camera for async code in the browser, that waits for frames without threads.
+#[cfg(all(feature = "output-async", feature = "input-jscam", target_arch = "wasm32"))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub mod wasm_async_camera;
 mod query;
-/// A camera that runs in a different thread and can call your code based on callbacks.
+/// Cameras that run in a different thread and can call your code based on callbacks, or be polled.
 #[cfg(feature = "output-threaded")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
 pub mod threaded;
+#[cfg(any(feature = "output-threaded", feature = "output-async"))]
+mod thread_options;
+#[cfg(any(
+    feature = "output-threaded",
+    feature = "input-uvc",
+    all(feature = "output-async", not(target_arch = "wasm32"))
+))]
+// the async camera only uses `join_with_timeout()`
+#[cfg_attr(
+    not(any(feature = "output-threaded", feature = "input-uvc")),
+    allow(dead_code)
+)]
+mod thread_utils;
+#[cfg(any(test, feature = "input-uvc"))]
+// only the tests use it without `input-uvc`
+#[cfg_attr(not(feature = "input-uvc"), allow(dead_code))]
+mod capture_buffers;
 pub mod decoders;
 
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub use async_camera::{AsyncCamera, FrameStream, FrameStreamBackpressure};
+#[cfg(all(feature = "output-async", feature = "input-jscam", target_arch = "wasm32"))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub use wasm_async_camera::AsyncCamera;
+#[cfg(all(feature = "output-tokio", not(target_arch = "wasm32")))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+pub use async_camera::decode_frame;
 pub use camera::Camera;
 pub use init::*;
 pub use nokhwa_core::buffer::Buffer;
@@ -58,7 +91,16 @@ pub use nokhwa_core::error::NokhwaError;
 pub use query::*;
 #[cfg(feature = "output-threaded")]
 #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
-pub use threaded::CallbackCamera;
+pub use threaded::{
+    CallbackCamera, CaptureSession, FrameDispatch, FrameEvent, OpenRetryPolicy, StallAction,
+    StreamWatchdog, ThreadedCamera,
+};
+#[cfg(any(feature = "output-threaded", feature = "output-async"))]
+#[cfg_attr(
+    feature = "docs-features",
+    doc(cfg(any(feature = "output-threaded", feature = "output-async")))
+)]
+pub use thread_options::ThreadOptions;
 
 pub mod utils {
     pub use nokhwa_core::types::*;
@@ -0,0 +1,245 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// A camera backend for tests, that delivers numbered YUYV frames and counts what is left open.
+
+use crate::Camera;
+use nokhwa_core::{
+    buffer::Buffer,
+    error::NokhwaError,
+    format_request::FormatFilter,
+    frame_format::{FrameFormat, SourceFrameFormat},
+    traits::{Backend, CaptureTrait},
+    types::{
+        ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueSetter,
+        FrameRate, KnownCameraControl, Resolution,
+    },
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+const RESOLUTION: Resolution = Resolution {
+    width_x: 4,
+    height_y: 2,
+};
+
+// Shared with the test, as the camera itself usually ends up on a capture thread.
+#[derive(Debug, Default)]
+pub(crate) struct MockStats {
+    // mock cameras not dropped yet
+    pub(crate) devices: AtomicUsize,
+    // streams opened and not stopped yet
+    pub(crate) open_streams: AtomicUsize,
+    // frames handed out
+    pub(crate) frames: AtomicUsize,
+    // how many of the next `open_stream()` calls fail, after setting up the stream like a backend failing late
+    pub(crate) failing_opens: AtomicUsize,
+}
+
+pub(crate) struct MockCamera {
+    info: CameraInfo,
+    format: CameraFormat,
+    stream_open: bool,
+    frame_interval: Duration,
+    sequence: u64,
+    stats: Arc<MockStats>,
+}
+
+impl MockCamera {
+    pub(crate) fn new(frame_interval: Duration, stats: Arc<MockStats>) -> Self {
+        stats.devices.fetch_add(1, Ordering::SeqCst);
+        MockCamera {
+            info: CameraInfo::new("Mock", "Mock Camera", "", &CameraIndex::Index(0)),
+            format: CameraFormat::new(RESOLUTION, FrameFormat::Yuv422, FrameRate::new_integer(30)),
+            stream_open: false,
+            frame_interval,
+            sequence: 0,
+            stats,
+        }
+    }
+
+    // A `Camera` on a new mock, and the stats to check it with.
+    pub(crate) fn camera(frame_interval: Duration) -> (Camera, Arc<MockStats>) {
+        let stats = Arc::new(MockStats::default());
+        let camera = Camera::from_device(Box::new(MockCamera::new(frame_interval, stats.clone())));
+        (camera, stats)
+    }
+}
+
+// The number of a frame from a `MockCamera`, starting at 1.
+pub(crate) fn mock_sequence(frame: &Buffer) -> u64 {
+    let mut sequence = [0; 8];
+    sequence.copy_from_slice(&frame.buffer()[..8]);
+    u64::from_le_bytes(sequence)
+}
+
+impl Backend for MockCamera {
+    const BACKEND: ApiBackend = ApiBackend::Custom("Mock");
+}
+
+impl CaptureTrait for MockCamera {
+    fn init(&mut self) -> Result<(), NokhwaError> {
+        Ok(())
+    }
+
+    fn init_with_format(&mut self, _: FormatFilter) -> Result<CameraFormat, NokhwaError> {
+        Ok(self.format)
+    }
+
+    fn backend(&self) -> ApiBackend {
+        Self::BACKEND
+    }
+
+    fn camera_info(&self) -> &CameraInfo {
+        &self.info
+    }
+
+    fn refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
+        Ok(())
+    }
+
+    fn camera_format(&self) -> Option<CameraFormat> {
+        Some(self.format)
+    }
+
+    fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
+        if new_fmt == self.format {
+            Ok(())
+        } else {
+            Err(NokhwaError::SetPropertyError {
+                property: "CameraFormat".to_string(),
+                value: new_fmt.to_string(),
+                error: "The mock camera has one format".to_string(),
+            })
+        }
+    }
+
+    fn compatible_list_by_resolution(
+        &mut self,
+        _: SourceFrameFormat,
+    ) -> Result<HashMap<Resolution, Vec<u32>>, NokhwaError> {
+        Ok(HashMap::from([(RESOLUTION, vec![30])]))
+    }
+
+    fn compatible_fourcc(&mut self) -> Result<Vec<SourceFrameFormat>, NokhwaError> {
+        Ok(vec![FrameFormat::Yuv422.into()])
+    }
+
+    fn resolution(&self) -> Option<Resolution> {
+        Some(RESOLUTION)
+    }
+
+    fn set_resolution(&mut self, _: Resolution) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    fn frame_rate(&self) -> Option<u32> {
+        Some(30)
+    }
+
+    fn set_frame_rate(&mut self, _: u32) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    fn frame_format(&self) -> SourceFrameFormat {
+        FrameFormat::Yuv422.into()
+    }
+
+    fn set_frame_format(&mut self, _: SourceFrameFormat) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    fn camera_control(&self, _: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+        Ok(vec![])
+    }
+
+    fn set_camera_control(
+        &mut self,
+        _: KnownCameraControl,
+        _: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    fn open_stream(&mut self) -> Result<(), NokhwaError> {
+        if self.stream_open {
+            return Err(NokhwaError::OpenStreamError(
+                "The stream is already open".to_string(),
+            ));
+        }
+        self.stream_open = true;
+        self.stats.open_streams.fetch_add(1, Ordering::SeqCst);
+        if self
+            .stats
+            .failing_opens
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(NokhwaError::OpenStreamError(
+                "Injected open failure".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_stream_open(&self) -> bool {
+        self.stream_open
+    }
+
+    fn frame(&mut self) -> Result<Buffer, NokhwaError> {
+        let data = self.frame_raw()?.into_owned();
+        Ok(Buffer::new(RESOLUTION, &data, FrameFormat::Yuv422.into()))
+    }
+
+    fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+        if !self.stream_open {
+            return Err(NokhwaError::ReadFrameError(
+                "Please call `open_stream()` first!".to_string(),
+            ));
+        }
+        std::thread::sleep(self.frame_interval);
+        self.sequence += 1;
+        self.stats.frames.fetch_add(1, Ordering::SeqCst);
+        let mut data = vec![128; 16];
+        data[..8].copy_from_slice(&self.sequence.to_le_bytes());
+        Ok(Cow::Owned(data))
+    }
+
+    fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+        if self.stream_open {
+            self.stream_open = false;
+            self.stats.open_streams.fetch_sub(1, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MockCamera {
+    fn drop(&mut self) {
+        self.stats.devices.fetch_sub(1, Ordering::SeqCst);
+    }
+}
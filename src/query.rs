@@ -18,6 +18,15 @@ use nokhwa_core::{
     error::NokhwaError,
     types::{ApiBackend, CameraInfo},
 };
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
+use std::sync::Mutex;
+
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
+type QueryReply = flume::Sender<Result<Vec<CameraInfo>, NokhwaError>>;
+
+// The callers waiting for each backend's running query.
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
+static QUERIES_IN_FLIGHT: Mutex<Vec<(ApiBackend, Vec<QueryReply>)>> = Mutex::new(Vec::new());
 
 /// Gets the native [`ApiBackend`]
 #[must_use]
@@ -105,6 +114,63 @@ pub fn query(api: ApiBackend) -> Result<Vec<CameraInfo>, NokhwaError> {
     }
 }
 
+/// Like [`query()`], but the devices are enumerated on a thread of its own, so the (sometimes slow) enumeration does not
+/// block the async runtime. It works with any async runtime.
+///
+/// Calls for the same backend while one is running share its result instead of enumerating again. Dropping the future
+/// does not cancel the enumeration, it finishes on its thread, so no backend is left half-initialized.
+/// # Errors
+/// If the enumeration thread could not be spawned, or as [`query()`] does, this will error.
+#[cfg(all(feature = "output-async", not(target_arch = "wasm32")))]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub async fn query_async(api: ApiBackend) -> Result<Vec<CameraInfo>, NokhwaError> {
+    let (reply, reply_receiver) = flume::bounded(1);
+    let spawn = {
+        let mut in_flight = QUERIES_IN_FLIGHT
+            .lock()
+            .map_err(|why| NokhwaError::GeneralError(why.to_string()))?;
+        match in_flight.iter_mut().find(|(backend, _)| *backend == api) {
+            Some((_, waiting)) => {
+                waiting.push(reply);
+                false
+            }
+            None => {
+                in_flight.push((api, vec![reply]));
+                true
+            }
+        }
+    };
+
+    if spawn {
+        let spawned = std::thread::Builder::new()
+            .name("nokhwa-query".to_string())
+            .spawn(move || {
+                let devices = query(api);
+                let waiting = QUERIES_IN_FLIGHT.lock().ok().and_then(|mut in_flight| {
+                    let index = in_flight.iter().position(|(backend, _)| *backend == api)?;
+                    Some(in_flight.swap_remove(index).1)
+                });
+                // the receivers of dropped futures are gone, which is fine
+                for reply in waiting.into_iter().flatten() {
+                    let _ = reply.send(devices.clone());
+                }
+            });
+        if let Err(why) = spawned {
+            if let Ok(mut in_flight) = QUERIES_IN_FLIGHT.lock() {
+                in_flight.retain(|(backend, _)| *backend != api);
+            }
+            return Err(NokhwaError::GeneralError(format!(
+                "Failed to spawn query thread: {why}"
+            )));
+        }
+    }
+
+    reply_receiver
+        .recv_async()
+        .await
+        .map_err(|why| NokhwaError::GeneralError(format!("Query thread stopped: {why}")))?
+}
+
 // TODO: More
 
 #[cfg(all(feature = "input-v4l", target_os = "linux"))]
@@ -121,7 +187,7 @@ fn query_v4l() -> Result<Vec<CameraInfo>, NokhwaError> {
 
 #[cfg(feature = "input-uvc")]
 fn query_uvc() -> Result<Vec<CameraInfo>, NokhwaError> {
-    use crate::CameraIndex;
+    use crate::{backends::capture::uvc_is_camera, CameraIndex};
     use uvc::Device;
 
     let context = match uvc::Context::new() {
@@ -149,11 +215,14 @@ fn query_uvc() -> Result<Vec<CameraInfo>, NokhwaError> {
     };
 
     let mut camera_info_vec = vec![];
-    let mut counter = 0_usize;
 
     // Optimize this O(n*m) algorithm
     for usb_dev in &usb_devices {
-        for uvc_dev in &uvc_devices {
+        // the index has to be the one `UVCCaptureDevice::create()` opens, so non-cameras are skipped but still counted
+        for (index, uvc_dev) in uvc_devices.iter().enumerate() {
+            if !uvc_is_camera(uvc_dev) {
+                continue;
+            }
             if let Ok(desc) = uvc_dev.description() {
                 if desc.product_id == usb_dev.product_id && desc.vendor_id == usb_dev.vendor_id {
                     let name = usb_dev
@@ -181,9 +250,8 @@ fn query_uvc() -> Result<Vec<CameraInfo>, NokhwaError> {
                             desc.product_id,
                             desc.serial_number.unwrap_or_else(|| "".to_string())
                         ),
-                        CameraIndex::Index(counter as u32),
+                        CameraIndex::Index(index as u32),
                     ));
-                    counter += 1;
                 }
             }
         }
@@ -0,0 +1,110 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use nokhwa_core::error::NokhwaError;
+use std::thread::{Builder, JoinHandle};
+
+/// How the threads a camera wrapper spawns (capture, decoding) are set up.
+///
+/// Setting the priority needs the `thread-priority` feature, which uses `pthread_setschedparam` or `SetThreadPriority`.
+/// Raising it above normal usually needs extra privileges (e.g. `CAP_SYS_NICE` on Linux). Failing to set it does not stop
+/// the thread from starting, the camera reports it as a warning instead.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ThreadOptions {
+    /// The thread name, as shown by debuggers and profilers. Further threads of the same camera get a suffix, e.g. `-decode-0`.
+    pub name: Option<String>,
+    /// The thread priority, from `0` (lowest) to `99` (highest), mapped onto the platform's range. `None` keeps the default.
+    pub priority: Option<u8>,
+}
+
+impl ThreadOptions {
+    /// Creates `ThreadOptions` with only a name.
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        ThreadOptions {
+            name: Some(name.into()),
+            priority: None,
+        }
+    }
+
+    /// Sets the priority, see [`priority`](ThreadOptions::priority).
+    #[must_use]
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub(crate) fn with_suffix(&self, suffix: &str) -> Self {
+        ThreadOptions {
+            name: self.name.as_ref().map(|name| format!("{name}-{suffix}")),
+            priority: self.priority,
+        }
+    }
+
+    // Spawns `f` on a thread set up with these options. The second value is the warning if the priority could not be set.
+    pub(crate) fn spawn<F, T>(
+        &self,
+        f: F,
+    ) -> Result<(JoinHandle<T>, Option<NokhwaError>), NokhwaError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut builder = Builder::new();
+        if let Some(name) = &self.name {
+            builder = builder.name(name.clone());
+        }
+        let priority = self.priority;
+        let (priority_result, priority_receiver) = std::sync::mpsc::sync_channel(1);
+        let handle = builder
+            .spawn(move || {
+                let _ = priority_result.send(priority.map_or(Ok(()), set_current_thread_priority));
+                f()
+            })
+            .map_err(|why| NokhwaError::GeneralError(format!("Failed to spawn thread: {why}")))?;
+        let warning = priority_receiver.recv().ok().and_then(Result::err);
+        Ok((handle, warning))
+    }
+}
+
+#[cfg(feature = "thread-priority")]
+fn set_current_thread_priority(priority: u8) -> Result<(), NokhwaError> {
+    let value =
+        thread_priority::ThreadPriorityValue::try_from(priority.min(99)).map_err(|why| {
+            NokhwaError::SetPropertyError {
+                property: "thread priority".to_string(),
+                value: priority.to_string(),
+                error: why.to_string(),
+            }
+        })?;
+    thread_priority::set_current_thread_priority(thread_priority::ThreadPriority::Crossplatform(
+        value,
+    ))
+    .map_err(|why| NokhwaError::SetPropertyError {
+        property: "thread priority".to_string(),
+        value: priority.to_string(),
+        error: format!("{why:?}"),
+    })
+}
+
+#[cfg(not(feature = "thread-priority"))]
+fn set_current_thread_priority(priority: u8) -> Result<(), NokhwaError> {
+    Err(NokhwaError::SetPropertyError {
+        property: "thread priority".to_string(),
+        value: priority.to_string(),
+        error: "Setting the thread priority needs the `thread-priority` feature".to_string(),
+    })
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Thread helpers shared by the threaded and async cameras and the UVC backend.
+
+use crate::NokhwaError;
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+// The message of a caught panic, if it has one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => (*message).to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "Box<dyn Any>".to_string(),
+        },
+    }
+}
+
+// Runs `check` on a thread of its own a few times per `timeout`, to find out whether a stream stalled.
+// Dropping it stops and joins the thread.
+pub(crate) struct StallMonitor {
+    die_bool: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StallMonitor {
+    pub(crate) fn spawn(timeout: Duration, mut check: impl FnMut() + Send + 'static) -> Self {
+        let die_bool = Arc::new(AtomicBool::new(false));
+        let die_bool_thread = die_bool.clone();
+        let handle = std::thread::spawn(move || {
+            let poll_interval =
+                (timeout / 4).clamp(Duration::from_millis(1), Duration::from_millis(100));
+            while !die_bool_thread.load(Ordering::SeqCst) {
+                check();
+                std::thread::sleep(poll_interval);
+            }
+        });
+
+        StallMonitor {
+            die_bool,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for StallMonitor {
+    fn drop(&mut self) {
+        self.die_bool.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+// Joins `handle`, giving up after `timeout`. A thread that has not finished by then is left running, detached.
+pub(crate) fn join_with_timeout<T>(
+    handle: JoinHandle<T>,
+    timeout: Duration,
+) -> Result<T, NokhwaError> {
+    let deadline = Instant::now() + timeout;
+    while !handle.is_finished() {
+        if Instant::now() >= deadline {
+            return Err(NokhwaError::StreamShutdownError(format!(
+                "The capture thread did not stop within {timeout:?}"
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    handle
+        .join()
+        .map_err(|_| NokhwaError::StreamShutdownError("Capture thread panicked".to_string()))
+}
@@ -14,22 +14,36 @@
  * limitations under the License.
  */
 
-use crate::Camera;
+use crate::{
+    thread_utils::{join_with_timeout, panic_message, StallMonitor},
+    Camera, ThreadOptions,
+};
+use image::{ImageBuffer, Rgb};
 use nokhwa_core::{
     buffer::Buffer,
     error::NokhwaError,
+    pixel_format::RgbFormat,
+    traits::CaptureTrait,
     types::{
         ApiBackend, CameraControl, CameraFormat, CameraIndex, CameraInfo, ControlValueSetter,
-        FrameFormat, KnownCameraControl, RequestedFormat, RequestedFormatType, Resolution,
+        FrameFormat, FrameStats, KnownCameraControl, RequestedFormat, RequestedFormatType,
+        Resolution,
     },
 };
 use std::thread::JoinHandle;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{channel, sync_channel, Receiver, Sender, SyncSender},
+        Arc, Condvar, Mutex, TryLockError, Weak,
     },
+    task::{Context, Poll, Waker},
+    thread::Scope,
+    time::{Duration, Instant},
 };
 
 type AtomicLock<T> = Arc<Mutex<T>>;
@@ -40,6 +54,772 @@ pub type CallbackFn = fn(
     _die_bool: &Arc<AtomicBool>,
 );
 type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) + Send + 'static>>>;
+type HeldErrorCallbackType = Arc<Mutex<Option<Box<dyn FnMut(NokhwaError) + Send + 'static>>>>;
+type Subscribers = AtomicLock<Vec<Weak<SubscriberSlot>>>;
+
+// How many frames a `CaptureSession` holds before the capture thread waits for the closure to take one.
+const SESSION_FRAME_QUEUE_LEN: usize = 4;
+
+/// How long dropping a [`CallbackCamera`] or [`ThreadedCamera`] waits for its capture thread to stop.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many frames a [`FrameReceiver`] keeps while its owner has not taken them.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SubscriberBuffering {
+    /// Only the latest frame is kept.
+    LatestOnly,
+    /// Up to this many frames are kept. When a new frame arrives while they are full, the oldest one is dropped.
+    Bounded(usize),
+}
+
+/// What a [`CallbackCamera`] does when the frame or error callback panics. Either way, the panic is caught, kept in
+/// [`last_panic()`](CallbackCamera::last_panic), and (if it was the frame callback) passed to the error callback.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CallbackPanicPolicy {
+    /// Keep capturing and calling the callbacks.
+    #[default]
+    KeepStreaming,
+    /// Stop the stream and the capture thread.
+    StopStream,
+}
+
+/// What a [`StreamWatchdog`] does when it fires.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StallAction {
+    /// Report a [`NokhwaError::StreamStalled`] like any other capture error.
+    #[default]
+    Report,
+    /// Report it, then stop and reopen the stream. This happens on the capture thread the next time reading a frame
+    /// returns, so a backend that blocks forever on a hung camera can only report it.
+    Restart,
+}
+
+/// Watches for a stream that is open, but has stopped delivering frames (e.g. because the camera's firmware hung), see
+/// [`CallbackCamera::set_stream_watchdog()`] and [`ThreadedCamera::set_stream_watchdog()`].
+///
+/// It fires once no frame arrived for `timeout`, and not again until a frame arrived in between. Its clock restarts when the
+/// stream is (re)opened, and stops while the camera is busy with something else through the wrapper (e.g. changing the
+/// format), so intentional pauses do not set it off. Whether the stream is stalled, and how often it was, is in
+/// [`FrameStats`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StreamWatchdog {
+    /// How long without a frame counts as a stall.
+    pub timeout: Duration,
+    /// What happens on a stall.
+    pub action: StallAction,
+}
+
+impl StreamWatchdog {
+    #[must_use]
+    pub fn new(timeout: Duration, action: StallAction) -> Self {
+        StreamWatchdog { timeout, action }
+    }
+}
+
+/// How [`CallbackCamera::open_stream()`] and [`ThreadedCamera::with_retry_policy()`] retry opening the stream, for cameras
+/// that fail the first open after boot or resume with a transient error and succeed shortly after.
+///
+/// Errors that retrying cannot fix (an unsupported operation, or permission denied) fail right away, whatever
+/// [`retryable`](OpenRetryPolicy::retryable) says. If every attempt fails, the error is an [`NokhwaError::OpenStreamError`]
+/// listing each attempt's error.
+#[derive(Clone, Debug)]
+pub struct OpenRetryPolicy {
+    /// How many times opening is tried, including the first. `1` (the default) does not retry.
+    pub max_attempts: usize,
+    /// How long to wait before each retry. If there are more retries than entries, the last one is used for the rest, so
+    /// e.g. `[50ms, 200ms]` waits 50ms, then 200ms every time after.
+    pub backoff: Vec<Duration>,
+    /// Which errors are retried, [`is_transient()`](OpenRetryPolicy::is_transient) by default.
+    pub retryable: fn(&NokhwaError) -> bool,
+}
+
+impl Default for OpenRetryPolicy {
+    fn default() -> Self {
+        OpenRetryPolicy {
+            max_attempts: 1,
+            backoff: Vec::new(),
+            retryable: OpenRetryPolicy::is_transient,
+        }
+    }
+}
+
+impl OpenRetryPolicy {
+    /// Creates an `OpenRetryPolicy` that retries the [transient](OpenRetryPolicy::is_transient) errors.
+    #[must_use]
+    pub fn new(max_attempts: usize, backoff: Vec<Duration>) -> Self {
+        OpenRetryPolicy {
+            max_attempts,
+            backoff,
+            ..OpenRetryPolicy::default()
+        }
+    }
+
+    /// Whether `error` is one a device may report while it is still coming up: failing to open the device or stream, to
+    /// read from it, or timing out.
+    #[must_use]
+    pub fn is_transient(error: &NokhwaError) -> bool {
+        matches!(
+            error,
+            NokhwaError::OpenDeviceError(_, _)
+                | NokhwaError::OpenStreamError(_)
+                | NokhwaError::ReadFrameError(_)
+                | NokhwaError::TimeoutError(_)
+                | NokhwaError::GeneralError(_)
+        )
+    }
+
+    // Errors that fail the same way however often they are retried.
+    fn is_fatal(error: &NokhwaError) -> bool {
+        if matches!(
+            error,
+            NokhwaError::UnsupportedOperationError(_) | NokhwaError::NotImplementedError(_)
+        ) {
+            return true;
+        }
+        let message = error.to_string().to_lowercase();
+        message.contains("permission denied") || message.contains("access denied")
+    }
+
+    // Calls `open` until it succeeds, fails with an error that is not retried, or runs out of attempts.
+    fn run(&self, mut open: impl FnMut() -> Result<(), NokhwaError>) -> Result<(), NokhwaError> {
+        let mut failures = Vec::new();
+        loop {
+            let why = match open() {
+                Ok(()) => return Ok(()),
+                Err(why) => why,
+            };
+            let retry = failures.len() + 1 < self.max_attempts
+                && !Self::is_fatal(&why)
+                && (self.retryable)(&why);
+            if !retry && failures.is_empty() {
+                return Err(why);
+            }
+            let delay = self
+                .backoff
+                .get(failures.len())
+                .or_else(|| self.backoff.last())
+                .copied()
+                .unwrap_or_default();
+            failures.push(why);
+            if !retry {
+                let attempts = failures
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, why)| format!("attempt {}: {why}", idx + 1))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                return Err(NokhwaError::OpenStreamError(format!(
+                    "Failed after {} attempts ({attempts})",
+                    failures.len()
+                )));
+            }
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// What a [`FrameDispatch`] other than the frame callback is handed.
+pub enum FrameEvent {
+    /// A captured frame.
+    Frame(Buffer),
+    /// An error the capture thread ran into. It is also kept in [`last_error()`](CallbackCamera::last_error) and passed to
+    /// the error callback.
+    Error(NokhwaError),
+}
+
+/// Where a [`CallbackCamera`] delivers frames, see [`set_frame_dispatch()`](CallbackCamera::set_frame_dispatch).
+///
+/// Frames are delivered in the order they were captured. If delivering fails (e.g. the receiver was dropped or the
+/// runtime shut down), this is reported once as an error, and frames are no longer delivered until the stream is reopened.
+#[derive(Clone, Default)]
+pub enum FrameDispatch {
+    /// The frame callback is called on the capture thread.
+    #[default]
+    InThread,
+    /// The frame callback is called on a task spawned on this runtime, e.g. to process frames where the rest of the async
+    /// code runs. One task calls it for all frames, in order.
+    #[cfg(feature = "output-tokio")]
+    #[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-tokio")))]
+    Runtime(tokio::runtime::Handle),
+    /// Frames and errors are sent to this channel instead of the callbacks being called with them.
+    Channel(Sender<FrameEvent>),
+    /// Frames and errors are passed to this function on the capture thread, instead of the callbacks being called with them.
+    /// It should only post them elsewhere, e.g. to a GUI toolkit's main loop.
+    Custom(Arc<dyn Fn(FrameEvent) + Send + Sync>),
+}
+
+// A `FrameDispatch` as it is used by a running capture thread.
+struct Dispatcher {
+    dispatch: FrameDispatch,
+    #[cfg(feature = "output-tokio")]
+    runtime_frames: Option<flume::Sender<Buffer>>,
+    failed: bool,
+}
+
+impl Dispatcher {
+    fn new(
+        dispatch: FrameDispatch,
+        frame_callback: &HeldCallbackType,
+        callback_panics: &Arc<CallbackPanics>,
+    ) -> Self {
+        #[cfg(feature = "output-tokio")]
+        let runtime_frames = match &dispatch {
+            FrameDispatch::Runtime(handle) => {
+                let (sender, receiver) = flume::unbounded::<Buffer>();
+                let frame_callback = frame_callback.clone();
+                let callback_panics = callback_panics.clone();
+                // if the runtime is shut down, the task is dropped with the receiver, which `dispatch()` then reports
+                handle.spawn(async move {
+                    while let Ok(frame) = receiver.recv_async().await {
+                        if let Ok(mut cb) = frame_callback.lock() {
+                            callback_panics.run(|| cb(frame));
+                        }
+                    }
+                });
+                Some(sender)
+            }
+            _ => None,
+        };
+        #[cfg(not(feature = "output-tokio"))]
+        let _ = (frame_callback, callback_panics);
+
+        Dispatcher {
+            dispatch,
+            #[cfg(feature = "output-tokio")]
+            runtime_frames,
+            failed: false,
+        }
+    }
+
+    fn in_thread(&self) -> bool {
+        matches!(self.dispatch, FrameDispatch::InThread)
+    }
+
+    // Hands `event` to a dispatcher other than `InThread`. Returns the message of a panic in a custom dispatcher, or the
+    // error if delivering failed for the first time.
+    fn dispatch(
+        &mut self,
+        event: FrameEvent,
+        callback_panics: &CallbackPanics,
+    ) -> Result<Option<String>, NokhwaError> {
+        if self.failed {
+            return Ok(None);
+        }
+        let delivered = match &self.dispatch {
+            FrameDispatch::InThread => Ok(()),
+            #[cfg(feature = "output-tokio")]
+            FrameDispatch::Runtime(_) => match (event, &self.runtime_frames) {
+                (FrameEvent::Frame(frame), Some(runtime_frames)) => {
+                    runtime_frames.send(frame).map_err(|_| {
+                        "The runtime the frame callback runs on has shut down".to_string()
+                    })
+                }
+                // errors go to the error callback as usual
+                _ => Ok(()),
+            },
+            FrameDispatch::Channel(sender) => sender
+                .send(event)
+                .map_err(|_| "The frame channel's receiver was dropped".to_string()),
+            FrameDispatch::Custom(dispatcher) => {
+                return Ok(callback_panics.run(|| dispatcher(event)))
+            }
+        };
+        delivered.map(|()| None).map_err(|why| {
+            self.failed = true;
+            NokhwaError::GeneralError(format!("Failed to dispatch frames: {why}"))
+        })
+    }
+}
+
+#[derive(Default)]
+struct CallbackPanics {
+    policy: Mutex<CallbackPanicPolicy>,
+    last_panic: Mutex<Option<String>>,
+}
+
+impl CallbackPanics {
+    // Runs a user callback, so that a panic in it does not unwind through the capture thread.
+    // Returns the panic message if it panicked.
+    fn run(&self, callback: impl FnOnce()) -> Option<String> {
+        let payload = catch_unwind(AssertUnwindSafe(callback)).err()?;
+        let message = panic_message(&*payload);
+        if let Ok(mut last_panic) = self.last_panic.lock() {
+            *last_panic = Some(message.clone());
+        }
+        Some(message)
+    }
+
+    fn keep_streaming(&self) -> bool {
+        self.policy
+            .lock()
+            .map_or(true, |policy| *policy == CallbackPanicPolicy::KeepStreaming)
+    }
+}
+
+struct SubscriberSlot {
+    frames: Mutex<VecDeque<Arc<Buffer>>>,
+    available: Condvar,
+    capacity: usize,
+    closed: AtomicBool,
+}
+
+impl SubscriberSlot {
+    fn push(&self, frame: Arc<Buffer>) {
+        if let Ok(mut frames) = self.frames.lock() {
+            while frames.len() >= self.capacity {
+                frames.pop_front();
+            }
+            frames.push_back(frame);
+        }
+        self.available.notify_one();
+    }
+
+    fn close(&self) {
+        // taking the lock makes sure a receiver is either waiting already, or sees the flag
+        let _frames = self.frames.lock();
+        self.closed.store(true, Ordering::SeqCst);
+        self.available.notify_all();
+    }
+}
+
+// Counts the frames that were queued, and wakes everyone waiting for the next one.
+#[derive(Default)]
+struct FrameSignal {
+    state: Mutex<(u64, Vec<Waker>)>,
+    available: Condvar,
+}
+
+impl FrameSignal {
+    fn notify(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.0 = state.0.wrapping_add(1);
+            for waker in state.1.drain(..) {
+                waker.wake();
+            }
+        }
+        self.available.notify_all();
+    }
+
+    fn wait(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(_) => return false,
+        };
+        let seen = state.0;
+        while state.0 == seen {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            state = match self.available.wait_timeout(state, deadline - now) {
+                Ok((state, _)) => state,
+                Err(_) => return false,
+            };
+        }
+        true
+    }
+
+    fn generation(&self) -> u64 {
+        self.state.lock().map_or(0, |state| state.0)
+    }
+}
+
+// Counts the frames a capture thread receives. Updating only touches atomics, so it never waits on a reader or allocates.
+struct FrameCounters {
+    epoch: Instant,
+    frames_received: AtomicU64,
+    frames_dropped: AtomicU64,
+    frames_too_old: AtomicU64,
+    bytes_received: AtomicU64,
+    // nanoseconds since `epoch`, or 0 if no frame was received yet
+    last_frame_nanos: AtomicU64,
+    // nanoseconds since `epoch` the stall clock was last restarted, e.g. when the stream was opened
+    armed_nanos: AtomicU64,
+    // set while the camera is busy with something else through the wrapper, so the stall clock stops
+    paused: AtomicBool,
+    stalled: AtomicBool,
+    stalls: AtomicU64,
+    restart_requested: AtomicBool,
+}
+
+impl Default for FrameCounters {
+    fn default() -> Self {
+        FrameCounters {
+            epoch: Instant::now(),
+            frames_received: AtomicU64::new(0),
+            frames_dropped: AtomicU64::new(0),
+            frames_too_old: AtomicU64::new(0),
+            bytes_received: AtomicU64::new(0),
+            last_frame_nanos: AtomicU64::new(0),
+            armed_nanos: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+            stalled: AtomicBool::new(false),
+            stalls: AtomicU64::new(0),
+            restart_requested: AtomicBool::new(false),
+        }
+    }
+}
+
+impl FrameCounters {
+    fn now_nanos(&self) -> u64 {
+        u64::try_from(self.epoch.elapsed().as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    fn received(&self, bytes: usize) {
+        self.frames_received.fetch_add(1, Ordering::Relaxed);
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.last_frame_nanos
+            .store(self.now_nanos().max(1), Ordering::Release);
+        self.stalled.store(false, Ordering::SeqCst);
+    }
+
+    // Restarts the stall clock, e.g. because the stream was (re)opened.
+    fn arm(&self) {
+        self.armed_nanos.store(self.now_nanos(), Ordering::Release);
+        self.stalled.store(false, Ordering::SeqCst);
+    }
+
+    fn pause(&self) -> Instant {
+        self.paused.store(true, Ordering::SeqCst);
+        Instant::now()
+    }
+
+    // Moves the stall clock forward by the time since `pause()`, so the pause does not count towards a stall.
+    fn resume(&self, paused_at: Instant) {
+        let paused_nanos = u64::try_from(paused_at.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        let since = self
+            .last_frame_nanos
+            .load(Ordering::Acquire)
+            .max(self.armed_nanos.load(Ordering::Acquire));
+        self.armed_nanos
+            .store(since.saturating_add(paused_nanos), Ordering::Release);
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    // How long the stall clock has been running, or `None` while it is paused.
+    fn idle(&self) -> Option<Duration> {
+        if self.paused.load(Ordering::SeqCst) {
+            return None;
+        }
+        let since = self
+            .last_frame_nanos
+            .load(Ordering::Acquire)
+            .max(self.armed_nanos.load(Ordering::Acquire));
+        Some(Duration::from_nanos(self.now_nanos().saturating_sub(since)))
+    }
+
+    // Marks the stream stalled. Returns `false` if it already was, so the watchdog fires once per stall.
+    fn stall(&self) -> bool {
+        let newly_stalled = !self.stalled.swap(true, Ordering::SeqCst);
+        if newly_stalled {
+            self.stalls.fetch_add(1, Ordering::Relaxed);
+        }
+        newly_stalled
+    }
+
+    // Called by the capture thread after each read: reopens the stream if the watchdog asked for it.
+    fn restart_if_requested(&self, camera: &mut Camera) -> Result<(), NokhwaError> {
+        if !self.restart_requested.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let restarted = camera.restart_stream();
+        self.arm();
+        restarted
+    }
+
+    fn dropped(&self, frames: u64) {
+        self.frames_dropped.fetch_add(frames, Ordering::Relaxed);
+    }
+
+    fn too_old(&self) {
+        self.frames_too_old.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> FrameStats {
+        let last_frame_nanos = self.last_frame_nanos.load(Ordering::Acquire);
+        FrameStats {
+            frames_received: self.frames_received.load(Ordering::Relaxed),
+            frames_dropped: self.frames_dropped.load(Ordering::Relaxed),
+            frames_too_old: self.frames_too_old.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            last_frame_at: (last_frame_nanos != 0)
+                .then(|| self.epoch + Duration::from_nanos(last_frame_nanos)),
+            stalled: self.stalled.load(Ordering::SeqCst),
+            stalls: self.stalls.load(Ordering::Relaxed),
+        }
+    }
+
+    // Each counter is swapped with 0, so a frame counted meanwhile lands either in the returned stats or after the reset,
+    // never in both or neither. The stall clock keeps running from the last frame, so a stream that is still stalled is
+    // reported again.
+    fn reset(&self) -> FrameStats {
+        let last_frame_nanos = self.last_frame_nanos.swap(0, Ordering::AcqRel);
+        self.armed_nanos
+            .fetch_max(last_frame_nanos, Ordering::AcqRel);
+        FrameStats {
+            frames_received: self.frames_received.swap(0, Ordering::Relaxed),
+            frames_dropped: self.frames_dropped.swap(0, Ordering::Relaxed),
+            frames_too_old: self.frames_too_old.swap(0, Ordering::Relaxed),
+            bytes_received: self.bytes_received.swap(0, Ordering::Relaxed),
+            last_frame_at: (last_frame_nanos != 0)
+                .then(|| self.epoch + Duration::from_nanos(last_frame_nanos)),
+            stalled: self.stalled.swap(false, Ordering::SeqCst),
+            stalls: self.stalls.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+// Watches a camera's `FrameCounters` for a stall, see `StreamWatchdog`. Dropping the monitor stops and joins its thread.
+fn spawn_stall_monitor(
+    watchdog: StreamWatchdog,
+    frame_counters: Arc<FrameCounters>,
+    mut report: impl FnMut(NokhwaError) + Send + 'static,
+) -> StallMonitor {
+    frame_counters.arm();
+    StallMonitor::spawn(watchdog.timeout, move || {
+        if let Some(idle) = frame_counters.idle() {
+            if idle >= watchdog.timeout && frame_counters.stall() {
+                if watchdog.action == StallAction::Restart {
+                    frame_counters
+                        .restart_requested
+                        .store(true, Ordering::SeqCst);
+                }
+                report(NokhwaError::StreamStalled(idle));
+            }
+        }
+    })
+}
+
+// One of the three buffers of a `LatestFrameSlot`. The data is copied in, so its allocation is reused once it is large enough.
+#[derive(Default)]
+struct SlotBuffer {
+    data: Vec<u8>,
+    resolution: Resolution,
+    captured_at: Option<Instant>,
+    generation: u64,
+}
+
+impl std::fmt::Debug for SlotBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlotBuffer")
+            .field("len", &self.data.len())
+            .field("resolution", &self.resolution)
+            .field("captured_at", &self.captured_at)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+// A triple buffer: the capture thread owns `back` and the reader owns its front buffer, so neither waits on the other
+// while copying or reading. Only the swaps with `middle` take a lock.
+struct LatestFrameSlot {
+    back: Mutex<SlotBuffer>,
+    middle: Mutex<(SlotBuffer, bool)>,
+    generation: AtomicU64,
+}
+
+impl LatestFrameSlot {
+    fn publish(&self, frame: &Buffer) {
+        let mut back = match self.back.lock() {
+            Ok(back) => back,
+            Err(_) => return,
+        };
+        back.data.clear();
+        back.data.extend_from_slice(frame.buffer());
+        back.resolution = frame.resolution();
+        back.captured_at = Some(Instant::now());
+        back.generation = self.generation.load(Ordering::SeqCst) + 1;
+        if let Ok(mut middle) = self.middle.lock() {
+            std::mem::swap(&mut *back, &mut middle.0);
+            middle.1 = true;
+            self.generation.store(middle.0.generation, Ordering::SeqCst);
+        }
+    }
+}
+
+/// The latest frame of a [`LatestFrameReader`], borrowed from it until the next [`poll_frame()`](LatestFrameReader::poll_frame).
+#[derive(Copy, Clone, Debug)]
+pub struct SlotFrame<'a> {
+    buffer: &'a SlotBuffer,
+}
+
+impl<'a> SlotFrame<'a> {
+    /// Gets the undecoded frame data, in the camera's frame format.
+    #[must_use]
+    pub fn data(&self) -> &'a [u8] {
+        &self.buffer.data
+    }
+
+    /// Gets the [`Resolution`] of the frame.
+    #[must_use]
+    pub fn resolution(&self) -> Resolution {
+        self.buffer.resolution
+    }
+
+    /// Gets when the frame was received from the camera.
+    #[must_use]
+    pub fn captured_at(&self) -> Option<Instant> {
+        self.buffer.captured_at
+    }
+
+    /// Gets the generation of the frame, see [`LatestFrameReader::generation()`].
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.buffer.generation
+    }
+}
+
+/// Reads the latest frame of a [`CallbackCamera`] from a single slot that the capture thread overwrites, instead of a queue.
+/// Get one from [`latest_frame_reader()`](CallbackCamera::latest_frame_reader).
+///
+/// The slot is a triple buffer of reused allocations: once they have grown to the frame size, neither capturing nor reading a
+/// frame allocates, and reading never waits for the camera.
+pub struct LatestFrameReader {
+    slot: Arc<LatestFrameSlot>,
+    front: SlotBuffer,
+}
+
+impl LatestFrameReader {
+    /// Gets the generation of the latest captured frame. It starts at `0` (no frame yet) and counts up by one per frame,
+    /// so comparing it with [`SlotFrame::generation()`] tells whether a newer frame is waiting, and how many were skipped.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.slot.generation.load(Ordering::SeqCst)
+    }
+
+    /// Gets the latest frame, if one was captured since the last call. This does not wait for the camera.
+    pub fn poll_frame(&mut self) -> Option<SlotFrame<'_>> {
+        let mut middle = self.slot.middle.lock().ok()?;
+        if !middle.1 {
+            return None;
+        }
+        std::mem::swap(&mut self.front, &mut middle.0);
+        middle.1 = false;
+        drop(middle);
+        Some(SlotFrame {
+            buffer: &self.front,
+        })
+    }
+
+    /// Gets the frame returned by the last [`poll_frame()`](LatestFrameReader::poll_frame) again, if any.
+    #[must_use]
+    pub fn frame(&self) -> Option<SlotFrame<'_>> {
+        (self.front.generation != 0).then_some(SlotFrame {
+            buffer: &self.front,
+        })
+    }
+}
+
+/// A handle that is woken whenever a new frame is captured, without taking the frame. Get one from
+/// [`CallbackCamera::frame_notify()`] or [`ThreadedCamera::frame_notify()`].
+///
+/// Any number of handles can wait at the same time, each of them is woken by every frame.
+#[derive(Clone)]
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
+pub struct FrameNotify {
+    signal: Arc<FrameSignal>,
+}
+
+impl FrameNotify {
+    /// Blocks until a new frame is captured, or `timeout` passes. Returns whether a frame was captured.
+    #[must_use]
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.signal.wait(timeout)
+    }
+
+    /// A future that completes once a frame is captured after this call, for async code.
+    #[must_use]
+    pub fn notified(&self) -> Notified {
+        Notified {
+            signal: self.signal.clone(),
+            seen: self.signal.generation(),
+        }
+    }
+}
+
+/// The future returned by [`FrameNotify::notified()`].
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
+pub struct Notified {
+    signal: Arc<FrameSignal>,
+    seen: u64,
+}
+
+impl Future for Notified {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = match self.signal.state.lock() {
+            Ok(state) => state,
+            // nothing will ever wake us, don't hang the task
+            Err(_) => return Poll::Ready(()),
+        };
+        if state.0 != self.seen {
+            return Poll::Ready(());
+        }
+        if !state.1.iter().any(|waker| waker.will_wake(cx.waker())) {
+            state.1.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+/// A subscription to the frames of a [`CallbackCamera`], see [`subscribe()`](CallbackCamera::subscribe).
+///
+/// Every receiver gets every frame, independent of the others. The frames are shared, so they are not copied per receiver.
+/// Dropping a receiver unsubscribes it.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
+pub struct FrameReceiver {
+    slot: Arc<SubscriberSlot>,
+}
+
+impl FrameReceiver {
+    /// Takes the oldest kept frame, if there is one, without waiting.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<Arc<Buffer>> {
+        self.slot.frames.lock().ok()?.pop_front()
+    }
+
+    /// Takes the oldest kept frame, waiting for one if there is none.
+    ///
+    /// Returns `None` once the camera is dropped and every kept frame was taken.
+    #[must_use]
+    pub fn recv(&self) -> Option<Arc<Buffer>> {
+        let mut frames = self.slot.frames.lock().ok()?;
+        loop {
+            if let Some(frame) = frames.pop_front() {
+                return Some(frame);
+            }
+            if self.slot.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+            frames = self.slot.available.wait(frames).ok()?;
+        }
+    }
+
+    /// Like [`recv()`](FrameReceiver::recv), but gives up after `timeout`.
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Arc<Buffer>> {
+        let deadline = Instant::now() + timeout;
+        let mut frames = self.slot.frames.lock().ok()?;
+        loop {
+            if let Some(frame) = frames.pop_front() {
+                return Some(frame);
+            }
+            let now = Instant::now();
+            if self.slot.closed.load(Ordering::SeqCst) || now >= deadline {
+                return None;
+            }
+            frames = self
+                .slot
+                .available
+                .wait_timeout(frames, deadline - now)
+                .ok()?
+                .0;
+        }
+    }
+}
 
 /// Creates a camera that runs in a different thread that you can use a callback to access the frames of.
 /// It uses a `Arc` and a `Mutex` to ensure that this feels like a normal camera, but callback based.
@@ -50,6 +830,13 @@ type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) + Send + 'static>>>;
 /// beneficial to directly pipe the data to a new thread to process it there.
 ///
 /// Note that this does not have `WGPU` capabilities. This should be implemented in your callback.
+///
+/// To have several consumers (e.g. a preview and a recorder) get every frame, give each one a [`FrameReceiver`] from
+/// [`subscribe()`](CallbackCamera::subscribe).
+///
+/// Errors while capturing do not stop the stream. They are passed to the callback set with
+/// [`set_error_callback()`](CallbackCamera::set_error_callback), and the latest one is kept in [`last_error()`](CallbackCamera::last_error).
+/// A panic in a callback is caught too, see [`CallbackPanicPolicy`].
 /// # SAFETY
 /// The `Mutex` guarantees exclusive access to the underlying camera struct. They should be safe to
 /// impl `Send` on.
@@ -57,9 +844,22 @@ type HeldCallbackType = Arc<Mutex<Box<dyn FnMut(Buffer) + Send + 'static>>>;
 pub struct CallbackCamera {
     camera: AtomicLock<Camera>,
     frame_callback: HeldCallbackType,
+    error_callback: HeldErrorCallbackType,
     last_frame_captured: AtomicLock<Buffer>,
+    last_error: AtomicLock<Option<NokhwaError>>,
+    subscribers: Subscribers,
+    latest_frame_slot: AtomicLock<Option<Arc<LatestFrameSlot>>>,
+    callback_panics: Arc<CallbackPanics>,
+    frame_signal: Arc<FrameSignal>,
+    frame_counters: Arc<FrameCounters>,
+    frame_dispatch: FrameDispatch,
+    open_retry_policy: OpenRetryPolicy,
+    stream_watchdog: Option<StreamWatchdog>,
+    stall_monitor: Option<StallMonitor>,
     die_bool: Arc<AtomicBool>,
     current_camera: CameraInfo,
+    thread_options: ThreadOptions,
+    thread_priority_warning: Option<NokhwaError>,
     handle: AtomicLock<Option<JoinHandle<()>>>,
 }
 
@@ -85,13 +885,26 @@ impl CallbackCamera {
         Ok(CallbackCamera {
             camera: arc_camera,
             frame_callback: Arc::new(Mutex::new(Box::new(callback))),
+            error_callback: Arc::new(Mutex::new(None)),
             last_frame_captured: Arc::new(Mutex::new(Buffer::new(
                 Resolution::new(0, 0),
                 &vec![],
                 FrameFormat::GRAY,
             ))),
+            last_error: Arc::new(Mutex::new(None)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            latest_frame_slot: Arc::new(Mutex::new(None)),
+            callback_panics: Arc::new(CallbackPanics::default()),
+            frame_signal: Arc::new(FrameSignal::default()),
+            frame_counters: Arc::new(FrameCounters::default()),
+            frame_dispatch: FrameDispatch::default(),
+            open_retry_policy: OpenRetryPolicy::default(),
+            stream_watchdog: None,
+            stall_monitor: None,
             die_bool: Arc::new(Default::default()),
             current_camera,
+            thread_options: ThreadOptions::default(),
+            thread_priority_warning: None,
             handle: Arc::new(Mutex::new(None)),
         })
     }
@@ -104,17 +917,52 @@ impl CallbackCamera {
         CallbackCamera {
             camera: Arc::new(Mutex::new(camera)),
             frame_callback: Arc::new(Mutex::new(Box::new(callback))),
+            error_callback: Arc::new(Mutex::new(None)),
             last_frame_captured: Arc::new(Mutex::new(Buffer::new(
                 Resolution::new(0, 0),
                 &vec![],
                 FrameFormat::GRAY,
             ))),
+            last_error: Arc::new(Mutex::new(None)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            latest_frame_slot: Arc::new(Mutex::new(None)),
+            callback_panics: Arc::new(CallbackPanics::default()),
+            frame_signal: Arc::new(FrameSignal::default()),
+            frame_counters: Arc::new(FrameCounters::default()),
+            frame_dispatch: FrameDispatch::default(),
+            open_retry_policy: OpenRetryPolicy::default(),
+            stream_watchdog: None,
+            stall_monitor: None,
             die_bool: Arc::new(Default::default()),
             current_camera,
+            thread_options: ThreadOptions::default(),
+            thread_priority_warning: None,
             handle: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Creates a [`LatestFrameReader`], which reads the latest frame from a slot instead of the callback or a queue. It
+    /// replaces the reader created before, if any, which then sees no new frames.
+    ///
+    /// Call this before [`open_stream()`](CallbackCamera::open_stream), so the reader sees the first frame.
+    /// # Errors
+    /// If the lock on the slot is poisoned, this will error.
+    pub fn latest_frame_reader(&self) -> Result<LatestFrameReader, NokhwaError> {
+        let slot = Arc::new(LatestFrameSlot {
+            back: Mutex::new(SlotBuffer::default()),
+            middle: Mutex::new((SlotBuffer::default(), false)),
+            generation: AtomicU64::new(0),
+        });
+        *self
+            .latest_frame_slot
+            .lock()
+            .map_err(|why| NokhwaError::GeneralError(why.to_string()))? = Some(slot.clone());
+        Ok(LatestFrameReader {
+            slot,
+            front: SlotBuffer::default(),
+        })
+    }
+
     /// Gets the current Camera's index.
     pub fn index(&self) -> &CameraIndex {
         &self.current_camera.index()
@@ -438,10 +1286,31 @@ impl CallbackCamera {
             .set_camera_control(id, control)
     }
 
+    /// Sets several controls at once, returning the result of each in the same order as `controls`.
+    /// See [`set_camera_controls()`](nokhwa_core::traits::CaptureTrait::set_camera_controls) for the order they are applied in.
+    /// The camera is locked for the whole batch, so no other call lands between them.
+    /// # Errors
+    /// If the camera lock is poisoned, this will error.
+    pub fn set_camera_controls(
+        &mut self,
+        controls: &[(KnownCameraControl, ControlValueSetter)],
+    ) -> Result<Vec<Result<(), NokhwaError>>, NokhwaError> {
+        Ok(self
+            .camera
+            .lock()
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "Camera Controls".to_string(),
+                value: format!("{controls:?}"),
+                error: why.to_string(),
+            })?
+            .set_camera_controls(controls))
+    }
+
     /// Will open the camera stream with set parameters. This will be called internally if you try and call [`frame()`](crate::Camera::frame()) before you call [`open_stream()`](crate::Camera::open_stream()).
     /// The callback will be called every frame.
     /// # Errors
-    /// If the specific backend fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error.
+    /// If the specific backend fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error,
+    /// after retrying as the [`OpenRetryPolicy`] says.
     pub fn open_stream(&mut self) -> Result<(), NokhwaError> {
         let mut handle_lock = self
             .handle
@@ -451,22 +1320,63 @@ impl CallbackCamera {
                 error: why.to_string(),
             })?;
         if handle_lock.is_none() {
-            self.camera
+            let mut camera = self
+                .camera
                 .lock()
                 .map_err(|why| NokhwaError::SetPropertyError {
                     property: "camera".to_string(),
                     value: "callback".to_string(),
                     error: why.to_string(),
-                })?
-                .open_stream()?;
+                })?;
+            self.open_retry_policy.run(|| camera.open_stream())?;
+            drop(camera);
             let die_bool_clone = self.die_bool.clone();
             let camera_clone = self.camera.clone();
             let last_frame = self.last_frame_captured.clone();
             let callback = self.frame_callback.clone();
-            let handle = std::thread::spawn(move || {
-                camera_frame_thread_loop(camera_clone, callback, last_frame, die_bool_clone)
-            });
+            let last_error = self.last_error.clone();
+            let error_callback = self.error_callback.clone();
+            let subscribers = self.subscribers.clone();
+            let latest_frame_slot = self.latest_frame_slot.clone();
+            let callback_panics = self.callback_panics.clone();
+            let frame_signal = self.frame_signal.clone();
+            let frame_counters = self.frame_counters.clone();
+            let frame_dispatch = self.frame_dispatch.clone();
+            let (handle, thread_priority_warning) = self.thread_options.spawn(move || {
+                camera_frame_thread_loop(
+                    camera_clone,
+                    callback,
+                    last_frame,
+                    error_callback,
+                    last_error,
+                    subscribers,
+                    latest_frame_slot,
+                    callback_panics,
+                    frame_signal,
+                    frame_counters,
+                    frame_dispatch,
+                    die_bool_clone,
+                )
+            })?;
             *handle_lock = Some(handle);
+            drop(handle_lock);
+            self.thread_priority_warning = thread_priority_warning;
+            self.frame_counters.arm();
+            self.stall_monitor = self.stream_watchdog.map(|watchdog| {
+                let last_error = self.last_error.clone();
+                let error_callback = self.error_callback.clone();
+                let callback_panics = self.callback_panics.clone();
+                spawn_stall_monitor(watchdog, self.frame_counters.clone(), move |why| {
+                    if let Ok(mut last) = last_error.lock() {
+                        *last = Some(why.clone());
+                    }
+                    if let Ok(mut error_cb) = error_callback.lock() {
+                        if let Some(cb) = error_cb.as_mut() {
+                            callback_panics.run(|| cb(why));
+                        }
+                    }
+                })
+            });
             Ok(())
         } else {
             Err(NokhwaError::OpenStreamError(
@@ -490,27 +1400,199 @@ impl CallbackCamera {
         Ok(())
     }
 
-    /// Polls the camera for a frame, analogous to [`Camera::frame`](crate::Camera::frame)
+    /// Sets the watchdog for a stream that stopped delivering frames, see [`StreamWatchdog`]. `None` (the default) disables it.
+    ///
+    /// Stalls are reported through the error callback. This applies from the next [`open_stream()`](CallbackCamera::open_stream) on.
+    pub fn set_stream_watchdog(&mut self, watchdog: Option<StreamWatchdog>) {
+        self.stream_watchdog = watchdog;
+    }
+
+    /// Sets how [`open_stream()`](CallbackCamera::open_stream) retries a failed open, see [`OpenRetryPolicy`]. By default, it
+    /// does not.
+    pub fn set_open_retry_policy(&mut self, policy: OpenRetryPolicy) {
+        self.open_retry_policy = policy;
+    }
+
+    /// Sets where frames are delivered, see [`FrameDispatch`]. By default, the frame callback is called on the capture thread.
+    ///
+    /// This applies from the next [`open_stream()`](CallbackCamera::open_stream) on.
+    pub fn set_frame_dispatch(&mut self, dispatch: FrameDispatch) {
+        self.frame_dispatch = dispatch;
+    }
+
+    /// Sets a callback that is called with every error the capture thread runs into, e.g. a frame that failed to decode.
+    /// This replaces the previous one.
     /// # Errors
-    /// This will error if the camera fails to capture a frame.
-    pub fn poll_frame(&mut self) -> Result<Buffer, NokhwaError> {
-        let frame = self
-            .camera
-            .lock()
-            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?
-            .frame()?;
+    /// If the lock on the error callback is poisoned, this will error.
+    pub fn set_error_callback(
+        &mut self,
+        callback: impl FnMut(NokhwaError) + Send + 'static,
+    ) -> Result<(), NokhwaError> {
         *self
-            .last_frame_captured
+            .error_callback
             .lock()
-            .map_err(|why| NokhwaError::GeneralError(why.to_string()))? = frame.clone();
-        Ok(frame)
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "error_callback".to_string(),
+                value: "callback".to_string(),
+                error: why.to_string(),
+            })? = Some(Box::new(callback));
+        Ok(())
     }
 
-    /// Gets the last frame captured by the camera.
-    pub fn last_frame(&self) -> Result<Buffer, NokhwaError> {
-        Ok(self
-            .last_frame_captured
-            .lock()
+    /// Sets the name and priority of the capture thread. This takes effect the next time the stream is opened.
+    pub fn set_thread_options(&mut self, options: ThreadOptions) {
+        self.thread_options = options;
+    }
+
+    /// Gets why the priority from [`set_thread_options()`](CallbackCamera::set_thread_options) could not be set on the
+    /// running capture thread, if it could not. The thread runs at its default priority then.
+    #[must_use]
+    pub fn thread_priority_warning(&self) -> Option<&NokhwaError> {
+        self.thread_priority_warning.as_ref()
+    }
+
+    /// Sets what happens when the frame or error callback panics, see [`CallbackPanicPolicy`].
+    /// # Errors
+    /// If the lock on the policy is poisoned, this will error.
+    pub fn set_callback_panic_policy(
+        &mut self,
+        policy: CallbackPanicPolicy,
+    ) -> Result<(), NokhwaError> {
+        *self
+            .callback_panics
+            .policy
+            .lock()
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "callback_panic_policy".to_string(),
+                value: format!("{policy:?}"),
+                error: why.to_string(),
+            })? = policy;
+        Ok(())
+    }
+
+    /// Gets the message of the last panic caught in the frame or error callback, if any.
+    /// # Errors
+    /// If the lock on the last panic is poisoned, this will error.
+    pub fn last_panic(&self) -> Result<Option<String>, NokhwaError> {
+        Ok(self
+            .callback_panics
+            .last_panic
+            .lock()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "last_panic".to_string(),
+                error: why.to_string(),
+            })?
+            .clone())
+    }
+
+    /// Gets the last error the capture thread ran into, if any.
+    /// # Errors
+    /// If the lock on the last error is poisoned, this will error.
+    pub fn last_error(&self) -> Result<Option<NokhwaError>, NokhwaError> {
+        Ok(self
+            .last_error
+            .lock()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "last_error".to_string(),
+                error: why.to_string(),
+            })?
+            .clone())
+    }
+
+    /// Blocks until the capture thread captures a new frame, or `timeout` passes, without taking the frame.
+    /// Returns whether a frame was captured. Read it with [`last_frame()`](CallbackCamera::last_frame).
+    #[must_use]
+    pub fn wait_for_frame(&self, timeout: Duration) -> bool {
+        self.frame_signal.wait(timeout)
+    }
+
+    /// Gets a [`FrameNotify`] handle that is woken by every new frame, e.g. to wait for frames in async code.
+    #[must_use]
+    pub fn frame_notify(&self) -> FrameNotify {
+        FrameNotify {
+            signal: self.frame_signal.clone(),
+        }
+    }
+
+    /// Gets a snapshot of the frames the capture thread received. It is cheap enough to call every UI frame.
+    ///
+    /// Every frame is handed to the callback, so [`frames_dropped`](FrameStats::frames_dropped) stays `0`.
+    #[must_use]
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_counters.snapshot()
+    }
+
+    /// Sets the counters of [`frame_stats()`](Self::frame_stats) back to `0`, and returns what they were, e.g. to report
+    /// them once per monitoring interval. It is safe to call while streaming: every frame is counted in exactly one interval.
+    ///
+    /// [`last_frame_at`](FrameStats::last_frame_at) and [`stalled`](FrameStats::stalled) are cleared too, until the next
+    /// frame or stall. There is no frame rate window to reset, see [`FrameStats`].
+    pub fn reset_frame_stats(&self) -> FrameStats {
+        self.frame_counters.reset()
+    }
+
+    /// Subscribes to the frames captured from now on. Each [`FrameReceiver`] gets every frame, independent of the frame
+    /// callback and the other receivers, and keeps as many as `buffering` says while they are not taken.
+    /// # Errors
+    /// If the lock on the subscribers is poisoned, this will error.
+    pub fn subscribe(&self, buffering: SubscriberBuffering) -> Result<FrameReceiver, NokhwaError> {
+        let capacity = match buffering {
+            SubscriberBuffering::LatestOnly => 1,
+            SubscriberBuffering::Bounded(frames) => frames.max(1),
+        };
+        let slot = Arc::new(SubscriberSlot {
+            frames: Mutex::new(VecDeque::with_capacity(capacity)),
+            available: Condvar::new(),
+            capacity,
+            closed: AtomicBool::new(false),
+        });
+        self.subscribers
+            .lock()
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "subscribers".to_string(),
+                value: "subscriber".to_string(),
+                error: why.to_string(),
+            })?
+            .push(Arc::downgrade(&slot));
+        Ok(FrameReceiver { slot })
+    }
+
+    /// Clears the last error, so [`last_error()`](CallbackCamera::last_error) only reports new ones.
+    /// # Errors
+    /// If the lock on the last error is poisoned, this will error.
+    pub fn clear_last_error(&mut self) -> Result<(), NokhwaError> {
+        *self
+            .last_error
+            .lock()
+            .map_err(|why| NokhwaError::SetPropertyError {
+                property: "last_error".to_string(),
+                value: "None".to_string(),
+                error: why.to_string(),
+            })? = None;
+        Ok(())
+    }
+
+    /// Polls the camera for a frame, analogous to [`Camera::frame`](crate::Camera::frame)
+    /// # Errors
+    /// This will error if the camera fails to capture a frame.
+    pub fn poll_frame(&mut self) -> Result<Buffer, NokhwaError> {
+        let frame = self
+            .camera
+            .lock()
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?
+            .frame()?;
+        *self
+            .last_frame_captured
+            .lock()
+            .map_err(|why| NokhwaError::GeneralError(why.to_string()))? = frame.clone();
+        Ok(frame)
+    }
+
+    /// Gets the last frame captured by the camera.
+    pub fn last_frame(&self) -> Result<Buffer, NokhwaError> {
+        Ok(self
+            .last_frame_captured
+            .lock()
             .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?
             .clone())
     }
@@ -527,10 +1609,54 @@ impl CallbackCamera {
             .is_stream_open())
     }
 
-    /// Will drop the stream.
+    /// Will drop the stream, stopping the capture thread first. The stream can be opened again afterwards.
     /// # Errors
     /// Please check the `Quirks` section of each backend.
     pub fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+        self.stall_monitor = None;
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|why| NokhwaError::StreamShutdownError(why.to_string()))?
+            .take();
+        if let Some(handle) = handle {
+            self.die_bool.store(true, Ordering::SeqCst);
+            let _ = handle.join();
+            self.die_bool.store(false, Ordering::SeqCst);
+        }
+        self.camera
+            .lock()
+            .map_err(|why| NokhwaError::StreamShutdownError(why.to_string()))?
+            .stop_stream()
+    }
+
+    /// Tears the camera down: signals the capture thread to stop, waits up to `timeout` for it, stops the stream, and
+    /// closes every [`FrameReceiver`], so their [`recv()`](FrameReceiver::recv) returns once the kept frames are taken.
+    ///
+    /// Dropping the camera does the same with [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    /// # Errors
+    /// If the capture thread did not stop within `timeout` (it is then left to finish on its own, and the stream is not stopped),
+    /// it panicked, or the stream could not be stopped, this will error.
+    pub fn shutdown(&mut self, timeout: Duration) -> Result<(), NokhwaError> {
+        self.stall_monitor = None;
+        self.die_bool.store(true, Ordering::SeqCst);
+        let handle = self
+            .handle
+            .lock()
+            .map_err(|why| NokhwaError::StreamShutdownError(why.to_string()))?
+            .take();
+        let joined = match handle {
+            Some(handle) => join_with_timeout(handle, timeout),
+            None => Ok(()),
+        };
+
+        if let Ok(subscribers) = self.subscribers.lock() {
+            for slot in subscribers.iter().filter_map(Weak::upgrade) {
+                slot.close();
+            }
+        }
+
+        joined?;
         self.camera
             .lock()
             .map_err(|why| NokhwaError::StreamShutdownError(why.to_string()))?
@@ -540,8 +1666,7 @@ impl CallbackCamera {
 
 impl Drop for CallbackCamera {
     fn drop(&mut self) {
-        let _stop_stream_err = self.stop_stream();
-        self.die_bool.store(true, Ordering::SeqCst);
+        let _shutdown_err = self.shutdown(DEFAULT_SHUTDOWN_TIMEOUT);
     }
 }
 
@@ -549,17 +1674,108 @@ fn camera_frame_thread_loop(
     camera: AtomicLock<Camera>,
     frame_callback: HeldCallbackType,
     last_frame_captured: AtomicLock<Buffer>,
+    error_callback: HeldErrorCallbackType,
+    last_error: AtomicLock<Option<NokhwaError>>,
+    subscribers: Subscribers,
+    latest_frame_slot: AtomicLock<Option<Arc<LatestFrameSlot>>>,
+    callback_panics: Arc<CallbackPanics>,
+    frame_signal: Arc<FrameSignal>,
+    frame_counters: Arc<FrameCounters>,
+    frame_dispatch: FrameDispatch,
     die_bool: Arc<AtomicBool>,
 ) {
+    let mut dispatcher = Dispatcher::new(frame_dispatch, &frame_callback, &callback_panics);
+    let report_error = |why: NokhwaError| {
+        if let Ok(mut last) = last_error.lock() {
+            *last = Some(why.clone());
+        }
+        if let Ok(mut error_cb) = error_callback.lock() {
+            if let Some(cb) = error_cb.as_mut() {
+                return callback_panics.run(|| cb(why)).is_none();
+            }
+        }
+        true
+    };
+
     loop {
-        if let Ok(mut camera) = camera.lock() {
-            if let Ok(frame) = camera.frame() {
-                if let Ok(mut last_frame) = last_frame_captured.lock() {
-                    *last_frame = frame.clone();
-                    if let Ok(mut cb) = frame_callback.lock() {
-                        cb(frame);
+        let locked = match camera.try_lock() {
+            Ok(camera) => Ok(camera),
+            // the camera is busy with something else through the wrapper, which is not a stall
+            Err(TryLockError::WouldBlock) => {
+                let paused_at = frame_counters.pause();
+                let locked = camera.lock();
+                frame_counters.resume(paused_at);
+                locked
+            }
+            Err(TryLockError::Poisoned(why)) => Err(why),
+        };
+        if let Ok(mut camera) = locked {
+            // whether the callbacks ran without panicking
+            let mut callbacks_ok = match camera.frame() {
+                Ok(frame) => {
+                    frame_counters.received(frame.buffer().len());
+                    if let Ok(mut subscribers) = subscribers.lock() {
+                        if !subscribers.is_empty() {
+                            let shared = Arc::new(frame.clone());
+                            // the slots of dropped receivers are removed here
+                            subscribers.retain(|slot| match slot.upgrade() {
+                                Some(slot) => {
+                                    slot.push(shared.clone());
+                                    true
+                                }
+                                None => false,
+                            });
+                        }
                     }
+                    if let Ok(latest_frame_slot) = latest_frame_slot.lock() {
+                        if let Some(slot) = latest_frame_slot.as_ref() {
+                            slot.publish(&frame);
+                        }
+                    }
+                    let mut panicked = None;
+                    let mut undelivered = None;
+                    if let Ok(mut last_frame) = last_frame_captured.lock() {
+                        *last_frame = frame.clone();
+                        frame_signal.notify();
+                        if dispatcher.in_thread() {
+                            if let Ok(mut cb) = frame_callback.lock() {
+                                panicked = callback_panics.run(|| cb(frame));
+                            }
+                        } else {
+                            match dispatcher.dispatch(FrameEvent::Frame(frame), &callback_panics) {
+                                Ok(message) => panicked = message,
+                                Err(why) => undelivered = Some(why),
+                            }
+                        }
+                    }
+                    // not being able to dispatch is not the callback's fault, so it does not stop the stream
+                    if let Some(why) = undelivered {
+                        report_error(why);
+                    }
+                    match panicked {
+                        Some(message) => {
+                            report_error(NokhwaError::GeneralError(format!(
+                                "Frame callback panicked: {message}"
+                            )));
+                            false
+                        }
+                        None => true,
+                    }
+                }
+                Err(why) => {
+                    if !dispatcher.in_thread() {
+                        let _ =
+                            dispatcher.dispatch(FrameEvent::Error(why.clone()), &callback_panics);
+                    }
+                    report_error(why)
                 }
+            };
+            if let Err(why) = frame_counters.restart_if_requested(&mut camera) {
+                callbacks_ok &= report_error(why);
+            }
+            if !callbacks_ok && !callback_panics.keep_streaming() {
+                let _stop_stream_err = camera.stop_stream();
+                break;
             }
         }
         if die_bool.load(Ordering::SeqCst) {
@@ -567,3 +1783,852 @@ fn camera_frame_thread_loop(
         }
     }
 }
+
+enum ThreadedCameraRequest {
+    CameraRequest(RequestedFormat, Sender<Result<CameraFormat, NokhwaError>>),
+    CameraControl(
+        KnownCameraControl,
+        ControlValueSetter,
+        Sender<Result<(), NokhwaError>>,
+    ),
+}
+
+#[derive(Default)]
+struct LatestFrame {
+    raw: Option<Buffer>,
+    decoded: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>,
+    captured_at: Option<Instant>,
+    // counts up from 1 in capture order, so a slow decode worker never replaces a newer frame
+    sequence: u64,
+}
+
+struct DecodeJob {
+    frame: Buffer,
+    captured_at: Instant,
+    sequence: u64,
+}
+
+// Frames waiting for a decode worker. When it is full, the oldest one is dropped, as only the latest frame is kept anyway.
+struct DecodeQueue {
+    jobs: Mutex<VecDeque<DecodeJob>>,
+    available: Condvar,
+    capacity: usize,
+    closed: AtomicBool,
+}
+
+impl DecodeQueue {
+    // Returns how many queued jobs were dropped to make room.
+    fn push(&self, job: DecodeJob) -> u64 {
+        let mut dropped = 0;
+        if let Ok(mut jobs) = self.jobs.lock() {
+            while jobs.len() >= self.capacity {
+                jobs.pop_front();
+                dropped += 1;
+            }
+            jobs.push_back(job);
+        }
+        self.available.notify_one();
+        dropped
+    }
+
+    fn pop(&self) -> Option<DecodeJob> {
+        let mut jobs = self.jobs.lock().ok()?;
+        loop {
+            if let Some(job) = jobs.pop_front() {
+                return Some(job);
+            }
+            if self.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+            jobs = self.available.wait(jobs).ok()?;
+        }
+    }
+
+    fn close(&self) {
+        // taking the lock makes sure a worker is either waiting already, or sees the flag
+        let _jobs = self.jobs.lock();
+        self.closed.store(true, Ordering::SeqCst);
+        self.available.notify_all();
+    }
+}
+
+/// A camera that captures and decodes frames on its own thread, meant to be polled from e.g. a UI thread.
+///
+/// The capture thread keeps the latest frame in a double buffer: a new frame is captured and decoded into the back
+/// buffer, then swapped with the front one. [`poll_frame()`](ThreadedCamera::poll_frame) and [`poll_frame_raw()`](ThreadedCamera::poll_frame_raw)
+/// only take the front buffer, so they never wait on the camera.
+///
+/// The capture thread owns the [`Camera`]. Format and control changes are sent to it and applied between frames.
+///
+/// By default, the capture thread decodes each frame before capturing the next one. To keep slow decoding (e.g. large MJPEG
+/// frames) from holding up the camera, create it with [`with_decode_workers()`](ThreadedCamera::with_decode_workers) instead.
+///
+/// Dropping the `ThreadedCamera` stops the capture thread, waits for it to finish and stops the stream.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-threaded")))]
+pub struct ThreadedCamera {
+    current_camera: CameraInfo,
+    latest_frame: AtomicLock<LatestFrame>,
+    last_error: AtomicLock<Option<NokhwaError>>,
+    requests: Sender<ThreadedCameraRequest>,
+    frame_signal: Arc<FrameSignal>,
+    frame_counters: Arc<FrameCounters>,
+    max_frame_age: Option<Duration>,
+    stall_monitor: Option<StallMonitor>,
+    die_bool: Arc<AtomicBool>,
+    thread_priority_warning: Option<NokhwaError>,
+    handle: Option<JoinHandle<Result<(), NokhwaError>>>,
+}
+
+impl ThreadedCamera {
+    /// Create a new `ThreadedCamera` from a [`CameraIndex`] and [`format`], and start capturing.
+    ///
+    /// # Errors
+    /// This will error if you either have a bad platform configuration (e.g. `input-v4l` but not on linux), the backend cannot create the camera (e.g. permission denied)
+    /// or the stream could not be opened.
+    pub fn new(index: CameraIndex, format: RequestedFormat) -> Result<Self, NokhwaError> {
+        Self::with_custom(Camera::new(index, format)?)
+    }
+
+    /// Creates a `ThreadedCamera` from an existing [`Camera`], and start capturing.
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the stream could not be opened, this will error.
+    pub fn with_custom(camera: Camera) -> Result<Self, NokhwaError> {
+        Self::with_decode_workers(camera, 0)
+    }
+
+    /// Creates a `ThreadedCamera` from an existing [`Camera`] like [`with_custom()`](ThreadedCamera::with_custom), but frames are
+    /// decoded by `workers` threads of their own, so the capture thread only captures. `0` decodes on the capture thread.
+    ///
+    /// - Up to `workers` captured frames wait for a free worker. When decoding falls further behind, the oldest waiting
+    ///   frame is dropped.
+    /// - Workers may finish out of order. A decoded frame only replaces the latest one if it was captured after it, so
+    ///   [`poll_frame()`](ThreadedCamera::poll_frame) never goes back in time, but a frame may be skipped.
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the stream could not be opened, this will error.
+    pub fn with_decode_workers(camera: Camera, workers: usize) -> Result<Self, NokhwaError> {
+        Self::with_thread_options(camera, workers, ThreadOptions::default())
+    }
+
+    /// Creates a `ThreadedCamera` like [`with_decode_workers()`](ThreadedCamera::with_decode_workers), with the name and
+    /// priority of the capture thread set by `options`. The decode workers get the same priority, and the name with a
+    /// `-decode-N` suffix.
+    ///
+    /// If a priority could not be set, the camera still starts, see [`thread_priority_warning()`](ThreadedCamera::thread_priority_warning).
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the stream could not be opened or the capture thread could not be spawned, this will error.
+    pub fn with_thread_options(
+        camera: Camera,
+        workers: usize,
+        options: ThreadOptions,
+    ) -> Result<Self, NokhwaError> {
+        Self::with_retry_policy(camera, workers, options, &OpenRetryPolicy::default())
+    }
+
+    /// Creates a `ThreadedCamera` like [`with_thread_options()`](ThreadedCamera::with_thread_options), retrying to open the
+    /// stream as `retry` says.
+    ///
+    /// You **must** have set a format beforehand.
+    /// # Errors
+    /// If the stream could not be opened within the attempts of `retry`, or the capture thread could not be spawned, this will
+    /// error.
+    pub fn with_retry_policy(
+        mut camera: Camera,
+        workers: usize,
+        options: ThreadOptions,
+        retry: &OpenRetryPolicy,
+    ) -> Result<Self, NokhwaError> {
+        let current_camera = camera.info().clone();
+        retry.run(|| camera.open_stream())?;
+
+        let latest_frame = Arc::new(Mutex::new(LatestFrame::default()));
+        let last_error = Arc::new(Mutex::new(None));
+        let frame_signal = Arc::new(FrameSignal::default());
+        let frame_counters = Arc::new(FrameCounters::default());
+        let die_bool = Arc::new(AtomicBool::default());
+        let (requests, request_receiver) = channel();
+
+        let latest_frame_clone = latest_frame.clone();
+        let last_error_clone = last_error.clone();
+        let frame_signal_clone = frame_signal.clone();
+        let frame_counters_clone = frame_counters.clone();
+        let die_bool_clone = die_bool.clone();
+        let worker_options = options.clone();
+        let (handle, thread_priority_warning) = options.spawn(move || {
+            threaded_camera_loop(
+                camera,
+                latest_frame_clone,
+                last_error_clone,
+                request_receiver,
+                frame_signal_clone,
+                frame_counters_clone,
+                die_bool_clone,
+                workers,
+                worker_options,
+            )
+        })?;
+
+        Ok(ThreadedCamera {
+            current_camera,
+            latest_frame,
+            last_error,
+            requests,
+            frame_signal,
+            frame_counters,
+            max_frame_age: None,
+            stall_monitor: None,
+            die_bool,
+            thread_priority_warning,
+            handle: Some(handle),
+        })
+    }
+
+    /// Opens a `ThreadedCamera` for each of `requests` at the same time, instead of one after another, so opening several
+    /// slow devices takes about as long as the slowest one.
+    ///
+    /// The results are in the order of `requests`. A camera that fails to open does not affect the others.
+    #[must_use]
+    pub fn open_all(
+        requests: impl IntoIterator<Item = (CameraIndex, RequestedFormat)>,
+    ) -> Vec<Result<Self, NokhwaError>> {
+        std::thread::scope(|scope| {
+            let opening = requests
+                .into_iter()
+                .map(|(index, format)| {
+                    (
+                        index.to_string(),
+                        scope.spawn(move || Self::new(index, format)),
+                    )
+                })
+                .collect::<Vec<_>>();
+            opening
+                .into_iter()
+                .map(|(index, handle)| {
+                    handle.join().unwrap_or_else(|payload| {
+                        Err(NokhwaError::OpenDeviceError(
+                            index,
+                            format!("Opening panicked: {}", panic_message(payload.as_ref())),
+                        ))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Takes a new frame from each of `cameras`, like [`poll_frame_raw()`](ThreadedCamera::poll_frame_raw), waiting for
+    /// the ones that have none yet. All of them share the same `timeout`, so this returns after at most `timeout`.
+    ///
+    /// The results are in the order of `cameras`. A camera that has no frame by then does not affect the others.
+    #[must_use]
+    pub fn capture_all(
+        cameras: &[ThreadedCamera],
+        timeout: Duration,
+    ) -> Vec<Result<Buffer, NokhwaError>> {
+        let deadline = Instant::now() + timeout;
+        // the cameras capture on their own threads, so waiting for one does not hold up the others
+        cameras
+            .iter()
+            .map(|camera| loop {
+                match camera.poll_frame_raw() {
+                    Ok(Some(frame)) => break Ok(frame),
+                    // a fresh frame may still arrive before the deadline
+                    Ok(None) | Err(NokhwaError::OnlyStaleFrames { .. }) => {}
+                    Err(why) => break Err(why),
+                }
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() || !camera.wait_for_frame(remaining) {
+                    break match camera.last_error() {
+                        Ok(Some(why)) => Err(why),
+                        _ => Err(NokhwaError::TimeoutError(format!(
+                            "No frame from {} within {timeout:?}",
+                            camera.info().human_name()
+                        ))),
+                    };
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the camera information such as Name and Index as a [`CameraInfo`].
+    pub fn info(&self) -> &CameraInfo {
+        &self.current_camera
+    }
+
+    /// Gets why the priority from [`with_thread_options()`](ThreadedCamera::with_thread_options) could not be set on the
+    /// capture thread, if it could not. For the decode workers, this is reported in [`last_error()`](ThreadedCamera::last_error).
+    #[must_use]
+    pub fn thread_priority_warning(&self) -> Option<&NokhwaError> {
+        self.thread_priority_warning.as_ref()
+    }
+
+    /// Gets the latest decoded frame, if a new one was captured since the last call. This does not wait for the camera.
+    /// # Errors
+    /// If the lock on the latest frame is poisoned, or the frame is older than the [maximum frame age](ThreadedCamera::set_max_frame_age),
+    /// this will error.
+    pub fn poll_frame(&self) -> Result<Option<ImageBuffer<Rgb<u8>, Vec<u8>>>, NokhwaError> {
+        self.take_fresh(|latest_frame| latest_frame.decoded.take())
+    }
+
+    /// Gets the latest undecoded frame, if a new one was captured since the last call. This does not wait for the camera.
+    /// # Errors
+    /// If the lock on the latest frame is poisoned, or the frame is older than the [maximum frame age](ThreadedCamera::set_max_frame_age),
+    /// this will error.
+    pub fn poll_frame_raw(&self) -> Result<Option<Buffer>, NokhwaError> {
+        self.take_fresh(|latest_frame| latest_frame.raw.take())
+    }
+
+    /// Sets how old a frame may be when it is polled, e.g. for overlays where an old frame is worse than none. `None` (the
+    /// default) allows any age.
+    ///
+    /// A frame that is older when [`poll_frame()`](ThreadedCamera::poll_frame) or [`poll_frame_raw()`](ThreadedCamera::poll_frame_raw)
+    /// would return it is discarded (and counted in [`frame_stats()`](ThreadedCamera::frame_stats)) instead, and the call
+    /// errors with [`NokhwaError::OnlyStaleFrames`]. Only the latest frame is kept, so there is never an older, fresh one.
+    pub fn set_max_frame_age(&mut self, max_age: Option<Duration>) {
+        self.max_frame_age = max_age;
+    }
+
+    // Takes a frame out of the latest one with `take`, unless it is older than `max_frame_age`, in which case it is discarded.
+    fn take_fresh<T>(
+        &self,
+        take: impl FnOnce(&mut LatestFrame) -> Option<T>,
+    ) -> Result<Option<T>, NokhwaError> {
+        let mut latest_frame = self
+            .latest_frame
+            .lock()
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+        let has_frame = latest_frame.raw.is_some() || latest_frame.decoded.is_some();
+        if let (Some(max_age), Some(captured_at), true) =
+            (self.max_frame_age, latest_frame.captured_at, has_frame)
+        {
+            let age = captured_at.elapsed();
+            if age > max_age {
+                // the raw and decoded frames are the same one, so both go
+                latest_frame.raw = None;
+                latest_frame.decoded = None;
+                self.frame_counters.too_old();
+                return Err(NokhwaError::OnlyStaleFrames { age, max_age });
+            }
+        }
+        Ok(take(&mut latest_frame))
+    }
+
+    /// Blocks until a new frame is captured, or `timeout` passes, without taking the frame.
+    /// Returns whether a frame was captured. Take it with [`poll_frame()`](ThreadedCamera::poll_frame).
+    #[must_use]
+    pub fn wait_for_frame(&self, timeout: Duration) -> bool {
+        self.frame_signal.wait(timeout)
+    }
+
+    /// Gets a [`FrameNotify`] handle that is woken by every new frame, e.g. to wait for frames in async code.
+    #[must_use]
+    pub fn frame_notify(&self) -> FrameNotify {
+        FrameNotify {
+            signal: self.frame_signal.clone(),
+        }
+    }
+
+    /// Gets a snapshot of the frames the capture thread received. It is cheap enough to call every UI frame.
+    ///
+    /// A frame counts as dropped if a newer one replaced it before [`poll_frame()`](ThreadedCamera::poll_frame) or
+    /// [`poll_frame_raw()`](ThreadedCamera::poll_frame_raw) took it, or while it waited for a decode worker.
+    #[must_use]
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_counters.snapshot()
+    }
+
+    /// Sets the counters of [`frame_stats()`](Self::frame_stats) back to `0`, and returns what they were, e.g. to report
+    /// them once per monitoring interval. It is safe to call while streaming: every frame is counted in exactly one interval.
+    ///
+    /// [`last_frame_at`](FrameStats::last_frame_at) and [`stalled`](FrameStats::stalled) are cleared too, until the next
+    /// frame or stall. There is no frame rate window to reset, see [`FrameStats`].
+    pub fn reset_frame_stats(&self) -> FrameStats {
+        self.frame_counters.reset()
+    }
+
+    /// How long ago the latest frame was captured, or `None` if no frame was captured yet.
+    /// # Errors
+    /// If the lock on the latest frame is poisoned, this will error.
+    pub fn last_frame_age(&self) -> Result<Option<Duration>, NokhwaError> {
+        Ok(self
+            .latest_frame
+            .lock()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "last_frame_age".to_string(),
+                error: why.to_string(),
+            })?
+            .captured_at
+            .map(|captured_at| captured_at.elapsed()))
+    }
+
+    /// Gets the last error the capture thread ran into, if any.
+    /// # Errors
+    /// If the lock on the last error is poisoned, this will error.
+    pub fn last_error(&self) -> Result<Option<NokhwaError>, NokhwaError> {
+        Ok(self
+            .last_error
+            .lock()
+            .map_err(|why| NokhwaError::GetPropertyError {
+                property: "last_error".to_string(),
+                error: why.to_string(),
+            })?
+            .clone())
+    }
+
+    /// Will set the current [`CameraFormat`], using a [`RequestedFormat.`] The capture thread applies it between frames,
+    /// and this waits for it to do so.
+    ///
+    /// This will return the new [`CameraFormat`]
+    /// # Errors
+    /// If nothing fits the requested criteria or the capture thread has stopped, this will return an error.
+    pub fn set_camera_request(
+        &self,
+        request: RequestedFormat,
+    ) -> Result<CameraFormat, NokhwaError> {
+        let (sender, receiver) = channel();
+        self.send_request(
+            ThreadedCameraRequest::CameraRequest(request, sender),
+            receiver,
+            "CameraFormat",
+        )
+    }
+
+    /// Sets the control to `control` in the camera. The capture thread applies it between frames, and this waits for it to do so.
+    /// # Errors
+    /// If the `control` is not supported, the value is invalid, there was an error setting the control or the capture thread has stopped,
+    /// this will error.
+    pub fn set_camera_control(
+        &self,
+        id: KnownCameraControl,
+        control: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        let (sender, receiver) = channel();
+        self.send_request(
+            ThreadedCameraRequest::CameraControl(id, control, sender),
+            receiver,
+            "Camera Control",
+        )
+    }
+
+    fn send_request<T>(
+        &self,
+        request: ThreadedCameraRequest,
+        receiver: Receiver<Result<T, NokhwaError>>,
+        property: &str,
+    ) -> Result<T, NokhwaError> {
+        let thread_stopped = |why: String| NokhwaError::SetPropertyError {
+            property: property.to_string(),
+            value: "request".to_string(),
+            error: why,
+        };
+        self.requests
+            .send(request)
+            .map_err(|why| thread_stopped(why.to_string()))?;
+        receiver
+            .recv()
+            .map_err(|why| thread_stopped(why.to_string()))?
+    }
+
+    /// Stops the capture thread, waits for it to finish, and stops the stream. This is [`shutdown()`](ThreadedCamera::shutdown)
+    /// with [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    /// # Errors
+    /// If the capture thread did not stop in time or panicked, or the stream could not be stopped, this will error.
+    pub fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+        self.shutdown(DEFAULT_SHUTDOWN_TIMEOUT)
+    }
+
+    /// Sets the watchdog for a stream that stopped delivering frames, see [`StreamWatchdog`]. `None` (the default) disables it.
+    ///
+    /// Stalls are reported through [`last_error()`](ThreadedCamera::last_error). The watchdog starts right away, its clock
+    /// starting now.
+    pub fn set_stream_watchdog(&mut self, watchdog: Option<StreamWatchdog>) {
+        // the old one is joined before the new one starts
+        self.stall_monitor = None;
+        self.stall_monitor = watchdog.map(|watchdog| {
+            let last_error = self.last_error.clone();
+            spawn_stall_monitor(watchdog, self.frame_counters.clone(), move |why| {
+                set_last_error(&last_error, why);
+            })
+        });
+    }
+
+    /// Signals the capture thread to stop and waits up to `timeout` for it. The thread stops the stream before it finishes.
+    ///
+    /// Dropping the camera does the same with [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    /// # Errors
+    /// If the capture thread did not stop within `timeout` (it is then left to finish on its own), it panicked, or the stream
+    /// could not be stopped, this will error.
+    pub fn shutdown(&mut self, timeout: Duration) -> Result<(), NokhwaError> {
+        self.stall_monitor = None;
+        self.die_bool.store(true, Ordering::SeqCst);
+        match self.handle.take() {
+            Some(handle) => join_with_timeout(handle, timeout)?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ThreadedCamera {
+    fn drop(&mut self) {
+        let _shutdown_err = self.shutdown(DEFAULT_SHUTDOWN_TIMEOUT);
+    }
+}
+
+/// The frames of a [`Camera::session()`], captured on a scoped thread.
+///
+/// Iterate over it (`for frame in &mut session`) to get the frames in order. Iteration does not end by itself, break out of
+/// it when you are done.
+pub struct CaptureSession<'scope, 'env: 'scope> {
+    frames: Receiver<Result<Buffer, NokhwaError>>,
+    scope: &'scope Scope<'scope, 'env>,
+}
+
+impl<'scope, 'env> CaptureSession<'scope, 'env> {
+    /// Waits for the next frame, or the error reading it.
+    /// # Errors
+    /// If the camera failed to read a frame, or the capture thread stopped, this will error.
+    pub fn next_frame(&self) -> Result<Buffer, NokhwaError> {
+        self.frames.recv().map_err(|_| {
+            NokhwaError::ReadFrameError("The capture thread has stopped".to_string())
+        })?
+    }
+
+    /// Waits up to `timeout` for the next frame. Returns `None` if none arrived in time.
+    pub fn next_frame_timeout(&self, timeout: Duration) -> Option<Result<Buffer, NokhwaError>> {
+        self.frames.recv_timeout(timeout).ok()
+    }
+
+    /// The scope the session runs in. Threads spawned on it may borrow from outside the session, and are joined before
+    /// [`Camera::session()`] returns.
+    #[must_use]
+    pub fn scope(&self) -> &'scope Scope<'scope, 'env> {
+        self.scope
+    }
+}
+
+impl<'scope, 'env> Iterator for CaptureSession<'scope, 'env> {
+    type Item = Result<Buffer, NokhwaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.frames.recv().ok()
+    }
+}
+
+impl Camera {
+    /// Opens the stream and runs `f` with the frames it captures. When `f` returns or panics, the stream is stopped and
+    /// every thread of the session (including those spawned on [`CaptureSession::scope()`]) is joined before this returns
+    /// or the panic continues.
+    ///
+    /// Frames are read on a scoped thread, so `f` may borrow anything from the caller. If `f` falls behind, at most a few
+    /// frames are held and the capture thread waits.
+    /// # Errors
+    /// If the stream fails to open or stop, or the capture thread panics, this will error.
+    pub fn session<'env, R>(
+        &'env mut self,
+        f: impl for<'scope> FnOnce(&mut CaptureSession<'scope, 'env>) -> R,
+    ) -> Result<R, NokhwaError> {
+        self.open_stream()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, frames) = sync_channel(SESSION_FRAME_QUEUE_LEN);
+
+        let (result, capture_result) = std::thread::scope(|scope| {
+            let mut camera = StopStreamOnDrop(Some(self));
+            let capture_stop = stop.clone();
+            let capture = scope.spawn(move || {
+                session_capture_loop(&mut camera, &capture_stop, &sender);
+                camera.stop_stream()
+            });
+            // on unwind, this stops the capture thread before the scope joins it
+            let _stop_guard = SetOnDrop(&stop);
+            let mut session = CaptureSession { frames, scope };
+            let result = f(&mut session);
+            drop(session);
+            stop.store(true, Ordering::SeqCst);
+            (result, capture.join())
+        });
+
+        match capture_result {
+            Ok(Ok(())) => Ok(result),
+            Ok(Err(why)) => Err(why),
+            Err(payload) => Err(NokhwaError::StreamShutdownError(format!(
+                "Capture thread panicked: {}",
+                panic_message(payload.as_ref())
+            ))),
+        }
+    }
+}
+
+// Stops the stream of the camera it holds when dropped, unless `stop_stream()` already did.
+struct StopStreamOnDrop<'a>(Option<&'a mut Camera>);
+
+impl<'a> StopStreamOnDrop<'a> {
+    fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+        match self.0.take() {
+            Some(camera) => camera.stop_stream(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Drop for StopStreamOnDrop<'a> {
+    fn drop(&mut self) {
+        let _stop_stream_err = self.stop_stream();
+    }
+}
+
+struct SetOnDrop<'a>(&'a AtomicBool);
+
+impl<'a> Drop for SetOnDrop<'a> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+fn session_capture_loop(
+    camera: &mut StopStreamOnDrop<'_>,
+    stop: &AtomicBool,
+    sender: &SyncSender<Result<Buffer, NokhwaError>>,
+) {
+    if let Some(camera) = camera.0.as_mut() {
+        // sending fails once the session is dropped, so a capture thread waiting on a full queue stops too
+        while !stop.load(Ordering::SeqCst) {
+            if sender.send(camera.frame()).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+fn set_last_error(last_error: &AtomicLock<Option<NokhwaError>>, why: NokhwaError) {
+    if let Ok(mut last) = last_error.lock() {
+        *last = Some(why);
+    }
+}
+
+fn decode_and_publish(
+    job: DecodeJob,
+    latest_frame: &AtomicLock<LatestFrame>,
+    last_error: &AtomicLock<Option<NokhwaError>>,
+    frame_signal: &FrameSignal,
+    frame_counters: &FrameCounters,
+) {
+    let decoded = match job.frame.decode_image::<RgbFormat>() {
+        Ok(decoded) => Some(decoded),
+        Err(why) => {
+            set_last_error(last_error, why);
+            None
+        }
+    };
+    let mut back_buffer = LatestFrame {
+        raw: Some(job.frame),
+        decoded,
+        captured_at: Some(job.captured_at),
+        sequence: job.sequence,
+    };
+    if let Ok(mut front_buffer) = latest_frame.lock() {
+        // another worker already published a newer frame
+        if front_buffer.sequence > back_buffer.sequence {
+            frame_counters.dropped(1);
+            return;
+        }
+        // neither poll took the frame being replaced
+        if front_buffer.raw.is_some() && front_buffer.decoded.is_some() {
+            frame_counters.dropped(1);
+        }
+        std::mem::swap(&mut *front_buffer, &mut back_buffer);
+    }
+    frame_signal.notify();
+}
+
+fn threaded_camera_loop(
+    mut camera: Camera,
+    latest_frame: AtomicLock<LatestFrame>,
+    last_error: AtomicLock<Option<NokhwaError>>,
+    requests: Receiver<ThreadedCameraRequest>,
+    frame_signal: Arc<FrameSignal>,
+    frame_counters: Arc<FrameCounters>,
+    die_bool: Arc<AtomicBool>,
+    decode_workers: usize,
+    thread_options: ThreadOptions,
+) -> Result<(), NokhwaError> {
+    let decode_queue = Arc::new(DecodeQueue {
+        jobs: Mutex::new(VecDeque::with_capacity(decode_workers)),
+        available: Condvar::new(),
+        capacity: decode_workers.max(1),
+        closed: AtomicBool::new(false),
+    });
+    let mut workers = Vec::with_capacity(decode_workers);
+    for worker in 0..decode_workers {
+        let decode_queue = decode_queue.clone();
+        let latest_frame_clone = latest_frame.clone();
+        let last_error_clone = last_error.clone();
+        let frame_signal_clone = frame_signal.clone();
+        let frame_counters_clone = frame_counters.clone();
+        let spawned = thread_options
+            .with_suffix(&format!("decode-{worker}"))
+            .spawn(move || {
+                while let Some(job) = decode_queue.pop() {
+                    decode_and_publish(
+                        job,
+                        &latest_frame_clone,
+                        &last_error_clone,
+                        &frame_signal_clone,
+                        &frame_counters_clone,
+                    );
+                }
+            });
+        // with no workers at all, the capture thread decodes
+        match spawned {
+            Ok((handle, warning)) => {
+                workers.push(handle);
+                if let Some(warning) = warning {
+                    set_last_error(&last_error, warning);
+                }
+            }
+            Err(why) => set_last_error(&last_error, why),
+        }
+    }
+
+    let mut sequence = 0;
+    while !die_bool.load(Ordering::SeqCst) {
+        // the receiving ends may have been dropped, in which case no one cares about the result.
+        let mut pending = requests.try_iter().peekable();
+        if pending.peek().is_some() {
+            // the stream may be reopened for a request, which is not a stall
+            let paused_at = frame_counters.pause();
+            for request in pending {
+                match request {
+                    ThreadedCameraRequest::CameraRequest(request, result) => {
+                        let _ = result.send(camera.set_camera_request(request));
+                    }
+                    ThreadedCameraRequest::CameraControl(id, control, result) => {
+                        let _ = result.send(camera.set_camera_control(id, control));
+                    }
+                }
+            }
+            frame_counters.resume(paused_at);
+        }
+
+        match camera.frame() {
+            Ok(frame) => {
+                frame_counters.received(frame.buffer().len());
+                sequence += 1;
+                let job = DecodeJob {
+                    frame,
+                    captured_at: Instant::now(),
+                    sequence,
+                };
+                if workers.is_empty() {
+                    decode_and_publish(
+                        job,
+                        &latest_frame,
+                        &last_error,
+                        &frame_signal,
+                        &frame_counters,
+                    );
+                } else {
+                    frame_counters.dropped(decode_queue.push(job));
+                }
+            }
+            Err(why) => set_last_error(&last_error, why),
+        }
+        if let Err(why) = frame_counters.restart_if_requested(&mut camera) {
+            set_last_error(&last_error, why);
+        }
+    }
+
+    // the workers finish the frames that are already queued
+    decode_queue.close();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    camera.stop_stream()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockCamera, MockStats};
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn assert_released(stats: &MockStats) {
+        assert_eq!(stats.open_streams.load(Ordering::SeqCst), 0);
+        assert_eq!(stats.devices.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn threaded_camera_create_destroy_stress() {
+        for _ in 0..100 {
+            let (camera, stats) = MockCamera::camera(Duration::from_millis(1));
+            let mut threaded = ThreadedCamera::with_custom(camera).unwrap();
+            assert!(threaded.wait_for_frame(TIMEOUT));
+            threaded.shutdown(TIMEOUT).unwrap();
+            drop(threaded);
+            assert_released(&stats);
+        }
+    }
+
+    #[test]
+    fn threaded_camera_drop_mid_frame_stress() {
+        for _ in 0..100 {
+            // dropped while the capture thread is inside `frame()`, with frames queued for the decode workers
+            let (camera, stats) = MockCamera::camera(Duration::from_micros(200));
+            drop(ThreadedCamera::with_decode_workers(camera, 2).unwrap());
+            assert_released(&stats);
+        }
+    }
+
+    #[test]
+    fn callback_camera_create_destroy_stress() {
+        for _ in 0..100 {
+            let (camera, stats) = MockCamera::camera(Duration::from_millis(1));
+            let mut callback = CallbackCamera::with_custom(camera, |_| {});
+            callback.open_stream().unwrap();
+            assert!(callback.wait_for_frame(TIMEOUT));
+            callback.shutdown(TIMEOUT).unwrap();
+            drop(callback);
+            assert_released(&stats);
+        }
+    }
+
+    #[test]
+    fn reset_frame_counters_clears_everything() {
+        let counters = FrameCounters::default();
+        counters.received(16);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(counters.stall());
+
+        let stats = counters.reset();
+        assert_eq!(stats.frames_received, 1);
+        assert_eq!(stats.bytes_received, 16);
+        assert!(stats.last_frame_at.is_some());
+        assert!(stats.stalled);
+        assert_eq!(stats.stalls, 1);
+        assert_eq!(counters.snapshot(), FrameStats::default());
+        // the stall clock still runs from the last frame
+        assert!(counters.idle().unwrap() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn shutdown_times_out_on_a_stuck_capture_thread() {
+        let (camera, stats) = MockCamera::camera(Duration::from_millis(500));
+        let mut threaded = ThreadedCamera::with_custom(camera).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(matches!(
+            threaded.shutdown(Duration::from_millis(10)),
+            Err(NokhwaError::StreamShutdownError(_))
+        ));
+        // the detached thread still stops the stream once its frame is in
+        let deadline = Instant::now() + TIMEOUT;
+        while stats.devices.load(Ordering::SeqCst) != 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        drop(threaded);
+        assert_released(&stats);
+    }
+}
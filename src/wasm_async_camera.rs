@@ -0,0 +1,222 @@
+/*
+ * Copyright 2022 l1npengtul <l1npengtul@protonmail.com> / The Nokhwa Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::Camera;
+use gloo_timers::future::TimeoutFuture;
+use js_sys::{Function, Promise, Reflect};
+use nokhwa_core::{
+    buffer::Buffer,
+    error::NokhwaError,
+    traits::CaptureTrait,
+    types::{
+        CameraFormat, CameraIndex, CameraInfo, ControlValueSetter, KnownCameraControl,
+        RequestedFormat,
+    },
+};
+use std::{
+    cell::{RefCell, RefMut},
+    future::{poll_fn, Future},
+    task::Poll,
+    time::Duration,
+};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlVideoElement;
+
+/// A camera for async code in the browser, where there are no threads to capture on.
+///
+/// Instead of waiting on a thread, [`frame()`](AsyncCamera::frame) waits for the browser to present the next video frame,
+/// with `requestVideoFrameCallback` on the [video element](AsyncCamera::set_video_element) if there is one and the browser
+/// supports it, and `requestAnimationFrame` otherwise, then reads the frame on the main thread. The methods are the same as
+/// those of the `AsyncCamera` on other targets, so the same code works on both.
+///
+/// The futures are cancel-safe: if one is dropped while waiting, nothing has been read from the camera yet.
+#[cfg_attr(feature = "docs-features", doc(cfg(feature = "output-async")))]
+pub struct AsyncCamera {
+    current_camera: CameraInfo,
+    camera: RefCell<Camera>,
+    video_element: RefCell<Option<HtmlVideoElement>>,
+}
+
+impl AsyncCamera {
+    /// Create a new `AsyncCamera` from a [`CameraIndex`] and [`format`]
+    ///
+    /// # Errors
+    /// This will error if the browser cannot create the camera (e.g. permission denied).
+    pub fn new(index: CameraIndex, format: RequestedFormat) -> Result<Self, NokhwaError> {
+        Ok(Self::with_custom(Camera::new(index, format)?))
+    }
+
+    /// Create a new `AsyncCamera` like [`new()`](AsyncCamera::new). There is no camera thread in the browser, so this only
+    /// exists for parity with other targets.
+    ///
+    /// # Errors
+    /// This will error if the browser cannot create the camera (e.g. permission denied).
+    pub async fn new_async(
+        index: CameraIndex,
+        format: RequestedFormat,
+    ) -> Result<Self, NokhwaError> {
+        Self::new(index, format)
+    }
+
+    /// Allows creation of an `AsyncCamera` from an existing [`Camera`], e.g. one with a custom backend.
+    ///
+    /// You **must** have set a format beforehand.
+    #[must_use]
+    pub fn with_custom(camera: Camera) -> Self {
+        AsyncCamera {
+            current_camera: camera.info().clone(),
+            camera: RefCell::new(camera),
+            video_element: RefCell::new(None),
+        }
+    }
+
+    /// Gets the camera information such as Name and Index as a [`CameraInfo`].
+    pub fn info(&self) -> &CameraInfo {
+        &self.current_camera
+    }
+
+    /// Sets the `<video>` element playing the camera's stream. [`frame()`](AsyncCamera::frame) then waits on its
+    /// `requestVideoFrameCallback`, so each frame is read once it is presented, instead of once per display refresh.
+    pub fn set_video_element(&self, video_element: Option<HtmlVideoElement>) {
+        *self.video_element.borrow_mut() = video_element;
+    }
+
+    /// Will open the camera stream with set parameters.
+    /// # Errors
+    /// If the browser fails to open the camera (e.g. already taken, busy, doesn't exist anymore) this will error.
+    pub async fn open_stream(&self) -> Result<(), NokhwaError> {
+        self.camera()?.open_stream()
+    }
+
+    /// Waits for the browser to present the next frame, then reads it.
+    /// # Errors
+    /// If the stream is not open or the frame could not be read, this will error.
+    pub async fn frame(&self) -> Result<Buffer, NokhwaError> {
+        self.next_video_frame().await?;
+        self.camera()?.frame()
+    }
+
+    /// Waits for the next frame like [`frame()`](AsyncCamera::frame), but for at most `timeout`, measured with the browser's timers.
+    /// # Errors
+    /// If no frame arrived within `timeout`, this will error with [`NokhwaError::TimeoutError`]. Otherwise, like [`frame()`](AsyncCamera::frame).
+    pub async fn frame_timeout(&self, timeout: Duration) -> Result<Buffer, NokhwaError> {
+        let millis = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+        let mut timer = Box::pin(TimeoutFuture::new(millis));
+        let mut frame = Box::pin(self.frame());
+        poll_fn(|cx| {
+            if let Poll::Ready(frame) = frame.as_mut().poll(cx) {
+                return Poll::Ready(frame);
+            }
+            timer.as_mut().poll(cx).map(|()| {
+                Err(NokhwaError::TimeoutError(format!(
+                    "No frame within {timeout:?}"
+                )))
+            })
+        })
+        .await
+    }
+
+    /// Will drop the stream.
+    /// # Errors
+    /// Please check the `Quirks` section of each backend.
+    pub async fn stop_stream(&self) -> Result<(), NokhwaError> {
+        self.camera()?.stop_stream()
+    }
+
+    /// Will set the current [`CameraFormat`], using a [`RequestedFormat.`]
+    ///
+    /// This will return the new [`CameraFormat`]
+    /// # Errors
+    /// If nothing fits the requested criteria, this will return an error.
+    pub async fn set_camera_request(
+        &self,
+        request: RequestedFormat,
+    ) -> Result<CameraFormat, NokhwaError> {
+        self.camera()?.set_camera_request(request)
+    }
+
+    /// Sets the control to `control` in the camera.
+    /// # Errors
+    /// If the `control` is not supported, the value is invalid (less than min, greater than max, not in step), or there was an error setting the control,
+    /// this will error.
+    pub async fn set_camera_control(
+        &self,
+        id: KnownCameraControl,
+        control: ControlValueSetter,
+    ) -> Result<(), NokhwaError> {
+        self.camera()?.set_camera_control(id, control)
+    }
+
+    // The camera is only borrowed between awaits, so this only fails if a method is called from inside another.
+    fn camera(&self) -> Result<RefMut<'_, Camera>, NokhwaError> {
+        self.camera
+            .try_borrow_mut()
+            .map_err(|why| NokhwaError::GeneralError(format!("Camera is busy: {why}")))
+    }
+
+    async fn next_video_frame(&self) -> Result<(), NokhwaError> {
+        let video_element = self.video_element.borrow().clone();
+        let mut scheduled = Ok(());
+        let presented = Promise::new(&mut |resolve, _| {
+            scheduled = schedule_video_frame(video_element.as_ref(), &resolve);
+        });
+        scheduled?;
+        JsFuture::from(presented)
+            .await
+            .map(|_| ())
+            .map_err(|why| NokhwaError::ReadFrameError(format!("{why:?}")))
+    }
+}
+
+impl Drop for AsyncCamera {
+    fn drop(&mut self) {
+        let camera = self.camera.get_mut();
+        if camera.is_stream_open() {
+            let _stop_stream_err = camera.stop_stream();
+        }
+    }
+}
+
+// Calls `callback` once the next frame is presented, see `AsyncCamera::next_video_frame()`.
+fn schedule_video_frame(
+    video_element: Option<&HtmlVideoElement>,
+    callback: &Function,
+) -> Result<(), NokhwaError> {
+    let to_error = |why: JsValue| NokhwaError::ReadFrameError(format!("{why:?}"));
+
+    // `requestVideoFrameCallback` is not in every browser yet, so it is looked up instead of bound
+    if let Some(video_element) = video_element {
+        let request_video_frame = Reflect::get(
+            video_element,
+            &JsValue::from_str("requestVideoFrameCallback"),
+        )
+        .map_err(to_error)?;
+        if let Some(request_video_frame) = request_video_frame.dyn_ref::<Function>() {
+            return request_video_frame
+                .call1(video_element, callback)
+                .map(|_| ())
+                .map_err(to_error);
+        }
+    }
+
+    let window = web_sys::window()
+        .ok_or_else(|| NokhwaError::ReadFrameError("No window to wait on".to_string()))?;
+    window
+        .request_animation_frame(callback)
+        .map(|_| ())
+        .map_err(to_error)
+}